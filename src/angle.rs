@@ -61,11 +61,13 @@ pub trait Angle where Self:
 
 /// Angle in degrees.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Deg<T>(pub T);
 
 /// Angle in radians.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Rad<T>(pub T);
 
@@ -294,6 +296,164 @@ impl<T: Float> From<Rad<T>> for Deg<T> {
 
 //----------------------------------------------------------------
 
+/// Angular range.
+///
+/// The arc starts at the `start` angle and extends counter-clockwise (towards positive angles) by the `sweep`.
+/// Sweeps of a full turn or more cover the whole circle.
+///
+/// ```
+/// use cvmath::angle::{ArcRange, Deg, Rad};
+///
+/// // From 350° to 10°, wrapping around zero
+/// let arc = ArcRange::between(Deg(350.0f64).to_rad(), Deg(10.0).to_rad());
+/// assert!(arc.contains(Deg(5.0).to_rad()));
+/// assert!(arc.contains(Deg(-5.0).to_rad()));
+/// assert!(!arc.contains(Deg(180.0).to_rad()));
+///
+/// // Angles outside the arc are clamped to the nearest end
+/// let clamped = arc.clamp(Deg(30.0).to_rad());
+/// assert!((clamped.to_deg().0 - 370.0).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ArcRange<T> {
+	pub start: Rad<T>,
+	pub sweep: Rad<T>,
+}
+
+impl<T: Float> ArcRange<T> {
+	/// Constructs a new arc from its start angle and sweep.
+	pub fn new(start: Rad<T>, sweep: Rad<T>) -> ArcRange<T> {
+		ArcRange { start, sweep }
+	}
+	/// Constructs the arc from the start angle counter-clockwise to the end angle.
+	pub fn between(start: Rad<T>, end: Rad<T>) -> ArcRange<T> {
+		let sweep = Rad(ArcRange::new(start, Rad::zero()).offset(end));
+		ArcRange { start, sweep }
+	}
+	/// Constructs the full circle starting at the angle.
+	pub fn full(start: Rad<T>) -> ArcRange<T> {
+		ArcRange { start, sweep: Rad::turn() }
+	}
+	/// Returns the end angle.
+	pub fn end(self) -> Rad<T> {
+		self.start + self.sweep
+	}
+	/// Returns if the arc covers the whole circle.
+	pub fn is_full(self) -> bool {
+		self.sweep >= Rad::turn()
+	}
+	/// Returns the counter-clockwise offset of the angle from the start in range `[0, 2π rad)`.
+	fn offset(self, angle: Rad<T>) -> T {
+		let turn = Rad::<T>::turn().0;
+		let delta = angle.0 - self.start.0;
+		let offset = delta - ::num::snap_floor(delta, turn);
+		if offset >= turn { T::zero() } else { offset }
+	}
+	/// Returns if the angle lies within the arc.
+	pub fn contains(self, angle: Rad<T>) -> bool {
+		self.is_full() || self.offset(angle) <= self.sweep.0
+	}
+	/// Clamps the angle to the arc.
+	///
+	/// Angles within the arc are returned unchanged, otherwise the nearest end of the arc is returned.
+	pub fn clamp(self, angle: Rad<T>) -> Rad<T> {
+		if self.contains(angle) {
+			return angle;
+		}
+		let offset = self.offset(angle);
+		let past_end = offset - self.sweep.0;
+		let before_start = Rad::<T>::turn().0 - offset;
+		if past_end < before_start { self.end() } else { self.start }
+	}
+	/// Intersects two arcs.
+	///
+	/// Returns `None` if the arcs do not overlap.
+	/// When the sweeps add up to more than a full turn the overlap may consist of two separate arcs, in which case the larger one is returned.
+	pub fn intersect(self, other: ArcRange<T>) -> Option<ArcRange<T>> {
+		if self.is_full() {
+			return Some(other);
+		}
+		if other.is_full() {
+			return Some(self);
+		}
+		// The overlap starting inside the other arc
+		let a = if other.contains(self.start) {
+			let sweep = self.sweep.0.min(other.sweep.0 - other.offset(self.start));
+			Some(ArcRange { start: self.start, sweep: Rad(sweep) })
+		}
+		else { None };
+		// The overlap starting inside this arc
+		let b = if self.contains(other.start) {
+			let sweep = other.sweep.0.min(self.sweep.0 - self.offset(other.start));
+			Some(ArcRange { start: other.start, sweep: Rad(sweep) })
+		}
+		else { None };
+		match (a, b) {
+			(Some(a), Some(b)) => Some(if b.sweep > a.sweep { b } else { a }),
+			(a, None) => a,
+			(None, b) => b,
+		}
+	}
+	/// Interpolates within the arc, `t = 0` returns the start and `t = 1` returns the end.
+	pub fn lerp(self, t: T) -> Rad<T> {
+		self.start + self.sweep * t
+	}
+	/// Returns the interpolation factor of the angle within the arc.
+	///
+	/// The inverse of `lerp`, the result is in range `[0, 1]` for angles within the arc.
+	pub fn inverse_lerp(self, angle: Rad<T>) -> T {
+		self.offset(angle) / self.sweep.0
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Field-of-view conversions.
+///
+/// The aspect ratio is the width divided by the height.
+/// Focal lengths and sensor sizes can be in any unit as long as they match, eg. a full-frame sensor is `36mm` wide and `24mm` high.
+///
+/// ```
+/// use cvmath::angle::{Deg, fov};
+///
+/// let fov_h = fov::vertical_to_horizontal(Deg(90.0f64), 2.0);
+/// assert!((fov_h.0 - 126.86989764584402).abs() < 1e-12);
+/// assert!((fov::horizontal_to_vertical(fov_h, 2.0).0 - 90.0).abs() < 1e-12);
+///
+/// // A 50mm lens on a full-frame sensor
+/// let fov_v: Deg<f64> = fov::from_focal_length(50.0, 24.0);
+/// assert!((fov_v.0 - 26.991466561591112).abs() < 1e-12);
+/// assert!((fov::to_focal_length(fov_v, 24.0) - 50.0).abs() < 1e-12);
+/// ```
+pub mod fov {
+	use num::CastFrom;
+	use super::Angle;
+
+	/// Converts the horizontal field-of-view to the vertical field-of-view.
+	pub fn horizontal_to_vertical<A: Angle>(fov_h: A, aspect: A::T) -> A {
+		let two = A::T::cast_from(2.0);
+		A::atan((fov_h / two).tan() / aspect) * two
+	}
+	/// Converts the vertical field-of-view to the horizontal field-of-view.
+	pub fn vertical_to_horizontal<A: Angle>(fov_v: A, aspect: A::T) -> A {
+		let two = A::T::cast_from(2.0);
+		A::atan((fov_v / two).tan() * aspect) * two
+	}
+	/// Returns the field-of-view spanned by the sensor size at the focal length.
+	pub fn from_focal_length<A: Angle>(focal_length: A::T, sensor_size: A::T) -> A {
+		let two = A::T::cast_from(2.0);
+		A::atan(sensor_size / (two * focal_length)) * two
+	}
+	/// Returns the focal length for which the sensor size spans the field-of-view.
+	pub fn to_focal_length<A: Angle>(fov: A, sensor_size: A::T) -> A::T {
+		let two = A::T::cast_from(2.0);
+		sensor_size / (two * (fov / two).tan())
+	}
+}
+
+//----------------------------------------------------------------
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -326,6 +486,33 @@ mod tests {
 		assert_eq!(Deg(90f32), "90".parse().unwrap());
 	}
 
+	#[test]
+	fn arc_range() {
+		let arc = ArcRange::new(Deg(300.0f64).to_rad(), Deg(90.0).to_rad());
+		assert!(arc.contains(Deg(-30.0).to_rad()));
+		assert!(arc.contains(Deg(750.0).to_rad()));
+		assert!(!arc.contains(Deg(60.0).to_rad()));
+		assert_eq!(arc.end(), arc.clamp(Deg(40.0).to_rad()));
+		assert_eq!(arc.start, arc.clamp(Deg(260.0).to_rad()));
+		assert_eq!(arc.start, arc.lerp(0.0));
+		assert!((arc.inverse_lerp(arc.lerp(0.25)) - 0.25).abs() < 1e-12);
+
+		// Overlapping across zero
+		let other = ArcRange::between(Deg(0.0).to_rad(), Deg(120.0).to_rad());
+		let overlap = arc.intersect(other).unwrap();
+		assert_eq!(other.start, overlap.start);
+		assert!((overlap.sweep.to_deg().0 - 30.0).abs() < 1e-9);
+		assert_eq!(overlap, other.intersect(arc).unwrap());
+
+		// Disjoint
+		let disjoint = ArcRange::new(Deg(90.0).to_rad(), Deg(90.0).to_rad());
+		assert_eq!(None, arc.intersect(disjoint));
+
+		// Full circle
+		assert_eq!(Some(arc), ArcRange::full(Rad(1.0)).intersect(arc));
+		assert!(ArcRange::full(Rad(1.0)).contains(Rad(-100.0)));
+	}
+
 	#[test]
 	fn from() {
 		fn rad<A: Into<Rad<f64>>>(_: A) {}
@@ -0,0 +1,215 @@
+/*!
+Approximate equality and test assertions.
+
+The `assert_approx_eq!` and `assert_ulps_eq!` macros compare the crate's scalars, angles, vectors, matrices and colors component-wise and print both sides when they differ.
+
+### Examples
+
+```
+#[macro_use]
+extern crate cvmath;
+use cvmath::prelude::{Vec2, Mat2, Deg, Rad};
+
+# fn main() {
+assert_approx_eq!(Vec2(0.1 + 0.2, 1.0), Vec2(0.3, 1.0));
+assert_approx_eq!(Mat2::<f64>::rotate(Deg(90.0)), Mat2::new(0.0, -1.0, 1.0, 0.0), 1e-15);
+assert_ulps_eq!(Rad::<f32>::half().to_deg(), Deg(180.0));
+assert_ulps_eq!(1.0f32, 1.0 + f32::EPSILON, 1);
+# }
+```
+
+A failed assertion prints both sides along with the tolerance:
+
+```text
+assertion `left ≈ right` failed (epsilon: 1e-5)
+  left: (1.0,2.0)
+ right: (1.0,2.1)
+```
+*/
+
+use std::fmt;
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use vecn::VecN;
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+use angle::{Deg, Rad};
+use color::{Rgb, Rgba};
+
+/// Approximate equality comparisons.
+pub trait ApproxEq {
+	/// The tolerance type, the underlying scalar type.
+	type Epsilon: Copy + fmt::Debug;
+	/// The default tolerance used by `assert_approx_eq!`.
+	fn default_epsilon() -> Self::Epsilon;
+	/// Returns true if all the components differ by at most `epsilon` in absolute terms or relative to their magnitude.
+	fn approx_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+	/// Returns true if all the components are at most `max_ulps` representable floats apart.
+	fn ulps_eq(&self, other: &Self, max_ulps: u32) -> bool;
+}
+
+macro_rules! impl_float {
+	($ty:ident $bits:ident $epsilon:expr) => {
+		impl ApproxEq for $ty {
+			type Epsilon = $ty;
+			fn default_epsilon() -> $ty {
+				$epsilon
+			}
+			fn approx_eq(&self, other: &$ty, epsilon: $ty) -> bool {
+				if self == other {
+					return true;
+				}
+				let diff = (self - other).abs();
+				diff <= epsilon || diff <= epsilon * self.abs().max(other.abs())
+			}
+			fn ulps_eq(&self, other: &$ty, max_ulps: u32) -> bool {
+				if self == other {
+					return true;
+				}
+				if self.is_nan() || other.is_nan() || self.is_sign_negative() != other.is_sign_negative() {
+					return false;
+				}
+				let (a, b) = (self.to_bits() as $bits, other.to_bits() as $bits);
+				(a - b).unsigned_abs() <= max_ulps as _
+			}
+		}
+	};
+}
+
+impl_float!(f32 i32 1e-5);
+impl_float!(f64 i64 1e-12);
+
+macro_rules! impl_approx_eq {
+	($ty:ident { $($field:tt),+ }) => {
+		impl<T: ApproxEq> ApproxEq for $ty<T> {
+			type Epsilon = T::Epsilon;
+			fn default_epsilon() -> T::Epsilon {
+				T::default_epsilon()
+			}
+			fn approx_eq(&self, other: &$ty<T>, epsilon: T::Epsilon) -> bool {
+				$(self.$field.approx_eq(&other.$field, epsilon))&&+
+			}
+			fn ulps_eq(&self, other: &$ty<T>, max_ulps: u32) -> bool {
+				$(self.$field.ulps_eq(&other.$field, max_ulps))&&+
+			}
+		}
+	};
+}
+
+impl_approx_eq!(Vec1 { x });
+impl_approx_eq!(Vec2 { x, y });
+impl_approx_eq!(Vec3 { x, y, z });
+impl_approx_eq!(Vec4 { x, y, z, w });
+
+impl_approx_eq!(Mat2 { a11, a12, a21, a22 });
+impl_approx_eq!(Affine2 { a11, a12, a13, a21, a22, a23 });
+impl_approx_eq!(Mat3 { a11, a12, a13, a21, a22, a23, a31, a32, a33 });
+impl_approx_eq!(Affine3 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34 });
+impl_approx_eq!(Mat4 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34, a41, a42, a43, a44 });
+
+impl_approx_eq!(Deg { 0 });
+impl_approx_eq!(Rad { 0 });
+
+impl_approx_eq!(Rgb { r, g, b });
+impl_approx_eq!(Rgba { r, g, b, a });
+
+impl<T: ApproxEq, const N: usize> ApproxEq for VecN<T, N> {
+	type Epsilon = T::Epsilon;
+	fn default_epsilon() -> T::Epsilon {
+		T::default_epsilon()
+	}
+	fn approx_eq(&self, other: &VecN<T, N>, epsilon: T::Epsilon) -> bool {
+		self.0.iter().zip(&other.0).all(|(a, b)| a.approx_eq(b, epsilon))
+	}
+	fn ulps_eq(&self, other: &VecN<T, N>, max_ulps: u32) -> bool {
+		self.0.iter().zip(&other.0).all(|(a, b)| a.ulps_eq(b, max_ulps))
+	}
+}
+
+#[doc(hidden)]
+pub fn default_epsilon<T: ApproxEq>(_: &T) -> T::Epsilon {
+	T::default_epsilon()
+}
+
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn assert_failed<T: fmt::Debug, E: fmt::Debug>(tolerance: &str, value: E, left: &T, right: &T) -> ! {
+	panic!("assertion `left ≈ right` failed ({}: {:?})\n  left: {:?}\n right: {:?}", tolerance, value, left, right)
+}
+
+/// Asserts that two values are approximately equal, see [`ApproxEq::approx_eq`](approx_eq/trait.ApproxEq.html#tymethod.approx_eq).
+///
+/// The tolerance defaults to `ApproxEq::default_epsilon()`.
+#[macro_export]
+macro_rules! assert_approx_eq {
+	($left:expr, $right:expr $(,)?) => {
+		match (&$left, &$right) {
+			(left, right) => {
+				let epsilon = $crate::approx_eq::default_epsilon(left);
+				if !$crate::approx_eq::ApproxEq::approx_eq(left, right, epsilon) {
+					$crate::approx_eq::assert_failed("epsilon", epsilon, left, right);
+				}
+			}
+		}
+	};
+	($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+		match (&$left, &$right) {
+			(left, right) => {
+				let epsilon = $epsilon;
+				if !$crate::approx_eq::ApproxEq::approx_eq(left, right, epsilon) {
+					$crate::approx_eq::assert_failed("epsilon", epsilon, left, right);
+				}
+			}
+		}
+	};
+}
+
+/// Asserts that two values are within a number of representable floats, see [`ApproxEq::ulps_eq`](approx_eq/trait.ApproxEq.html#tymethod.ulps_eq).
+///
+/// The tolerance defaults to `4` ulps.
+///
+/// The `approx` crate exports a macro with the same name which takes its tolerance as `max_ulps = ...`.
+/// When both crates are imported with `#[macro_use]` the last import shadows the other, invoke this one by path to avoid the clash:
+///
+/// ```
+/// cvmath::assert_ulps_eq!(1.0f32, 1.0f32);
+/// cvmath::assert_ulps_eq!(1.0f64, 1.0 + f64::EPSILON, 1);
+/// ```
+#[macro_export]
+macro_rules! assert_ulps_eq {
+	($left:expr, $right:expr $(,)?) => {
+		$crate::assert_ulps_eq!($left, $right, 4)
+	};
+	($left:expr, $right:expr, $max_ulps:expr $(,)?) => {
+		match (&$left, &$right) {
+			(left, right) => {
+				let max_ulps: u32 = $max_ulps;
+				if !$crate::approx_eq::ApproxEq::ulps_eq(left, right, max_ulps) {
+					$crate::approx_eq::assert_failed("max ulps", max_ulps, left, right);
+				}
+			}
+		}
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn floats() {
+		assert!(1.0f64.approx_eq(&(1.0 + 1e-13), 1e-12));
+		assert!(!1.0f64.approx_eq(&1.001, 1e-12));
+		assert!(1e10f64.approx_eq(&(1e10 + 1e-3), 1e-12));
+		assert!(1.0f32.ulps_eq(&(1.0 + f32::EPSILON), 1));
+		assert!(!1.0f32.ulps_eq(&-1.0, 4));
+		assert!(!f64::NAN.ulps_eq(&f64::NAN, 4));
+		assert!(0.0f64.ulps_eq(&-0.0, 0));
+	}
+
+	#[test]
+	#[should_panic(expected = "assertion `left ≈ right` failed (epsilon: 1e-5)\n  left: (1.0,2.0)\n right: (1.0,2.1)")]
+	fn failure_message() {
+		assert_approx_eq!(Vec2(1.0f32, 2.0), Vec2(1.0, 2.1));
+	}
+}
@@ -0,0 +1,66 @@
+/*!
+approx support.
+
+Vectors and matrices compare approximately equal when all their components do.
+
+```
+# #[macro_use] extern crate approx;
+# extern crate cvmath;
+# use cvmath::prelude::{Vec2, Mat2, Deg, Rad};
+# fn main() {
+assert_relative_eq!(Vec2(0.1 + 0.2, 1.0), Vec2(0.3, 1.0));
+assert_abs_diff_eq!(Mat2::<f64>::rotate(Deg(90.0)), Mat2::new(0.0, -1.0, 1.0, 0.0));
+assert_ulps_eq!(Rad::<f32>::half().to_deg(), Deg(180.0));
+# }
+```
+*/
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+use angle::{Deg, Rad};
+
+macro_rules! impl_approx {
+	($ty:ident { $($field:tt),+ }) => {
+		impl<T: AbsDiffEq> AbsDiffEq for $ty<T> where T::Epsilon: Copy {
+			type Epsilon = T::Epsilon;
+			fn default_epsilon() -> T::Epsilon {
+				T::default_epsilon()
+			}
+			fn abs_diff_eq(&self, other: &$ty<T>, epsilon: T::Epsilon) -> bool {
+				$(self.$field.abs_diff_eq(&other.$field, epsilon))&&+
+			}
+		}
+		impl<T: RelativeEq> RelativeEq for $ty<T> where T::Epsilon: Copy {
+			fn default_max_relative() -> T::Epsilon {
+				T::default_max_relative()
+			}
+			fn relative_eq(&self, other: &$ty<T>, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+				$(self.$field.relative_eq(&other.$field, epsilon, max_relative))&&+
+			}
+		}
+		impl<T: UlpsEq> UlpsEq for $ty<T> where T::Epsilon: Copy {
+			fn default_max_ulps() -> u32 {
+				T::default_max_ulps()
+			}
+			fn ulps_eq(&self, other: &$ty<T>, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+				$(self.$field.ulps_eq(&other.$field, epsilon, max_ulps))&&+
+			}
+		}
+	};
+}
+
+impl_approx!(Vec1 { x });
+impl_approx!(Vec2 { x, y });
+impl_approx!(Vec3 { x, y, z });
+impl_approx!(Vec4 { x, y, z, w });
+
+impl_approx!(Mat2 { a11, a12, a21, a22 });
+impl_approx!(Affine2 { a11, a12, a13, a21, a22, a23 });
+impl_approx!(Mat3 { a11, a12, a13, a21, a22, a23, a31, a32, a33 });
+impl_approx!(Affine3 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34 });
+impl_approx!(Mat4 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34, a41, a42, a43, a44 });
+
+impl_approx!(Deg { 0 });
+impl_approx!(Rad { 0 });
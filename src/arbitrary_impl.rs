@@ -0,0 +1,59 @@
+/*!
+Arbitrary support.
+
+Vectors, masks and angles implement `Arbitrary` when their components do.
+*/
+
+use arbitrary::{Arbitrary, Unstructured, Result};
+use arbitrary::size_hint;
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use angle::{Deg, Rad};
+
+macro_rules! arbitrary_vec {
+	($vec:ident $N:tt { $($field:ident),+ }) => {
+		impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for $vec<T> {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<$vec<T>> {
+				Ok($vec { $($field: u.arbitrary()?),+ })
+			}
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				size_hint::and_all(&[T::size_hint(depth); $N])
+			}
+		}
+	};
+}
+
+arbitrary_vec!(Vec1 1 { x });
+arbitrary_vec!(Vec2 2 { x, y });
+arbitrary_vec!(Vec3 3 { x, y, z });
+arbitrary_vec!(Vec4 4 { x, y, z, w });
+
+macro_rules! arbitrary_angle {
+	($ty:ident) => {
+		impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for $ty<T> {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<$ty<T>> {
+				u.arbitrary().map($ty)
+			}
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				T::size_hint(depth)
+			}
+		}
+	};
+}
+
+arbitrary_angle!(Deg);
+arbitrary_angle!(Rad);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bools::Bool2;
+
+	#[test]
+	fn unstructured() {
+		let data = [1u8, 0, 0, 0, 2, 0, 0, 0, 1, 0];
+		let mut u = Unstructured::new(&data);
+		assert_eq!(Vec2(1i32, 2), u.arbitrary().unwrap());
+		assert_eq!(Bool2(true, false), u.arbitrary().unwrap());
+	}
+}
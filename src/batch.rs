@@ -0,0 +1,311 @@
+/*!
+Batch operations over slices.
+
+These kernels are faster than calling the per-element API in a loop, the work is arranged in
+structure-of-arrays chunks (see [`wide`](../wide/index.html)) which the compiler vectorizes.
+
+All functions panic if the slice lengths don't match.
+
+With the `rayon` feature the [`par`](par/index.html) module provides parallel versions for large inputs.
+
+### Examples
+
+```
+# use cvmath::prelude::{Vec3, Affine3, Mat4};
+use cvmath::batch;
+
+let points = [Vec3(1.0, 2.0, 3.0), Vec3(-1.0, 0.0, 4.0), Vec3(0.0, 0.0, 0.0)];
+let translate = Affine3::new(
+    1.0, 0.0, 0.0, 10.0,
+    0.0, 1.0, 0.0, 20.0,
+    0.0, 0.0, 1.0, 30.0,
+);
+
+let mut result = [Vec3::default(); 3];
+batch::transform_points(&translate, &points, &mut result);
+assert_eq!([Vec3(11.0, 22.0, 33.0), Vec3(9.0, 20.0, 34.0), Vec3(10.0, 20.0, 30.0)], result);
+
+// Projective matrices include the homogeneous divide
+let half = Mat4::new(
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 1.0, 0.0,
+    0.0, 0.0, 0.0, 2.0,
+);
+batch::transform_points(&half, &points, &mut result);
+assert_eq!([Vec3(0.5, 1.0, 1.5), Vec3(-0.5, 0.0, 2.0), Vec3(0.0, 0.0, 0.0)], result);
+
+let mut lengths = [0.0; 3];
+batch::len(&points, &mut lengths);
+assert_eq!([14f64.sqrt(), 17f64.sqrt(), 0.0], lengths);
+```
+*/
+
+use std::array;
+
+use num::{Scalar, Float, Extrema};
+use vec::Vec3;
+use mat::{Mat3, Affine3, Mat4};
+use wide::Vec3x8;
+use bounds::Bounds;
+
+const LANES: usize = 8;
+
+/// Matrices which transform points in [`transform_points`](fn.transform_points.html).
+pub trait TransformPoints<T>: Copy {
+	/// Transforms a single point.
+	fn transform_point(&self, pt: Vec3<T>) -> Vec3<T>;
+	/// Transforms the points in every lane.
+	fn transform_wide(&self, pts: Vec3x8<T>) -> Vec3x8<T>;
+}
+
+impl<T: Scalar> TransformPoints<T> for Mat3<T> {
+	fn transform_point(&self, pt: Vec3<T>) -> Vec3<T> {
+		*self * pt
+	}
+	fn transform_wide(&self, pts: Vec3x8<T>) -> Vec3x8<T> {
+		Vec3x8 {
+			x: array::from_fn(|i| self.a11 * pts.x[i] + self.a12 * pts.y[i] + self.a13 * pts.z[i]),
+			y: array::from_fn(|i| self.a21 * pts.x[i] + self.a22 * pts.y[i] + self.a23 * pts.z[i]),
+			z: array::from_fn(|i| self.a31 * pts.x[i] + self.a32 * pts.y[i] + self.a33 * pts.z[i]),
+		}
+	}
+}
+
+impl<T: Scalar> TransformPoints<T> for Affine3<T> {
+	fn transform_point(&self, pt: Vec3<T>) -> Vec3<T> {
+		*self * pt
+	}
+	fn transform_wide(&self, pts: Vec3x8<T>) -> Vec3x8<T> {
+		Vec3x8 {
+			x: array::from_fn(|i| pts.x[i] * self.a11 + pts.y[i] * self.a12 + pts.z[i] * self.a13 + self.a14),
+			y: array::from_fn(|i| pts.x[i] * self.a21 + pts.y[i] * self.a22 + pts.z[i] * self.a23 + self.a24),
+			z: array::from_fn(|i| pts.x[i] * self.a31 + pts.y[i] * self.a32 + pts.z[i] * self.a33 + self.a34),
+		}
+	}
+}
+
+/// Includes the homogeneous divide, see [`Mat4::transform_point`](../mat/struct.Mat4.html#method.transform_point).
+impl<T: Scalar> TransformPoints<T> for Mat4<T> {
+	fn transform_point(&self, pt: Vec3<T>) -> Vec3<T> {
+		Mat4::transform_point(self, pt)
+	}
+	fn transform_wide(&self, pts: Vec3x8<T>) -> Vec3x8<T> {
+		let w: [T; LANES] = array::from_fn(|i| self.a41 * pts.x[i] + self.a42 * pts.y[i] + self.a43 * pts.z[i] + self.a44);
+		let hdiv = |i: usize, c: T| if w[i] != T::zero() { c / w[i] } else { c };
+		Vec3x8 {
+			x: array::from_fn(|i| hdiv(i, self.a11 * pts.x[i] + self.a12 * pts.y[i] + self.a13 * pts.z[i] + self.a14)),
+			y: array::from_fn(|i| hdiv(i, self.a21 * pts.x[i] + self.a22 * pts.y[i] + self.a23 * pts.z[i] + self.a24)),
+			z: array::from_fn(|i| hdiv(i, self.a31 * pts.x[i] + self.a32 * pts.y[i] + self.a33 * pts.z[i] + self.a34)),
+		}
+	}
+}
+
+/// Transforms the points by the matrix.
+///
+/// Projective `Mat4` transforms include the homogeneous divide.
+pub fn transform_points<T: Scalar, M: TransformPoints<T>>(mat: &M, src: &[Vec3<T>], dst: &mut [Vec3<T>]) {
+	assert_eq!(src.len(), dst.len());
+	let n = src.len() - src.len() % LANES;
+	for (src, dst) in src[..n].chunks(LANES).zip(dst[..n].chunks_mut(LANES)) {
+		mat.transform_wide(Vec3x8::gather(src)).scatter(dst);
+	}
+	for (src, dst) in src[n..].iter().zip(&mut dst[n..]) {
+		*dst = mat.transform_point(*src);
+	}
+}
+
+/// Calculates the dot products.
+pub fn dot<T: Scalar>(lhs: &[Vec3<T>], rhs: &[Vec3<T>], out: &mut [T]) {
+	assert_eq!(lhs.len(), rhs.len());
+	assert_eq!(lhs.len(), out.len());
+	let n = lhs.len() - lhs.len() % LANES;
+	for ((lhs, rhs), out) in lhs[..n].chunks(LANES).zip(rhs[..n].chunks(LANES)).zip(out[..n].chunks_mut(LANES)) {
+		out.copy_from_slice(&Vec3x8::gather(lhs).dot(Vec3x8::gather(rhs)));
+	}
+	for ((lhs, rhs), out) in lhs[n..].iter().zip(&rhs[n..]).zip(&mut out[n..]) {
+		*out = lhs.dot(*rhs);
+	}
+}
+
+/// Calculates the cross products.
+pub fn cross<T: Scalar>(lhs: &[Vec3<T>], rhs: &[Vec3<T>], out: &mut [Vec3<T>]) {
+	assert_eq!(lhs.len(), rhs.len());
+	assert_eq!(lhs.len(), out.len());
+	let n = lhs.len() - lhs.len() % LANES;
+	for ((lhs, rhs), out) in lhs[..n].chunks(LANES).zip(rhs[..n].chunks(LANES)).zip(out[..n].chunks_mut(LANES)) {
+		Vec3x8::gather(lhs).cross(Vec3x8::gather(rhs)).scatter(out);
+	}
+	for ((lhs, rhs), out) in lhs[n..].iter().zip(&rhs[n..]).zip(&mut out[n..]) {
+		*out = lhs.cross(*rhs);
+	}
+}
+
+/// Calculates the lengths.
+pub fn len<T: Float>(src: &[Vec3<T>], out: &mut [T]) {
+	assert_eq!(src.len(), out.len());
+	let n = src.len() - src.len() % LANES;
+	for (src, out) in src[..n].chunks(LANES).zip(out[..n].chunks_mut(LANES)) {
+		out.copy_from_slice(&Vec3x8::gather(src).len());
+	}
+	for (src, out) in src[n..].iter().zip(&mut out[n..]) {
+		*out = src.len();
+	}
+}
+
+/// Normalizes the vectors.
+pub fn norm<T: Float>(src: &[Vec3<T>], dst: &mut [Vec3<T>]) {
+	assert_eq!(src.len(), dst.len());
+	let n = src.len() - src.len() % LANES;
+	for (src, dst) in src[..n].chunks(LANES).zip(dst[..n].chunks_mut(LANES)) {
+		Vec3x8::gather(src).norm().scatter(dst);
+	}
+	for (src, dst) in src[n..].iter().zip(&mut dst[n..]) {
+		*dst = src.norm();
+	}
+}
+
+/// Calculates the bounds enclosing the points.
+///
+/// Returns `None` if there are no points.
+pub fn bounds<V: Copy + Extrema>(points: &[V]) -> Option<Bounds<V>> {
+	let (&first, rest) = points.split_first()?;
+	Some(rest.iter().fold(Bounds::point(first), |bounds, &pt| bounds.union(Bounds::point(pt))))
+}
+
+/// Calculates the average of the points.
+///
+/// Returns `None` if there are no points.
+pub fn centroid<T: Float>(points: &[Vec3<T>]) -> Option<Vec3<T>> {
+	if points.is_empty() {
+		return None;
+	}
+	let sum = points.iter().fold(Vec3::<T>::origin(), |acc, &pt| acc + pt);
+	Some(sum / T::cast_from(points.len() as f64))
+}
+
+//----------------------------------------------------------------
+
+/// Parallel batch operations.
+///
+/// The slices are split in chunks which are processed with the sequential kernels on the rayon thread pool.
+///
+/// ```
+/// # extern crate cvmath;
+/// # use cvmath::prelude::{Vec3, Mat3, Cuboid, Point3};
+/// use cvmath::batch::par;
+///
+/// # fn main() {
+/// let points: Vec<Vec3<f64>> = (0..100_000).map(|i| Vec3(i as f64, 0.0, -(i as f64))).collect();
+///
+/// let mut result = vec![Vec3::default(); points.len()];
+/// par::transform_points(&Mat3::<f64>::identity(), &points, &mut result);
+/// assert_eq!(points, result);
+///
+/// assert_eq!(Some(Cuboid(Point3(0.0, 0.0, -99_999.0), Point3(99_999.0, 0.0, 0.0))), par::bounds(&points));
+/// assert_eq!(Some(Vec3(49_999.5, 0.0, -49_999.5)), par::centroid(&points));
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub mod par {
+	use rayon::prelude::*;
+
+	use num::{Scalar, Float, Extrema};
+	use vec::Vec3;
+	use bounds::Bounds;
+	use super::TransformPoints;
+
+	const CHUNK: usize = 4096;
+
+	/// Transforms the points by the matrix.
+	pub fn transform_points<T: Scalar + Send + Sync, M: TransformPoints<T> + Sync>(mat: &M, src: &[Vec3<T>], dst: &mut [Vec3<T>]) {
+		assert_eq!(src.len(), dst.len());
+		src.par_chunks(CHUNK).zip(dst.par_chunks_mut(CHUNK)).for_each(|(src, dst)| super::transform_points(mat, src, dst));
+	}
+
+	/// Calculates the dot products.
+	pub fn dot<T: Scalar + Send + Sync>(lhs: &[Vec3<T>], rhs: &[Vec3<T>], out: &mut [T]) {
+		assert_eq!(lhs.len(), rhs.len());
+		assert_eq!(lhs.len(), out.len());
+		lhs.par_chunks(CHUNK).zip(rhs.par_chunks(CHUNK)).zip(out.par_chunks_mut(CHUNK)).for_each(|((lhs, rhs), out)| super::dot(lhs, rhs, out));
+	}
+
+	/// Calculates the lengths.
+	pub fn len<T: Float + Send + Sync>(src: &[Vec3<T>], out: &mut [T]) {
+		assert_eq!(src.len(), out.len());
+		src.par_chunks(CHUNK).zip(out.par_chunks_mut(CHUNK)).for_each(|(src, out)| super::len(src, out));
+	}
+
+	/// Normalizes the vectors.
+	pub fn norm<T: Float + Send + Sync>(src: &[Vec3<T>], dst: &mut [Vec3<T>]) {
+		assert_eq!(src.len(), dst.len());
+		src.par_chunks(CHUNK).zip(dst.par_chunks_mut(CHUNK)).for_each(|(src, dst)| super::norm(src, dst));
+	}
+
+	/// Calculates the bounds enclosing the points.
+	///
+	/// Returns `None` if there are no points.
+	pub fn bounds<V: Copy + Extrema + Send + Sync>(points: &[V]) -> Option<Bounds<V>> {
+		points.par_chunks(CHUNK).filter_map(super::bounds).reduce_with(Bounds::union)
+	}
+
+	/// Calculates the average of the points.
+	///
+	/// Returns `None` if there are no points.
+	pub fn centroid<T: Float + Send + Sync>(points: &[Vec3<T>]) -> Option<Vec3<T>> {
+		if points.is_empty() {
+			return None;
+		}
+		let sum = points.par_chunks(CHUNK)
+			.map(|chunk| chunk.iter().fold(Vec3::<T>::origin(), |acc, &pt| acc + pt))
+			.reduce(Vec3::<T>::origin, |lhs, rhs| lhs + rhs);
+		Some(sum / T::cast_from(points.len() as f64))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_scalar() {
+		let a: Vec<Vec3<f64>> = (0..19).map(|i| Vec3(i as f64, 2.0 - i as f64, 0.25 * i as f64)).collect();
+		let b: Vec<Vec3<f64>> = (0..19).map(|i| Vec3(1.0, i as f64, -3.0)).collect();
+
+		let mut dots = vec![0.0; 19];
+		dot(&a, &b, &mut dots);
+		let mut crosses = vec![Vec3::default(); 19];
+		cross(&a, &b, &mut crosses);
+		let mut norms = vec![Vec3::default(); 19];
+		norm(&a, &mut norms);
+		let mat = Mat3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+		let mut points = vec![Vec3::default(); 19];
+		transform_points(&mat, &a, &mut points);
+
+		for (i, (&a, &b)) in a.iter().zip(&b).enumerate() {
+			assert_eq!(a.dot(b), dots[i]);
+			assert_eq!(a.cross(b), crosses[i]);
+			assert_eq!(a.norm(), norms[i]);
+			assert_eq!(mat * a, points[i]);
+		}
+
+		assert_eq!(None, bounds::<Vec3<f64>>(&[]));
+		assert_eq!(Some(Bounds(Vec3(0.0, -16.0, 0.0), Vec3(18.0, 2.0, 4.5))), bounds(&a));
+		assert_eq!(Some(Vec3(9.0, -7.0, 2.25)), centroid(&a));
+	}
+
+	#[test]
+	fn transform_points_mat4() {
+		let points: Vec<Vec3<f64>> = (0..19).map(|i| Vec3(i as f64 - 9.0, 0.5 * i as f64, -1.0 - i as f64)).collect();
+		let proj = Mat4::perspective_rh(::angle::Deg(60.0), 1.5, 0.1, 100.0, ::projection::DepthRange::ZeroToOne);
+		let mut result = vec![Vec3::default(); points.len()];
+		transform_points(&proj, &points, &mut result);
+		for (&pt, &r) in points.iter().zip(&result) {
+			assert_eq!(proj.transform_point(pt), r);
+		}
+		// Points on the eye plane have w = 0 and skip the divide
+		let mut result = [Vec3::default(); 1];
+		transform_points(&proj, &[Vec3(1.0, 2.0, 0.0)], &mut result);
+		assert_eq!(proj.transform_point(Vec3(1.0, 2.0, 0.0)), result[0]);
+	}
+}
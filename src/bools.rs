@@ -47,13 +47,42 @@ assert!(Bool2 { x: true, y: true }.all());
 assert!(Bool2 { x: false, y: false }.none());
 ```
 
+## Ordering
+
+The derived `PartialOrd` on vectors is lexicographic: `x` is compared first and only on a tie is `y` compared, and so on.
+This means `Vec2(1, 9) < Vec2(2, 0)` even though `9 > 0`, which is rarely what is meant for points.
+These helpers make the intent explicit:
+
+`cmp_lex(self, rhs)`: Compares lexicographically, the same as the derived `PartialOrd`.
+
+`lt_all(self, rhs)`, `le_all(self, rhs)`, `gt_all(self, rhs)`, `ge_all(self, rhs)`: Returns `true` if the comparison holds for all the components.
+
+`partial_dominance(self, rhs)`: Compares component-wise, returns `None` if some components compare less and others greater.
+
+### Examples
+
+```
+# use cvmath::prelude::{Vec2};
+use std::cmp::Ordering;
+
+assert!(Vec2(1, 9) < Vec2(2, 0));
+assert_eq!(Some(Ordering::Less), Vec2(1, 9).cmp_lex(Vec2(2, 0)));
+assert!(!Vec2(1, 9).lt_all(Vec2(2, 0)));
+assert!(Vec2(1, 0).le_all(Vec2(2, 0)));
+
+assert_eq!(None, Vec2(1, 9).partial_dominance(Vec2(2, 0)));
+assert_eq!(Some(Ordering::Less), Vec2(1, 0).partial_dominance(Vec2(2, 0)));
+assert_eq!(Some(Ordering::Equal), Vec2(2, 0).partial_dominance(Vec2(2, 0)));
+```
+
 */
 
-use std::ops;
+use std::{cmp, ops};
 
-use vec::{Vec2, Vec3, Vec4};
+use vec::{Vec1, Vec2, Vec3, Vec4};
 use num::Float;
 
+pub type Bool1 = Vec1<bool>;
 pub type Bool2 = Vec2<bool>;
 pub type Bool3 = Vec3<bool>;
 pub type Bool4 = Vec4<bool>;
@@ -104,6 +133,52 @@ macro_rules! bools {
 			}
 		}
 
+		//----------------------------------------------------------------
+		// Ordering
+
+		impl<T: PartialOrd> $vec<T> {
+			/// Compares the vectors lexicographically, the same as the derived `PartialOrd`.
+			pub fn cmp_lex(self, rhs: $vec<T>) -> Option<cmp::Ordering> {
+				self.partial_cmp(&rhs)
+			}
+			/// Returns `true` if all the left-hand side components are less than the right-hand side.
+			pub fn lt_all(self, rhs: $vec<T>) -> bool {
+				infix!(&& $(self.$field < rhs.$field),+)
+			}
+			/// Returns `true` if all the left-hand side components are less than or equal the right-hand side.
+			pub fn le_all(self, rhs: $vec<T>) -> bool {
+				infix!(&& $(self.$field <= rhs.$field),+)
+			}
+			/// Returns `true` if all the left-hand side components are greater than the right-hand side.
+			pub fn gt_all(self, rhs: $vec<T>) -> bool {
+				infix!(&& $(self.$field > rhs.$field),+)
+			}
+			/// Returns `true` if all the left-hand side components are greater than or equal the right-hand side.
+			pub fn ge_all(self, rhs: $vec<T>) -> bool {
+				infix!(&& $(self.$field >= rhs.$field),+)
+			}
+			/// Compares the vectors component-wise.
+			///
+			/// Returns `Less` if no component is greater, `Greater` if no component is less and `Equal` if all components are equal.
+			/// Returns `None` if some components are less and others greater, or any component is unordered (eg. NaN).
+			pub fn partial_dominance(self, rhs: $vec<T>) -> Option<cmp::Ordering> {
+				let (mut less, mut greater) = (false, false);
+				$(
+					match self.$field.partial_cmp(&rhs.$field)? {
+						cmp::Ordering::Less => less = true,
+						cmp::Ordering::Greater => greater = true,
+						cmp::Ordering::Equal => (),
+					}
+				)+
+				match (less, greater) {
+					(false, false) => Some(cmp::Ordering::Equal),
+					(true, false) => Some(cmp::Ordering::Less),
+					(false, true) => Some(cmp::Ordering::Greater),
+					(true, true) => None,
+				}
+			}
+		}
+
 		//----------------------------------------------------------------
 		// Comparison operators
 
@@ -156,6 +231,7 @@ macro_rules! bools {
 	};
 }
 
+bools!(Bool1 Vec1 { x });
 bools!(Bool2 Vec2 { x, y });
 bools!(Bool3 Vec3 { x, y, z });
 bools!(Bool4 Vec4 { x, y, z, w });
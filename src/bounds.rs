@@ -2,17 +2,19 @@
 Rectangle and Cuboid bounding boxes.
 */
 
-use std::{ops};
+use std::{cmp, ops};
 
 use point::{Point2, Point3};
 use line2::{Line2};
 
-use num::{Scalar, Zero, One, Extrema, SpatialOrd};
+use vec::Vec2;
+use num::{Scalar, Float, Zero, One, Extrema, SpatialOrd};
 
 //----------------------------------------------------------------
 
 /// General bounds.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Bounds<T> {
 	pub mins: T,
@@ -314,6 +316,154 @@ impl<T: Scalar> Rect<T> {
 	}
 }
 
+/// Anchor point within a rectangle.
+///
+/// The y axis points down, the top is at the minimum y coordinate.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Anchor {
+	TopLeft,
+	Top,
+	TopRight,
+	Left,
+	#[default]
+	Center,
+	Right,
+	BottomLeft,
+	Bottom,
+	BottomRight,
+}
+
+impl Anchor {
+	/// Returns the anchor's relative position, from `(0, 0)` at the top left to `(1, 1)` at the bottom right.
+	pub fn fraction<T: Float>(self) -> Vec2<T> {
+		let (zero, half, one) = (T::zero(), T::cast_from(0.5), T::one());
+		match self {
+			Anchor::TopLeft => Vec2(zero, zero),
+			Anchor::Top => Vec2(half, zero),
+			Anchor::TopRight => Vec2(one, zero),
+			Anchor::Left => Vec2(zero, half),
+			Anchor::Center => Vec2(half, half),
+			Anchor::Right => Vec2(one, half),
+			Anchor::BottomLeft => Vec2(zero, one),
+			Anchor::Bottom => Vec2(half, one),
+			Anchor::BottomRight => Vec2(one, one),
+		}
+	}
+}
+
+/// Layout operations.
+///
+/// ```
+/// use cvmath::prelude::{Rect, Point2, Vec2};
+/// use cvmath::bounds::Anchor;
+///
+/// let window = Rect(Point2(0.0, 0.0), Point2(800.0, 600.0));
+/// let content = window.inflate(Vec2(-10.0, -10.0));
+/// assert_eq!(content, Rect(Point2(10.0, 10.0), Point2(790.0, 590.0)));
+///
+/// let (sidebar, main) = content.split_h(0.25);
+/// assert_eq!(sidebar, Rect(Point2(10.0, 10.0), Point2(205.0, 590.0)));
+/// assert_eq!(main.left(), 205.0);
+///
+/// let (header, body) = main.split_v(0.1);
+/// assert_eq!(header.height(), 58.0);
+/// assert_eq!(body.bottom(), 590.0);
+///
+/// let buttons: Vec<_> = sidebar.split_rows(4).collect();
+/// assert_eq!(buttons[1], Rect(Point2(10.0, 155.0), Point2(205.0, 300.0)));
+///
+/// let close = window.anchored(Anchor::TopRight, Vec2(20.0, 20.0));
+/// assert_eq!(close, Rect(Point2(780.0, 0.0), Point2(800.0, 20.0)));
+/// ```
+impl<T: Scalar> Rect<T> {
+	/// Grows the rectangle by the margins on each side, negative margins shrink it.
+	///
+	/// The horizontal margin is applied to the left and right side, the vertical margin to the top and bottom side.
+	pub fn inflate(self, margins: Vec2<T>) -> Rect<T> {
+		Rect { mins: self.mins - margins, maxs: self.maxs + margins }
+	}
+	/// Splits the rectangle into a left and right part at the fraction `t` of its width.
+	pub fn split_h(self, t: T) -> (Rect<T>, Rect<T>) {
+		let x = self.mins.x + self.width() * t;
+		(Rect { mins: self.mins, maxs: Point2 { x, y: self.maxs.y } }, Rect { mins: Point2 { x, y: self.mins.y }, maxs: self.maxs })
+	}
+	/// Splits the rectangle into a top and bottom part at the fraction `t` of its height.
+	pub fn split_v(self, t: T) -> (Rect<T>, Rect<T>) {
+		let y = self.mins.y + self.height() * t;
+		(Rect { mins: self.mins, maxs: Point2 { x: self.maxs.x, y } }, Rect { mins: Point2 { x: self.mins.x, y }, maxs: self.maxs })
+	}
+	/// Splits the rectangle into `n` columns of equal width from left to right.
+	pub fn split_cols(self, n: usize) -> impl Iterator<Item = Rect<T>> where T: Float {
+		let (left, width, count) = (self.mins.x, self.width(), T::cast_from(n as f64));
+		let x = move |i: usize| if i == n { self.maxs.x } else { left + width * T::cast_from(i as f64) / count };
+		(0..n).map(move |i| Rect { mins: Point2 { x: x(i), y: self.mins.y }, maxs: Point2 { x: x(i + 1), y: self.maxs.y } })
+	}
+	/// Splits the rectangle into `n` rows of equal height from top to bottom.
+	pub fn split_rows(self, n: usize) -> impl Iterator<Item = Rect<T>> where T: Float {
+		let (top, height, count) = (self.mins.y, self.height(), T::cast_from(n as f64));
+		let y = move |i: usize| if i == n { self.maxs.y } else { top + height * T::cast_from(i as f64) / count };
+		(0..n).map(move |i| Rect { mins: Point2 { x: self.mins.x, y: y(i) }, maxs: Point2 { x: self.maxs.x, y: y(i + 1) } })
+	}
+	/// Returns the largest centered rectangle with the aspect ratio `width / height` which fits inside this rectangle.
+	///
+	/// ```
+	/// use cvmath::prelude::{Rect, Point2};
+	///
+	/// // Letterbox a 16:9 viewport inside a 4:3 window
+	/// let window = Rect(Point2(0.0, 0.0), Point2(800.0, 600.0));
+	/// let viewport = window.fit_aspect(16.0 / 9.0);
+	/// assert_eq!(viewport, Rect(Point2(0.0, 75.0), Point2(800.0, 525.0)));
+	/// ```
+	pub fn fit_aspect(self, aspect: T) -> Rect<T> where T: Float {
+		let size = Vec2(aspect, T::one());
+		self.anchored(Anchor::Center, size * size.scale_to_fit(self.maxs - self.mins))
+	}
+	/// Positions a rectangle of the given size inside this rectangle at the anchor.
+	///
+	/// The anchor point of the result coincides with the anchor point of this rectangle.
+	pub fn anchored(&self, anchor: Anchor, size: Vec2<T>) -> Rect<T> where T: Float {
+		let mins = self.mins + (self.maxs - self.mins - size) * anchor.fraction();
+		Rect { mins, maxs: mins + size }
+	}
+}
+
+impl Rect<i32> {
+	/// Iterates over the integer coordinates in the rectangle, row by row.
+	///
+	/// The maximum is exclusive, an empty rectangle yields nothing.
+	///
+	/// ```
+	/// use cvmath::prelude::{Rect, Point2};
+	///
+	/// let rect = Rect(Point2(1, -1), Point2(3, 1));
+	/// let points: Vec<_> = rect.iter().collect();
+	/// assert_eq!(points, [Point2(1, -1), Point2(2, -1), Point2(1, 0), Point2(2, 0)]);
+	/// ```
+	pub fn iter(&self) -> impl Iterator<Item = Point2<i32>> {
+		let Bounds { mins, maxs } = *self;
+		(mins.y..maxs.y).flat_map(move |y| (mins.x..maxs.x).map(move |x| Point2 { x, y }))
+	}
+	/// Iterates over the integer coordinates on the border of the rectangle, row by row.
+	///
+	/// ```
+	/// use cvmath::prelude::{Rect, Point2};
+	///
+	/// let rect = Rect(Point2(0, 0), Point2(3, 3));
+	/// let points: Vec<_> = rect.iter_border().collect();
+	/// assert_eq!(points.len(), 8);
+	/// assert!(!points.contains(&Point2(1, 1)));
+	/// ```
+	pub fn iter_border(&self) -> impl Iterator<Item = Point2<i32>> {
+		let Bounds { mins, maxs } = *self;
+		// Interior rows only visit the first and last column
+		let inner = cmp::max(1, maxs.x - mins.x - 1) as usize;
+		(mins.y..maxs.y).flat_map(move |y| {
+			let step = if y == mins.y || y == maxs.y - 1 { 1 } else { inner };
+			(mins.x..maxs.x).step_by(step).map(move |x| Point2 { x, y })
+		})
+	}
+}
+
 //----------------------------------------------------------------
 
 pub type Cuboid<T> = Bounds<Point3<T>>;
@@ -348,3 +498,31 @@ impl<T> Cuboid<T> {
 		}
 	}
 }
+
+impl Cuboid<i32> {
+	/// Iterates over the integer coordinates in the cuboid, X varies fastest and Z slowest.
+	///
+	/// The maximum is exclusive, an empty cuboid yields nothing.
+	pub fn iter(&self) -> impl Iterator<Item = Point3<i32>> {
+		let Bounds { mins, maxs } = *self;
+		(mins.z..maxs.z).flat_map(move |z| (mins.y..maxs.y).flat_map(move |y| (mins.x..maxs.x).map(move |x| Point3 { x, y, z })))
+	}
+	/// Iterates over the integer coordinates on the surface of the cuboid in the same order as [`iter`](#method.iter).
+	///
+	/// ```
+	/// use cvmath::prelude::{Cuboid, Point3};
+	///
+	/// let cuboid = Cuboid(Point3(0, 0, 0), Point3(3, 3, 3));
+	/// assert_eq!(cuboid.iter_border().count(), 26);
+	/// assert!(cuboid.iter_border().all(|p| p != Point3(1, 1, 1)));
+	/// ```
+	pub fn iter_border(&self) -> impl Iterator<Item = Point3<i32>> {
+		let Bounds { mins, maxs } = *self;
+		let inner = cmp::max(1, maxs.x - mins.x - 1) as usize;
+		(mins.z..maxs.z).flat_map(move |z| (mins.y..maxs.y).flat_map(move |y| {
+			let face = z == mins.z || z == maxs.z - 1 || y == mins.y || y == maxs.y - 1;
+			let step = if face { 1 } else { inner };
+			(mins.x..maxs.x).step_by(step).map(move |x| Point3 { x, y, z })
+		}))
+	}
+}
@@ -0,0 +1,38 @@
+/*!
+Bytemuck support.
+
+All types are `repr(C)` structs of a single scalar type without padding,
+making them `Pod` and `Zeroable` whenever their scalar type is.
+
+```
+# extern crate cvmath;
+# extern crate bytemuck;
+# use cvmath::prelude::Vec3;
+# fn main() {
+let vertices = [Vec3(1.0f32, 2.0, 3.0), Vec3(4.0, 5.0, 6.0)];
+let floats: &[f32] = bytemuck::cast_slice(&vertices);
+assert_eq!(floats, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+# }
+```
+*/
+
+use bytemuck::{Pod, Zeroable};
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+use angle::{Deg, Rad};
+use bounds::Bounds;
+
+macro_rules! impl_pod {
+	($($ty:ident),+) => {
+		$(
+			unsafe impl<T: Zeroable> Zeroable for $ty<T> {}
+			unsafe impl<T: Pod> Pod for $ty<T> {}
+		)+
+	};
+}
+
+impl_pod!(Vec1, Vec2, Vec3, Vec4);
+impl_pod!(Mat2, Affine2, Mat3, Affine3, Mat4);
+impl_pod!(Deg, Rad);
+impl_pod!(Bounds);
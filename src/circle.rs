@@ -0,0 +1,145 @@
+/*!
+Circle in 2D space.
+*/
+
+use num::{Scalar, Float};
+use point::Point2;
+use bounds::Rect;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Circle in 2D space.
+///
+/// ```
+/// use cvmath::prelude::{Circle, Point2, Rect};
+///
+/// let circle = Circle::new(Point2(1.0, 2.0), 2.0);
+/// assert!(circle.contains(Point2(2.0, 3.0)));
+/// assert!(!circle.contains(Point2(3.0, 3.0)));
+/// assert_eq!(Rect(Point2(-1.0, 0.0), Point2(3.0, 4.0)), circle.bounds());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Circle<T> {
+	pub center: Point2<T>,
+	pub radius: T,
+}
+
+impl<T> Circle<T> {
+	/// Constructs a new circle.
+	pub fn new(center: Point2<T>, radius: T) -> Circle<T> {
+		Circle { center, radius }
+	}
+}
+
+impl<T: Scalar> Circle<T> {
+	/// Returns whether the point lies inside or on the circle.
+	pub fn contains(&self, pt: Point2<T>) -> bool {
+		self.center.dist_sqr(pt) <= self.radius * self.radius
+	}
+	/// Returns the bounds enclosing the circle.
+	pub fn bounds(&self) -> Rect<T> {
+		let extent = Point2::dup(self.radius);
+		Rect { mins: self.center - extent, maxs: self.center + extent }
+	}
+}
+
+//----------------------------------------------------------------
+// Minimum enclosing circle
+
+impl<T: Float> Circle<T> {
+	/// Calculates the smallest circle enclosing all the points.
+	///
+	/// Implements Welzl's algorithm in expected linear time, the points are visited in a shuffled order.
+	///
+	/// Returns `None` if there are no points.
+	///
+	/// ```
+	/// use cvmath::prelude::{Circle, Point2};
+	///
+	/// let points = [Point2(0.0, 0.0), Point2(4.0, 0.0), Point2(2.0, 1.0), Point2(1.0, -1.0)];
+	/// assert_eq!(Some(Circle::new(Point2(2.0, 0.0), 2.0)), Circle::enclosing(&points));
+	/// assert_eq!(None, Circle::<f64>::enclosing(&[]));
+	/// ```
+	pub fn enclosing(points: &[Point2<T>]) -> Option<Circle<T>> {
+		let first = *points.first()?;
+		let points = shuffled(points);
+		// Tolerate rounding errors on the boundary
+		let slack = T::one() + T::epsilon() * T::cast_from(64.0);
+		let outside = |c: &Circle<T>, pt: Point2<T>| c.center.dist_sqr(pt) > c.radius * c.radius * slack;
+		let mut circle = Circle::new(first, T::zero());
+		for i in 0..points.len() {
+			if outside(&circle, points[i]) {
+				circle = Circle::new(points[i], T::zero());
+				for j in 0..i {
+					if outside(&circle, points[j]) {
+						circle = Circle::diameter(points[i], points[j]);
+						for k in 0..j {
+							if outside(&circle, points[k]) {
+								circle = Circle::circumscribe(points[i], points[j], points[k]);
+							}
+						}
+					}
+				}
+			}
+		}
+		Some(circle)
+	}
+	fn diameter(a: Point2<T>, b: Point2<T>) -> Circle<T> {
+		let half = T::one() / (T::one() + T::one());
+		Circle { center: (a + b) * half, radius: a.dist(b) * half }
+	}
+	fn circumscribe(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> Circle<T> {
+		let (ab, ac) = (b - a, c - a);
+		let d = (ab.x * ac.y - ab.y * ac.x) * (T::one() + T::one());
+		if d == T::zero() {
+			// Collinear points, the farthest pair spans the circle
+			let candidates = [Circle::diameter(a, b), Circle::diameter(a, c), Circle::diameter(b, c)];
+			return candidates.iter().cloned().fold(candidates[0], |acc, c| if c.radius > acc.radius { c } else { acc });
+		}
+		let (ab_sqr, ac_sqr) = (ab.len_sqr(), ac.len_sqr());
+		let offset = Point2 {
+			x: (ac.y * ab_sqr - ab.y * ac_sqr) / d,
+			y: (ab.x * ac_sqr - ac.x * ab_sqr) / d,
+		};
+		Circle { center: a + offset, radius: offset.len() }
+	}
+}
+
+/// Copies the points in a pseudo-random order, avoids the quadratic worst case of sorted inputs.
+fn shuffled<T: Copy>(points: &[T]) -> Vec<T> {
+	let mut points = points.to_vec();
+	let mut state = 0x9e3779b97f4a7c15u64;
+	for i in (1..points.len()).rev() {
+		// Xorshift64
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		points.swap(i, (state % (i as u64 + 1)) as usize);
+	}
+	points
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn enclosing() {
+		let points: Vec<Point2<f64>> = (0..500).map(|i| {
+			let t = i as f64 * 0.37;
+			Point2(t.sin() * (i % 13) as f64, t.cos() * (i % 7) as f64 + 0.5 * t.sin())
+		}).collect();
+		let circle = Circle::enclosing(&points).unwrap();
+		assert!(points.iter().all(|&pt| circle.center.dist(pt) <= circle.radius * (1.0 + 1e-12)));
+		// The minimum circle touches at least two points
+		let touching = points.iter().filter(|&&pt| (circle.center.dist(pt) - circle.radius).abs() < 1e-9).count();
+		assert!(touching >= 2);
+
+		let triangle = [Point2(0.0, 0.0), Point2(2.0, 0.0), Point2(1.0, 3.0f64.sqrt())];
+		let circle = Circle::enclosing(&triangle).unwrap();
+		assert!(circle.center.dist(Point2(1.0, 1.0 / 3.0f64.sqrt())) < 1e-12);
+	}
+}
@@ -0,0 +1,942 @@
+/*!
+RGB and RGBA color types.
+
+Colors convert to and from vectors and support component wise arithmetic.
+Hex values are written the way they appear in CSS, `0xRRGGBB` and `0xRRGGBBAA`.
+
+### Examples
+
+```
+use cvmath::prelude::{Rgb, Rgba, Vec3};
+
+let orange = Rgb::from_hex(0xFF8000);
+assert_eq!(Rgb(255, 128, 0), orange);
+
+let color = Rgb::<f32>::from_u8(orange);
+assert_eq!(Rgb(1.0, 128.0 / 255.0, 0.0), color);
+assert_eq!(0xFF8000, color.to_u8().to_hex());
+
+let dimmed = color * 0.5 + Rgb(0.25, 0.0, 0.0);
+assert_eq!(Vec3(0.75, 64.0 / 255.0, 0.0), dimmed.into());
+
+let translucent = Rgba::from_hex(0x336699CC);
+assert_eq!(Rgba(0x33, 0x66, 0x99, 0xCC), translucent);
+assert_eq!(Rgb(0x33, 0x66, 0x99), translucent.rgb());
+```
+
+Colors stored in textures and hex values are usually sRGB encoded, convert them to linear before blending or lighting.
+
+```
+use cvmath::prelude::Rgb;
+
+let mid_gray = Rgb::<f64>::from_u8(Rgb::from_hex(0xBCBCBC)).srgb_to_linear();
+assert!((mid_gray.r - 0.5).abs() < 0.01);
+assert_eq!(Rgb::from_hex(0xBCBCBC), mid_gray.linear_to_srgb().to_u8());
+```
+*/
+
+use std::{error, fmt, ops};
+
+use num::{Float, CastFrom};
+use angle::Rad;
+use vec::{Vec3, Vec4};
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+//----------------------------------------------------------------
+// Transfer functions
+
+/// Converts an sRGB encoded component to linear.
+///
+/// Uses the exact piecewise sRGB curve with a linear segment near black.
+pub fn srgb_to_linear<T: Float>(x: T) -> T {
+	if x <= T::cast_from(0.04045) {
+		x / T::cast_from(12.92)
+	}
+	else {
+		((x + T::cast_from(0.055)) / T::cast_from(1.055)).powf(T::cast_from(2.4))
+	}
+}
+
+/// Converts a linear component to sRGB encoded.
+///
+/// Uses the exact piecewise sRGB curve with a linear segment near black.
+pub fn linear_to_srgb<T: Float>(x: T) -> T {
+	if x <= T::cast_from(0.0031308) {
+		x * T::cast_from(12.92)
+	}
+	else {
+		T::cast_from(1.055) * x.powf(T::one() / T::cast_from(2.4)) - T::cast_from(0.055)
+	}
+}
+
+macro_rules! color {
+	($(#[$meta:meta])* $color:ident $vec:ident $n:literal { $($field:ident $vfield:ident),+ }) => {
+		$(#[$meta])*
+		#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+		#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+		#[repr(C)]
+		pub struct $color<T> {
+			$(pub $field: T,)+
+		}
+
+		/// Color constructor.
+		#[allow(non_snake_case)]
+		pub fn $color<T>($($field: T),+) -> $color<T> {
+			$color { $($field),+ }
+		}
+
+		impl<T> $color<T> {
+			/// Applies the function to every component.
+			pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> $color<U> {
+				$color { $($field: f(self.$field)),+ }
+			}
+			/// Linear interpolation between the colors.
+			pub fn lerp(self, rhs: $color<T>, t: T) -> $color<T> where T: Float {
+				$color { $($field: self.$field + (rhs.$field - self.$field) * t),+ }
+			}
+		}
+
+		impl<T: Float> $color<T> {
+			/// Converts 8-bit components to normalized components in the range `[0, 1]`.
+			pub fn from_u8(color: $color<u8>) -> $color<T> {
+				let scale = T::one() / T::cast_from(255.0);
+				$color { $($field: T::cast_from(color.$field as f64) * scale),+ }
+			}
+			/// Converts normalized components to 8-bit components.
+			///
+			/// The components are clamped to the range `[0, 1]` and rounded to the nearest value.
+			pub fn to_u8(self) -> $color<u8> where u8: CastFrom<T> {
+				let (zero, one, half) = (T::zero(), T::one(), T::cast_from(0.5));
+				let max = T::cast_from(255.0);
+				$color { $($field: u8::cast_from((self.$field.max(zero).min(one) * max + half).floor())),+ }
+			}
+		}
+
+		//----------------------------------------------------------------
+		// Conversions
+
+		impl<T> From<$vec<T>> for $color<T> {
+			fn from(v: $vec<T>) -> $color<T> {
+				$color { $($field: v.$vfield),+ }
+			}
+		}
+		impl<T> From<$color<T>> for $vec<T> {
+			fn from(c: $color<T>) -> $vec<T> {
+				$vec { $($vfield: c.$field),+ }
+			}
+		}
+		impl<T> From<[T; $n]> for $color<T> {
+			fn from(arr: [T; $n]) -> $color<T> {
+				let [$($field),+] = arr;
+				$color { $($field),+ }
+			}
+		}
+		impl<T> From<$color<T>> for [T; $n] {
+			fn from(c: $color<T>) -> [T; $n] {
+				[$(c.$field),+]
+			}
+		}
+
+		//----------------------------------------------------------------
+		// Operators
+
+		impl<T: ops::Add<Output = T>> ops::Add for $color<T> {
+			type Output = $color<T>;
+			fn add(self, rhs: $color<T>) -> $color<T> {
+				$color { $($field: self.$field + rhs.$field),+ }
+			}
+		}
+		impl<T: ops::Sub<Output = T>> ops::Sub for $color<T> {
+			type Output = $color<T>;
+			fn sub(self, rhs: $color<T>) -> $color<T> {
+				$color { $($field: self.$field - rhs.$field),+ }
+			}
+		}
+		impl<T: ops::Mul<Output = T>> ops::Mul for $color<T> {
+			type Output = $color<T>;
+			fn mul(self, rhs: $color<T>) -> $color<T> {
+				$color { $($field: self.$field * rhs.$field),+ }
+			}
+		}
+		impl<T: Copy + ops::Mul<Output = T>> ops::Mul<T> for $color<T> {
+			type Output = $color<T>;
+			fn mul(self, rhs: T) -> $color<T> {
+				$color { $($field: self.$field * rhs),+ }
+			}
+		}
+		impl<T: Copy + ops::Div<Output = T>> ops::Div<T> for $color<T> {
+			type Output = $color<T>;
+			fn div(self, rhs: T) -> $color<T> {
+				$color { $($field: self.$field / rhs),+ }
+			}
+		}
+		impl<T: ops::AddAssign> ops::AddAssign for $color<T> {
+			fn add_assign(&mut self, rhs: $color<T>) {
+				$(self.$field += rhs.$field;)+
+			}
+		}
+		impl<T: ops::SubAssign> ops::SubAssign for $color<T> {
+			fn sub_assign(&mut self, rhs: $color<T>) {
+				$(self.$field -= rhs.$field;)+
+			}
+		}
+		impl<T: ops::MulAssign> ops::MulAssign for $color<T> {
+			fn mul_assign(&mut self, rhs: $color<T>) {
+				$(self.$field *= rhs.$field;)+
+			}
+		}
+		impl<T: Copy + ops::MulAssign> ops::MulAssign<T> for $color<T> {
+			fn mul_assign(&mut self, rhs: T) {
+				$(self.$field *= rhs;)+
+			}
+		}
+	};
+}
+
+//----------------------------------------------------------------
+// Luminance
+
+/// Weights of the color components for luminance and luma.
+///
+/// Luminance applies the weights to linear colors, the luma of [`YCbCr`](struct.YCbCr.html) applies them to gamma corrected colors.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum LumaWeights {
+	/// ITU-R BT.601 luma coefficients for standard definition video.
+	Rec601,
+	/// ITU-R BT.709 primaries, shared with sRGB.
+	#[default]
+	Rec709,
+	/// ITU-R BT.2020 primaries for wide gamut and HDR content.
+	Rec2020,
+}
+
+impl LumaWeights {
+	/// Returns the weights, they sum to one.
+	pub fn weights<T: Float>(self) -> Rgb<T> {
+		let (r, g, b) = match self {
+			LumaWeights::Rec601 => (0.299, 0.587, 0.114),
+			LumaWeights::Rec709 => (0.2126, 0.7152, 0.0722),
+			LumaWeights::Rec2020 => (0.2627, 0.6780, 0.0593),
+		};
+		Rgb { r: T::cast_from(r), g: T::cast_from(g), b: T::cast_from(b) }
+	}
+}
+
+color! {
+	/// RGB color.
+	Rgb Vec3 3 { r x, g y, b z }
+}
+
+color! {
+	/// RGBA color with straight alpha.
+	Rgba Vec4 4 { r x, g y, b z, a w }
+}
+
+impl<T: Float> Rgb<T> {
+	/// Relative luminance of the linear color with the Rec.709 weights.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgb;
+	///
+	/// let green = Rgb(0.0f64, 1.0, 0.0);
+	/// assert_eq!(0.7152, green.luminance());
+	/// assert_eq!(Rgb(0.7152, 0.7152, 0.7152), green.to_grayscale());
+	/// ```
+	pub fn luminance(self) -> T {
+		self.luminance_with(LumaWeights::Rec709)
+	}
+	/// Relative luminance of the linear color with the given weights.
+	pub fn luminance_with(self, weights: LumaWeights) -> T {
+		let w = weights.weights::<T>();
+		self.r * w.r + self.g * w.g + self.b * w.b
+	}
+	/// Converts the linear color to gray with the same luminance.
+	pub fn to_grayscale(self) -> Rgb<T> {
+		let y = self.luminance();
+		Rgb { r: y, g: y, b: y }
+	}
+	/// Converts the sRGB encoded color to linear.
+	pub fn srgb_to_linear(self) -> Rgb<T> {
+		self.map(srgb_to_linear)
+	}
+	/// Converts the linear color to sRGB encoded.
+	pub fn linear_to_srgb(self) -> Rgb<T> {
+		self.map(linear_to_srgb)
+	}
+}
+
+impl<T: Float> Rgb<T> {
+	/// Returns the linear color of a black body radiator at the temperature in kelvin.
+	///
+	/// Approximates the chromaticity of the Planckian locus with the cubic spline of Kim et al.
+	/// The temperature is clamped to the valid range of `1667 K` to `25000 K`.
+	/// The result is scaled so the largest component is one, multiply by an intensity as needed.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgb;
+	///
+	/// // Close to the D65 white point, which lies slightly off the locus
+	/// let daylight = Rgb::from_temperature(6504.0f64);
+	/// assert!(daylight.r > 0.9 && daylight.g > 0.9 && daylight.b > 0.9);
+	///
+	/// let candle = Rgb::from_temperature(1900.0f64);
+	/// assert!(candle.r == 1.0 && candle.g < 0.5 && candle.b < 0.1);
+	/// ```
+	pub fn from_temperature(kelvin: T) -> Rgb<T> {
+		let t = kelvin.max(T::cast_from(1667.0)).min(T::cast_from(25000.0));
+		let c = |v: f64| T::cast_from(v);
+		let (t1, t2, t3) = (T::one() / t, T::one() / (t * t), T::one() / (t * t * t));
+		let x = if t <= c(4000.0) {
+			c(-0.2661239e9) * t3 + c(-0.2343589e6) * t2 + c(0.8776956e3) * t1 + c(0.179910)
+		}
+		else {
+			c(-3.0258469e9) * t3 + c(2.1070379e6) * t2 + c(0.2226347e3) * t1 + c(0.240390)
+		};
+		let (x2, x3) = (x * x, x * x * x);
+		let y = if t <= c(2222.0) {
+			c(-1.1063814) * x3 + c(-1.34811020) * x2 + c(2.18555832) * x + c(-0.20219683)
+		}
+		else if t <= c(4000.0) {
+			c(-0.9549476) * x3 + c(-1.37418593) * x2 + c(2.09137015) * x + c(-0.16748867)
+		}
+		else {
+			c(3.0817580) * x3 + c(-5.87338670) * x2 + c(3.75112997) * x + c(-0.37001483)
+		};
+		// Chromaticity to XYZ with unit luminance, then to linear sRGB
+		let xyz = [x / y, T::one(), (T::one() - x - y) / y];
+		let [r, g, b] = mat3_mul(&XYZ_TO_RGB, xyz).map(|v| v.max(T::zero()));
+		let max = r.max(g).max(b);
+		Rgb { r: r / max, g: g / max, b: b / max }
+	}
+}
+
+impl<T: Float> Rgba<T> {
+	/// Relative luminance of the linear color with the Rec.709 weights, alpha is ignored.
+	pub fn luminance(self) -> T {
+		self.rgb().luminance()
+	}
+	/// Converts the linear color to gray with the same luminance, alpha is left unchanged.
+	pub fn to_grayscale(self) -> Rgba<T> {
+		self.rgb().to_grayscale().rgba(self.a)
+	}
+	/// Multiplies the color components by alpha.
+	pub fn premultiply(self) -> Rgba<T> {
+		Rgba { r: self.r * self.a, g: self.g * self.a, b: self.b * self.a, a: self.a }
+	}
+	/// Divides the color components of a premultiplied color by alpha.
+	///
+	/// Fully transparent colors have no recoverable color and return transparent black.
+	pub fn unpremultiply(self) -> Rgba<T> {
+		if self.a <= T::epsilon() {
+			return Rgba { r: T::zero(), g: T::zero(), b: T::zero(), a: T::zero() };
+		}
+		let inv = T::one() / self.a;
+		Rgba { r: self.r * inv, g: self.g * inv, b: self.b * inv, a: self.a }
+	}
+	/// Composites the premultiplied color over the premultiplied destination color.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgba;
+	///
+	/// let red = Rgba(1.0, 0.0, 0.0, 0.5).premultiply();
+	/// let blue = Rgba(0.0, 0.0, 1.0, 1.0);
+	/// assert_eq!(Rgba(0.5, 0.0, 0.5, 1.0), red.over(blue));
+	///
+	/// let half = Rgba(0.0, 1.0, 0.0, 0.5).premultiply().over(Rgba(0.0, 0.0, 0.0, 0.0));
+	/// assert_eq!(Rgba(0.0, 1.0, 0.0, 0.5), half.unpremultiply());
+	/// ```
+	pub fn over(self, dst: Rgba<T>) -> Rgba<T> {
+		self + dst * (T::one() - self.a)
+	}
+	/// Converts the sRGB encoded color to linear, alpha is already linear and left unchanged.
+	pub fn srgb_to_linear(self) -> Rgba<T> {
+		self.rgb().srgb_to_linear().rgba(self.a)
+	}
+	/// Converts the linear color to sRGB encoded, alpha is left unchanged.
+	pub fn linear_to_srgb(self) -> Rgba<T> {
+		self.rgb().linear_to_srgb().rgba(self.a)
+	}
+}
+
+impl<T: Float> Vec3<T> {
+	/// Converts the sRGB encoded color to linear.
+	pub fn srgb_to_linear(self) -> Vec3<T> {
+		self.map(srgb_to_linear)
+	}
+	/// Converts the linear color to sRGB encoded.
+	pub fn linear_to_srgb(self) -> Vec3<T> {
+		self.map(linear_to_srgb)
+	}
+}
+
+impl<T: Float> Vec4<T> {
+	/// Converts the sRGB encoded color to linear, the alpha in `w` is left unchanged.
+	pub fn srgb_to_linear(self) -> Vec4<T> {
+		self.xyz().srgb_to_linear().vec4(self.w)
+	}
+	/// Converts the linear color to sRGB encoded, the alpha in `w` is left unchanged.
+	pub fn linear_to_srgb(self) -> Vec4<T> {
+		self.xyz().linear_to_srgb().vec4(self.w)
+	}
+}
+
+impl<T> Rgb<T> {
+	/// Adds an alpha component.
+	pub fn rgba(self, a: T) -> Rgba<T> {
+		Rgba { r: self.r, g: self.g, b: self.b, a }
+	}
+}
+
+impl<T> Rgba<T> {
+	/// Drops the alpha component.
+	pub fn rgb(self) -> Rgb<T> {
+		Rgb { r: self.r, g: self.g, b: self.b }
+	}
+}
+
+impl Rgb<u8> {
+	/// Constructs the color from a `0xRRGGBB` hex value, the upper byte is ignored.
+	pub fn from_hex(hex: u32) -> Rgb<u8> {
+		Rgb {
+			r: (hex >> 16) as u8,
+			g: (hex >> 8) as u8,
+			b: hex as u8,
+		}
+	}
+	/// Returns the `0xRRGGBB` hex value of the color.
+	pub fn to_hex(self) -> u32 {
+		(self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+	}
+	/// Parses a `#rgb` or `#rrggbb` hex string, the `#` is optional.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgb;
+	///
+	/// assert_eq!(Ok(Rgb(0xFF, 0x88, 0x00)), Rgb::from_hex_str("#f80"));
+	/// assert_eq!(Ok(Rgb(0x12, 0x34, 0x56)), Rgb::from_hex_str("123456"));
+	/// assert!(Rgb::from_hex_str("#12345678").is_err());
+	/// ```
+	pub fn from_hex_str(s: &str) -> Result<Rgb<u8>, ParseColorError> {
+		match parse_hex(s)? {
+			(hex, 3) => Ok(Rgb::from_hex(expand_short(hex, 3))),
+			(hex, 6) => Ok(Rgb::from_hex(hex)),
+			_ => Err(ParseColorError::InvalidLength),
+		}
+	}
+	/// Formats the color as a `#rrggbb` hex string.
+	pub fn to_hex_string(self) -> String {
+		format!("#{:06x}", self.to_hex())
+	}
+}
+
+impl Rgba<u8> {
+	/// Constructs the color from a `0xRRGGBBAA` hex value.
+	pub fn from_hex(hex: u32) -> Rgba<u8> {
+		Rgba {
+			r: (hex >> 24) as u8,
+			g: (hex >> 16) as u8,
+			b: (hex >> 8) as u8,
+			a: hex as u8,
+		}
+	}
+	/// Returns the `0xRRGGBBAA` hex value of the color.
+	pub fn to_hex(self) -> u32 {
+		(self.r as u32) << 24 | (self.g as u32) << 16 | (self.b as u32) << 8 | self.a as u32
+	}
+	/// Parses a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` hex string, the `#` is optional.
+	///
+	/// Forms without alpha are opaque.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgba;
+	///
+	/// assert_eq!(Ok(Rgba(0xFF, 0x88, 0x00, 0xFF)), Rgba::from_hex_str("#f80"));
+	/// assert_eq!(Ok(Rgba(0xFF, 0x88, 0x00, 0x44)), Rgba::from_hex_str("#f804"));
+	/// assert_eq!(Ok(Rgba(0x12, 0x34, 0x56, 0x78)), Rgba::from_hex_str("#12345678"));
+	/// assert_eq!("#123456ff", Rgba::from_hex_str("123456").unwrap().to_hex_string());
+	/// assert!(Rgba::from_hex_str("#12g").is_err());
+	/// ```
+	pub fn from_hex_str(s: &str) -> Result<Rgba<u8>, ParseColorError> {
+		match parse_hex(s)? {
+			(hex, 3) => Ok(Rgba::from_hex(expand_short(hex, 3) << 8 | 0xFF)),
+			(hex, 4) => Ok(Rgba::from_hex(expand_short(hex, 4))),
+			(hex, 6) => Ok(Rgba::from_hex(hex << 8 | 0xFF)),
+			(hex, 8) => Ok(Rgba::from_hex(hex)),
+			_ => Err(ParseColorError::InvalidLength),
+		}
+	}
+	/// Formats the color as a `#rrggbbaa` hex string.
+	pub fn to_hex_string(self) -> String {
+		format!("#{:08x}", self.to_hex())
+	}
+}
+
+/// Error parsing a hex color string.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ParseColorError {
+	/// The number of hex digits doesn't match any of the accepted forms.
+	InvalidLength,
+	/// The string contains a character which isn't a hex digit.
+	InvalidDigit,
+}
+impl fmt::Display for ParseColorError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match *self {
+			ParseColorError::InvalidLength => "invalid length",
+			ParseColorError::InvalidDigit => "invalid digit",
+		})
+	}
+}
+impl error::Error for ParseColorError {}
+
+/// Parses the hex digits after an optional `#`, returns the value and the number of digits.
+fn parse_hex(s: &str) -> Result<(u32, usize), ParseColorError> {
+	let digits = s.strip_prefix('#').unwrap_or(s);
+	if digits.is_empty() || digits.len() > 8 {
+		return Err(ParseColorError::InvalidLength);
+	}
+	let mut hex = 0;
+	for c in digits.chars() {
+		let digit = c.to_digit(16).ok_or(ParseColorError::InvalidDigit)?;
+		hex = hex << 4 | digit;
+	}
+	Ok((hex, digits.len()))
+}
+
+/// Expands every hex digit of the short form to a byte, `0xf80` becomes `0xff8800`.
+fn expand_short(hex: u32, digits: usize) -> u32 {
+	(0..digits).rev().fold(0, |acc, i| {
+		let nibble = (hex >> (i * 4)) & 0xF;
+		(acc << 8) | (nibble * 0x11)
+	})
+}
+
+//----------------------------------------------------------------
+// Blend modes
+
+/// Separable blend modes of image editors and the W3C compositing specification.
+///
+/// The modes combine a source color with the destination (backdrop) color per component, for components in the range `[0, 1]`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum BlendMode {
+	/// The source color replaces the destination.
+	#[default]
+	Normal,
+	/// Multiplies the colors, darkens.
+	Multiply,
+	/// Inverse of multiplying the inverted colors, lightens.
+	Screen,
+	/// Multiplies or screens depending on the destination, increases contrast.
+	Overlay,
+	/// Adds the colors clamped to one, also known as linear dodge.
+	Additive,
+	/// Darkens or lightens depending on the source, a softer version of overlay.
+	SoftLight,
+}
+
+impl BlendMode {
+	/// Blends a single component of the source with the destination.
+	pub fn apply<T: Float>(self, src: T, dst: T) -> T {
+		let one = T::one();
+		let two = one + one;
+		let half = one / two;
+		match self {
+			BlendMode::Normal => src,
+			BlendMode::Multiply => src * dst,
+			BlendMode::Screen => src + dst - src * dst,
+			BlendMode::Overlay => {
+				if dst <= half { two * src * dst }
+				else { one - two * (one - src) * (one - dst) }
+			},
+			BlendMode::Additive => (src + dst).min(one),
+			BlendMode::SoftLight => {
+				if src <= half {
+					dst - (one - two * src) * dst * (one - dst)
+				}
+				else {
+					let d = if dst <= T::cast_from(0.25) {
+						((T::cast_from(16.0) * dst - T::cast_from(12.0)) * dst + T::cast_from(4.0)) * dst
+					}
+					else {
+						dst.sqrt()
+					};
+					dst + (two * src - one) * (d - dst)
+				}
+			},
+		}
+	}
+}
+
+/// Blends the source color with the destination color.
+///
+/// ```
+/// use cvmath::prelude::Rgb;
+/// use cvmath::color::{blend, BlendMode};
+///
+/// let src = Rgb(0.5, 0.25, 1.0);
+/// let dst = Rgb(0.5, 1.0, 0.0);
+/// assert_eq!(Rgb(0.25, 0.25, 0.0), blend(BlendMode::Multiply, src, dst));
+/// assert_eq!(Rgb(0.75, 1.0, 1.0), blend(BlendMode::Screen, src, dst));
+/// assert_eq!(Rgb(1.0, 1.0, 1.0), blend(BlendMode::Additive, src, dst));
+/// ```
+pub fn blend<T: Float>(mode: BlendMode, src: Rgb<T>, dst: Rgb<T>) -> Rgb<T> {
+	Rgb {
+		r: mode.apply(src.r, dst.r),
+		g: mode.apply(src.g, dst.g),
+		b: mode.apply(src.b, dst.b),
+	}
+}
+
+impl<T: Float> Rgb<T> {
+	/// Blends the color with the destination color.
+	pub fn blend(self, dst: Rgb<T>, mode: BlendMode) -> Rgb<T> {
+		blend(mode, self, dst)
+	}
+}
+
+impl<T: Float> Rgba<T> {
+	/// Blends the color with the destination color and composites the result over it.
+	///
+	/// Both colors have straight alpha, the blended color only applies where both are opaque.
+	///
+	/// ```
+	/// use cvmath::prelude::Rgba;
+	/// use cvmath::color::BlendMode;
+	///
+	/// let src = Rgba(0.5, 0.5, 0.5, 0.5);
+	/// let dst = Rgba(1.0, 0.5, 0.0, 1.0);
+	/// assert_eq!(Rgba(0.75, 0.375, 0.0, 1.0), src.blend(dst, BlendMode::Multiply));
+	/// // Over a transparent destination the source is unchanged
+	/// assert_eq!(src, src.blend(Rgba(0.0, 0.0, 0.0, 0.0), BlendMode::Screen));
+	/// ```
+	pub fn blend(self, dst: Rgba<T>, mode: BlendMode) -> Rgba<T> {
+		let mixed = blend(mode, self.rgb(), dst.rgb());
+		let both = self.a * dst.a;
+		let src_only = self.a * (T::one() - dst.a);
+		let dst_only = dst.a * (T::one() - self.a);
+		let a = both + src_only + dst_only;
+		if a <= T::zero() {
+			return Rgba { r: T::zero(), g: T::zero(), b: T::zero(), a: T::zero() };
+		}
+		let rgb = (mixed * both + self.rgb() * src_only + dst.rgb() * dst_only) / a;
+		rgb.rgba(a)
+	}
+}
+
+//----------------------------------------------------------------
+// Oklab
+
+/// Color in the Oklab perceptual color space.
+///
+/// Equal distances in Oklab appear as roughly equal differences in color, interpolating in Oklab avoids the muddy midpoints of RGB gradients.
+/// Lightness `l` ranges from `0` for black to `1` for white, `a` and `b` are the green-red and blue-yellow axes.
+///
+/// ```
+/// use cvmath::prelude::Rgb;
+/// use cvmath::color::Oklab;
+///
+/// let white = Oklab::from_linear(Rgb(1.0f64, 1.0, 1.0));
+/// assert!((white.l - 1.0).abs() < 1e-6 && white.a.abs() < 1e-6 && white.b.abs() < 1e-6);
+///
+/// let red = Oklab::from_linear(Rgb(1.0, 0.0, 0.0));
+/// let blue = Oklab::from_linear(Rgb(0.0, 0.0, 1.0));
+/// let mid = red.lerp(blue, 0.5).to_linear();
+/// assert!(mid.r > 0.0 && mid.b > 0.0);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Oklab<T> {
+	pub l: T,
+	pub a: T,
+	pub b: T,
+}
+
+/// Color in the Oklch color space, the polar form of Oklab.
+///
+/// Chroma `c` is the distance from the gray axis and hue `h` the angle around it.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Oklch<T> {
+	pub l: T,
+	pub c: T,
+	pub h: Rad<T>,
+}
+
+fn cbrt<T: Float>(x: T) -> T {
+	let cube_root = x.abs().powf(T::one() / T::cast_from(3.0));
+	if x < T::zero() { -cube_root } else { cube_root }
+}
+
+fn mat3_mul<T: Float>(m: &[[f64; 3]; 3], v: [T; 3]) -> [T; 3] {
+	let row = |r: &[f64; 3]| T::cast_from(r[0]) * v[0] + T::cast_from(r[1]) * v[1] + T::cast_from(r[2]) * v[2];
+	[row(&m[0]), row(&m[1]), row(&m[2])]
+}
+
+const XYZ_TO_RGB: [[f64; 3]; 3] = [
+	[3.2404542, -1.5371385, -0.4985314],
+	[-0.9692660, 1.8760108, 0.0415560],
+	[0.0556434, -0.2040259, 1.0572252],
+];
+const RGB_TO_LMS: [[f64; 3]; 3] = [
+	[0.4122214708, 0.5363325363, 0.0514459929],
+	[0.2119034982, 0.6806995451, 0.1073969566],
+	[0.0883024619, 0.2817188376, 0.6299787005],
+];
+const LMS_TO_LAB: [[f64; 3]; 3] = [
+	[0.2104542553, 0.7936177850, -0.0040720468],
+	[1.9779984951, -2.4285922050, 0.4505937099],
+	[0.0259040371, 0.7827717662, -0.8086757660],
+];
+const LAB_TO_LMS: [[f64; 3]; 3] = [
+	[1.0, 0.3963377774, 0.2158037573],
+	[1.0, -0.1055613458, -0.0638541728],
+	[1.0, -0.0894841775, -1.2914855480],
+];
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+	[4.0767416621, -3.3077115913, 0.2309699292],
+	[-1.2684380046, 2.6097574011, -0.3413193965],
+	[-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+impl<T> Oklab<T> {
+	/// Constructs a new Oklab color.
+	pub fn new(l: T, a: T, b: T) -> Oklab<T> {
+		Oklab { l, a, b }
+	}
+}
+
+impl<T: Float> Oklab<T> {
+	/// Converts from linear sRGB.
+	pub fn from_linear(rgb: Rgb<T>) -> Oklab<T> {
+		let lms = mat3_mul(&RGB_TO_LMS, [rgb.r, rgb.g, rgb.b]);
+		let [l, a, b] = mat3_mul(&LMS_TO_LAB, [cbrt(lms[0]), cbrt(lms[1]), cbrt(lms[2])]);
+		Oklab { l, a, b }
+	}
+	/// Converts to linear sRGB.
+	///
+	/// Colors outside the sRGB gamut have components outside the range `[0, 1]`.
+	pub fn to_linear(self) -> Rgb<T> {
+		let lms = mat3_mul(&LAB_TO_LMS, [self.l, self.a, self.b]);
+		let [r, g, b] = mat3_mul(&LMS_TO_RGB, [lms[0] * lms[0] * lms[0], lms[1] * lms[1] * lms[1], lms[2] * lms[2] * lms[2]]);
+		Rgb { r, g, b }
+	}
+	/// Linear interpolation between the colors.
+	pub fn lerp(self, rhs: Oklab<T>, t: T) -> Oklab<T> {
+		Oklab {
+			l: self.l + (rhs.l - self.l) * t,
+			a: self.a + (rhs.a - self.a) * t,
+			b: self.b + (rhs.b - self.b) * t,
+		}
+	}
+}
+
+impl<T> Oklch<T> {
+	/// Constructs a new Oklch color.
+	pub fn new(l: T, c: T, h: Rad<T>) -> Oklch<T> {
+		Oklch { l, c, h }
+	}
+}
+
+impl<T: Float> Oklch<T> {
+	/// Converts from linear sRGB.
+	pub fn from_linear(rgb: Rgb<T>) -> Oklch<T> {
+		Oklab::from_linear(rgb).into()
+	}
+	/// Converts to linear sRGB.
+	pub fn to_linear(self) -> Rgb<T> {
+		Oklab::from(self).to_linear()
+	}
+	/// Interpolates the lightness and chroma linearly and the hue along the shortest arc.
+	///
+	/// ```
+	/// use cvmath::prelude::Deg;
+	/// use cvmath::color::Oklch;
+	///
+	/// let a = Oklch::new(0.5, 0.1, Deg(350.0f64).into());
+	/// let b = Oklch::new(0.7, 0.2, Deg(30.0).into());
+	/// let mid = a.lerp(b, 0.5);
+	/// assert!((Deg::from(mid.h.norm()).0 - 10.0).abs() < 1e-9);
+	/// assert!((mid.l - 0.6).abs() < 1e-12);
+	/// ```
+	pub fn lerp(self, rhs: Oklch<T>, t: T) -> Oklch<T> {
+		Oklch {
+			l: self.l + (rhs.l - self.l) * t,
+			c: self.c + (rhs.c - self.c) * t,
+			h: self.h + (rhs.h - self.h).norm() * t,
+		}
+	}
+}
+
+impl<T: Float> From<Oklab<T>> for Oklch<T> {
+	fn from(lab: Oklab<T>) -> Oklch<T> {
+		Oklch {
+			l: lab.l,
+			c: (lab.a * lab.a + lab.b * lab.b).sqrt(),
+			h: Rad::atan2(lab.b, lab.a),
+		}
+	}
+}
+
+impl<T: Float> From<Oklch<T>> for Oklab<T> {
+	fn from(lch: Oklch<T>) -> Oklab<T> {
+		let (sin, cos) = lch.h.sin_cos();
+		Oklab { l: lch.l, a: lch.c * cos, b: lch.c * sin }
+	}
+}
+
+//----------------------------------------------------------------
+// YCbCr
+
+/// Range of the encoded YCbCr values.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ColorRange {
+	/// Values use the full range, in 8 bits luma spans `0..=255` and chroma is centered at `128`.
+	#[default]
+	Full,
+	/// Values use the studio range of broadcast video, in 8 bits luma spans `16..=235` and chroma `16..=240`.
+	Limited,
+}
+
+/// Color encoded as luma and blue and red chroma differences.
+///
+/// The components are normalized like 8-bit code values divided by `255`, neutral chroma is `0.5`.
+/// YCbCr encodes gamma corrected colors, convert sRGB encoded colors without linearizing them.
+///
+/// ```
+/// use cvmath::prelude::Rgb;
+/// use cvmath::color::{YCbCr, LumaWeights, ColorRange};
+///
+/// let white = Rgb(1.0f64, 1.0, 1.0);
+/// let ycc = YCbCr::from_rgb(white, LumaWeights::Rec709, ColorRange::Limited);
+/// assert!((ycc.y * 255.0 - 235.0).abs() < 1e-9 && (ycc.cb * 255.0 - 128.0).abs() < 1e-9);
+///
+/// let color = Rgb(0.8f64, 0.3, 0.1);
+/// let back = YCbCr::from_rgb(color, LumaWeights::Rec601, ColorRange::Full).to_rgb(LumaWeights::Rec601, ColorRange::Full);
+/// assert!((back.r - color.r).abs() < 1e-12 && (back.g - color.g).abs() < 1e-12 && (back.b - color.b).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct YCbCr<T> {
+	pub y: T,
+	pub cb: T,
+	pub cr: T,
+}
+
+impl<T> YCbCr<T> {
+	/// Constructs a new YCbCr color.
+	pub fn new(y: T, cb: T, cr: T) -> YCbCr<T> {
+		YCbCr { y, cb, cr }
+	}
+}
+
+impl<T: Float> YCbCr<T> {
+	/// Scale and offset of the luma and chroma for the range.
+	fn range(range: ColorRange) -> (T, T, T, T) {
+		let c = |v: f64| T::cast_from(v / 255.0);
+		match range {
+			ColorRange::Full => (T::one(), T::zero(), T::one(), c(128.0)),
+			ColorRange::Limited => (c(219.0), c(16.0), c(224.0), c(128.0)),
+		}
+	}
+	/// Encodes the gamma corrected color.
+	pub fn from_rgb(rgb: Rgb<T>, weights: LumaWeights, range: ColorRange) -> YCbCr<T> {
+		let k = weights.weights::<T>();
+		let two = T::one() + T::one();
+		let y = k.r * rgb.r + k.g * rgb.g + k.b * rgb.b;
+		let cb = (rgb.b - y) / (two * (T::one() - k.b));
+		let cr = (rgb.r - y) / (two * (T::one() - k.r));
+		let (y_scale, y_offset, c_scale, c_offset) = YCbCr::range(range);
+		YCbCr {
+			y: y * y_scale + y_offset,
+			cb: cb * c_scale + c_offset,
+			cr: cr * c_scale + c_offset,
+		}
+	}
+	/// Decodes to the gamma corrected color.
+	///
+	/// Values outside the range decode to components outside `[0, 1]`, they are not clamped.
+	pub fn to_rgb(self, weights: LumaWeights, range: ColorRange) -> Rgb<T> {
+		let k = weights.weights::<T>();
+		let two = T::one() + T::one();
+		let (y_scale, y_offset, c_scale, c_offset) = YCbCr::range(range);
+		let y = (self.y - y_offset) / y_scale;
+		let cb = (self.cb - c_offset) / c_scale;
+		let cr = (self.cr - c_offset) / c_scale;
+		let r = y + two * (T::one() - k.r) * cr;
+		let b = y + two * (T::one() - k.b) * cb;
+		let g = (y - k.r * r - k.b * b) / k.g;
+		Rgb { r, g, b }
+	}
+}
+
+/// Encodes the colors into the output slice.
+///
+/// Converts as many colors as fit in the shorter of the two slices.
+pub fn rgb_to_ycbcr<T: Float>(colors: &[Rgb<T>], out: &mut [YCbCr<T>], weights: LumaWeights, range: ColorRange) {
+	for (dst, &src) in out.iter_mut().zip(colors) {
+		*dst = YCbCr::from_rgb(src, weights, range);
+	}
+}
+
+/// Decodes the colors into the output slice.
+///
+/// Converts as many colors as fit in the shorter of the two slices.
+pub fn ycbcr_to_rgb<T: Float>(colors: &[YCbCr<T>], out: &mut [Rgb<T>], weights: LumaWeights, range: ColorRange) {
+	for (dst, &src) in out.iter_mut().zip(colors) {
+		*dst = src.to_rgb(weights, range);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn srgb_roundtrip() {
+		for i in 0..=1000 {
+			let x = i as f64 / 1000.0;
+			assert!((linear_to_srgb(srgb_to_linear(x)) - x).abs() < 1e-12);
+		}
+		// The segments meet at the threshold
+		assert!((srgb_to_linear(0.04045f64) - 0.0031308).abs() < 1e-6);
+		assert_eq!(1.0, srgb_to_linear(1.0f32));
+		for v in 0..=255 {
+			let c = Rgb(v, 255 - v, v / 2);
+			assert_eq!(c, Rgb::<f32>::from_u8(c).srgb_to_linear().linear_to_srgb().to_u8());
+		}
+	}
+
+	#[test]
+	fn oklab_roundtrip() {
+		for &rgb in &[Rgb(1.0f64, 0.0, 0.0), Rgb(0.2, 0.7, 0.1), Rgb(0.0, 0.0, 0.0), Rgb(0.05, 0.3, 0.9)] {
+			let lab = Oklab::from_linear(rgb);
+			let back = Oklch::from(lab).to_linear();
+			assert!((back.r - rgb.r).abs() < 1e-6 && (back.g - rgb.g).abs() < 1e-6 && (back.b - rgb.b).abs() < 1e-6);
+		}
+		// Reference value from the Oklab specification
+		let red = Oklab::from_linear(Rgb(1.0f64, 0.0, 0.0));
+		assert!((red.l - 0.627955).abs() < 1e-5 && (red.a - 0.224863).abs() < 1e-5 && (red.b - 0.125846).abs() < 1e-5);
+	}
+
+	#[test]
+	fn blend_modes() {
+		for i in 0..=20 {
+			let x = i as f64 / 20.0;
+			// Neutral sources leave the destination unchanged
+			assert!((BlendMode::SoftLight.apply(0.5, x) - x).abs() < 1e-12);
+			assert_eq!(x, BlendMode::Multiply.apply(1.0, x));
+			assert_eq!(x, BlendMode::Screen.apply(0.0, x));
+			// Overlay is hard light with the colors swapped and continuous at the midpoint
+			assert!((BlendMode::Overlay.apply(x, 0.5) - x).abs() < 1e-12);
+			for mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Screen, BlendMode::Overlay, BlendMode::Additive, BlendMode::SoftLight] {
+				let y = mode.apply(x, 1.0 - x);
+				assert!(y >= 0.0 && y <= 1.0);
+			}
+		}
+	}
+}
@@ -0,0 +1,197 @@
+/*!
+Random sampling.
+
+The `Standard` distribution samples every component of a vector independently.
+
+Bounds are distributions which uniformly sample points contained within them, see [`Bounds::contains`](../bounds/struct.Bounds.html#method.contains).
+
+Angles sampled from the `Standard` distribution are uniformly distributed in the range `[-180°, 180°)` or `[-π rad, π rad)`.
+
+### Examples
+
+```
+# extern crate cvmath;
+# extern crate rand;
+# use cvmath::prelude::{Vec2, Vec3, Rect, Point2};
+use cvmath::distributions::{UnitCircle, UnitBall};
+use cvmath::num::SpatialOrd;
+use rand::{Rng, SeedableRng};
+use rand::rngs::SmallRng;
+
+# fn main() {
+let mut rng = SmallRng::seed_from_u64(42);
+
+let v: Vec3<f32> = rng.gen();
+assert!(v.spatial_ge(&Vec3::dup(0.0)) && v.spatial_lt(&Vec3::dup(1.0)));
+
+let dir: Vec2<f64> = rng.sample(UnitCircle);
+assert!((dir.len() - 1.0).abs() < 1e-12);
+
+let p: Vec3<f64> = rng.sample(UnitBall);
+assert!(p.len() <= 1.0);
+
+let rect = Rect(Point2(-2, 5), Point2(2, 6));
+let p = rng.sample(rect);
+assert!(rect.contains(&p));
+# }
+```
+*/
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard, Uniform};
+use rand::distributions::uniform::SampleUniform;
+
+use num::Float;
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::Mat3;
+use bounds::Bounds;
+use angle::{Deg, Rad};
+
+macro_rules! standard {
+	($vec:ident { $($field:ident),+ }) => {
+		impl<T> Distribution<$vec<T>> for Standard where Standard: Distribution<T> {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $vec<T> {
+				$vec { $($field: rng.gen()),+ }
+			}
+		}
+		impl<T: SampleUniform + Copy> Distribution<$vec<T>> for Bounds<$vec<T>> {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $vec<T> {
+				$vec { $($field: Uniform::new_inclusive(self.mins.$field, self.maxs.$field).sample(rng)),+ }
+			}
+		}
+	};
+}
+
+standard!(Vec1 { x });
+standard!(Vec2 { x, y });
+standard!(Vec3 { x, y, z });
+standard!(Vec4 { x, y, z, w });
+
+/// Samples uniformly in the range `[-1, 1)`.
+fn signed<T: Float, R: Rng + ?Sized>(rng: &mut R) -> T where Standard: Distribution<T> {
+	let two = T::one() + T::one();
+	rng.gen::<T>() * two - T::one()
+}
+
+/// Uniformly samples directions on the unit circle.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UnitCircle;
+
+impl<T: Float> Distribution<Vec2<T>> for UnitCircle where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2<T> {
+		let (y, x) = (Rad::turn() * rng.gen::<T>()).sin_cos();
+		Vec2 { x, y }
+	}
+}
+
+/// Uniformly samples points inside the unit disk.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UnitDisk;
+
+impl<T: Float> Distribution<Vec2<T>> for UnitDisk where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec2<T> {
+		// The radius must be corrected for the area growing with the radius
+		let dir: Vec2<T> = UnitCircle.sample(rng);
+		dir * rng.gen::<T>().sqrt()
+	}
+}
+
+/// Uniformly samples directions on the unit sphere.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UnitSphere;
+
+impl<T: Float> Distribution<Vec3<T>> for UnitSphere where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3<T> {
+		// Archimedes' hat-box theorem: the height is uniformly distributed
+		let z = signed::<T, R>(rng);
+		let r = (T::one() - z * z).sqrt();
+		let (sin, cos) = (Rad::turn() * rng.gen::<T>()).sin_cos();
+		Vec3 { x: r * cos, y: r * sin, z }
+	}
+}
+
+/// Uniformly samples points inside the unit ball.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UnitBall;
+
+impl<T: Float> Distribution<Vec3<T>> for UnitBall where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3<T> {
+		// Rejection sampling accepts a little over half the samples from the enclosing cube
+		loop {
+			let p = Vec3 {
+				x: signed::<T, R>(rng),
+				y: signed::<T, R>(rng),
+				z: signed::<T, R>(rng),
+			};
+			if p.len_sqr() <= T::one() {
+				return p;
+			}
+		}
+	}
+}
+
+macro_rules! angle {
+	($ty:ident) => {
+		impl<T: Float> Distribution<$ty<T>> for Standard where Standard: Distribution<T> {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty<T> {
+				$ty::turn() * rng.gen::<T>() - $ty::half()
+			}
+		}
+	};
+}
+
+angle!(Deg);
+angle!(Rad);
+
+/// Uniformly samples rotations.
+///
+/// Rotations are sampled as unit quaternions with [Shoemake's method](http://planning.cs.uiuc.edu/node198.html),
+/// either as `Vec4` components `(x, y, z, w)` or as a rotation matrix.
+///
+/// Note that randomizing each euler angle independently does not produce uniformly distributed rotations.
+///
+/// ```
+/// # extern crate cvmath;
+/// # extern crate rand;
+/// # use cvmath::prelude::{Vec3, Vec4, Mat3};
+/// use cvmath::distributions::UniformRotation;
+/// use rand::{Rng, SeedableRng};
+/// use rand::rngs::SmallRng;
+///
+/// # fn main() {
+/// let mut rng = SmallRng::seed_from_u64(7);
+///
+/// let q: Vec4<f64> = rng.sample(UniformRotation);
+/// assert!((q.len() - 1.0).abs() < 1e-12);
+///
+/// let rot: Mat3<f64> = rng.sample(UniformRotation);
+/// assert!((rot.det() - 1.0).abs() < 1e-12);
+/// assert!(((rot * Vec3(3.0, 4.0, 0.0)).len() - 5.0).abs() < 1e-12);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UniformRotation;
+
+impl<T: Float> Distribution<Vec4<T>> for UniformRotation where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec4<T> {
+		let u1 = rng.gen::<T>();
+		let r1 = (T::one() - u1).sqrt();
+		let r2 = u1.sqrt();
+		let (s1, c1) = (Rad::turn() * rng.gen::<T>()).sin_cos();
+		let (s2, c2) = (Rad::turn() * rng.gen::<T>()).sin_cos();
+		Vec4 { x: r1 * s1, y: r1 * c1, z: r2 * s2, w: r2 * c2 }
+	}
+}
+
+impl<T: Float> Distribution<Mat3<T>> for UniformRotation where Standard: Distribution<T> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Mat3<T> {
+		let Vec4 { x, y, z, w }: Vec4<T> = self.sample(rng);
+		let one = T::one();
+		let two = one + one;
+		Mat3 {
+			a11: one - two * (y * y + z * z), a12: two * (x * y - z * w),       a13: two * (x * z + y * w),
+			a21: two * (x * y + z * w),       a22: one - two * (x * x + z * z), a23: two * (y * z - x * w),
+			a31: two * (x * z - y * w),       a32: two * (y * z + x * w),       a33: one - two * (x * x + y * y),
+		}
+	}
+}
@@ -0,0 +1,28 @@
+/*!
+Math errors.
+*/
+
+use std::{error, fmt};
+
+/// Error returned by the fallible `try_` operations when the input is degenerate.
+///
+/// The infallible counterparts return a fallback value instead, such as the original matrix for a singular `inverse`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MathError {
+	/// The matrix is singular and has no inverse.
+	Singular,
+	/// The vector has zero length and has no direction.
+	ZeroLength,
+	/// The input is degenerate, eg. collinear points don't span a plane.
+	Degenerate,
+}
+impl fmt::Display for MathError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match *self {
+			MathError::Singular => "singular matrix",
+			MathError::ZeroLength => "zero length vector",
+			MathError::Degenerate => "degenerate input",
+		})
+	}
+}
+impl error::Error for MathError {}
@@ -0,0 +1,119 @@
+/*!
+Fast approximate trigonometry.
+
+Polynomial approximations trading a little accuracy for throughput, useful for audio and animation code.
+
+The maximum absolute error is below `5e-6` for `sin`, `cos` and `sin_cos` with arguments in the range `[-2π, 2π]`
+and below `1e-5` radians for `atan2`. Outside this range the error grows with the magnitude of the angle due to argument reduction.
+
+### Examples
+
+```
+use cvmath::angle::{Angle, Rad};
+use cvmath::fast;
+
+let angle = Rad(1.0f32);
+assert!((fast::sin(angle) - angle.sin()).abs() < 5e-6);
+assert!((fast::cos(angle) - angle.cos()).abs() < 5e-6);
+
+let theta = fast::atan2(1.0f32, -1.0);
+assert!((theta - Rad::atan2(1.0, -1.0)).0.abs() < 1e-5);
+```
+*/
+
+use std::f64::consts::{PI, FRAC_PI_2};
+
+use num::Float;
+use angle::Rad;
+
+/// Reduces the angle to `[-π/2, π/2]`, preserving the sine.
+fn reduce<T: Float>(angle: T) -> T {
+	let pi = T::cast_from(PI);
+	let tau = T::cast_from(2.0 * PI);
+	let frac_pi_2 = T::cast_from(FRAC_PI_2);
+	// Wrap to [-π, π]
+	let x = angle.remainder(tau);
+	// Mirror around ±π/2
+	if x > frac_pi_2 { pi - x }
+	else if x < -frac_pi_2 { -pi - x }
+	else { x }
+}
+
+/// Taylor series up to `x⁹` for `x` in `[-π/2, π/2]`.
+fn sin_poly<T: Float>(x: T) -> T {
+	let x2 = x * x;
+	let c3 = T::cast_from(-1.0 / 6.0);
+	let c5 = T::cast_from(1.0 / 120.0);
+	let c7 = T::cast_from(-1.0 / 5040.0);
+	let c9 = T::cast_from(1.0 / 362880.0);
+	x + x * x2 * (c3 + x2 * (c5 + x2 * (c7 + x2 * c9)))
+}
+
+/// Approximates the sine.
+pub fn sin<T: Float>(angle: Rad<T>) -> T {
+	sin_poly(reduce(angle.0))
+}
+
+/// Approximates the cosine.
+pub fn cos<T: Float>(angle: Rad<T>) -> T {
+	sin_poly(reduce(angle.0 + T::cast_from(FRAC_PI_2)))
+}
+
+/// Approximates the sine and cosine.
+pub fn sin_cos<T: Float>(angle: Rad<T>) -> (T, T) {
+	(sin(angle), cos(angle))
+}
+
+/// Approximates the four quadrant arctangent of `y` and `x`.
+pub fn atan2<T: Float>(y: T, x: T) -> Rad<T> {
+	let zero = T::zero();
+	if x == zero && y == zero {
+		return Rad(zero);
+	}
+	let (ax, ay) = (x.abs(), y.abs());
+	// Evaluate on [0, 1] and mirror around π/4
+	let z = if ax >= ay { ay / ax } else { ax / ay };
+	let z2 = z * z;
+	let mut a = z * (T::cast_from(0.99997726) + z2 * (T::cast_from(-0.33262347) + z2 * (T::cast_from(0.19354346)
+		+ z2 * (T::cast_from(-0.11643287) + z2 * (T::cast_from(0.05265332) + z2 * T::cast_from(-0.01172120))))));
+	if ay > ax {
+		a = T::cast_from(FRAC_PI_2) - a;
+	}
+	if x < zero {
+		a = T::cast_from(PI) - a;
+	}
+	if y < zero {
+		a = -a;
+	}
+	Rad(a)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use angle::Angle;
+
+	#[test]
+	fn sin_cos_error() {
+		let mut max_err = 0.0f64;
+		for i in -100_000..=100_000 {
+			let angle = (i as f64 * (2.0 * PI / 100_000.0)) as f32;
+			let (s, c) = sin_cos(Rad(angle));
+			let angle = angle as f64;
+			max_err = max_err.max((s as f64 - angle.sin()).abs()).max((c as f64 - angle.cos()).abs());
+		}
+		assert!(max_err < 5e-6, "{}", max_err);
+	}
+
+	#[test]
+	fn atan2_error() {
+		let mut max_err = 0.0f64;
+		for i in 0..10_000 {
+			let (y, x) = Rad(i as f64 * (2.0 * PI / 10_000.0)).sin_cos();
+			let (y, x) = (y * 3.0, x * 3.0);
+			max_err = max_err.max((atan2(y as f32, x as f32).0 as f64 - y.atan2(x)).abs());
+		}
+		assert!(max_err < 1e-5, "{}", max_err);
+		assert_eq!(Rad(0.0f32), atan2(0.0, 0.0));
+	}
+}
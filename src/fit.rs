@@ -0,0 +1,110 @@
+/*!
+Least-squares fitting.
+
+The fits minimize the sum of squared orthogonal distances using [principal component analysis](../stats/fn.pca.html).
+
+### Examples
+
+```
+use cvmath::prelude::{Vec2, Vec3};
+use cvmath::fit;
+
+// Noisy samples of the plane z = 1
+let points = [Vec3(0.0f64, 0.0, 1.1), Vec3(4.0, 0.0, 0.9), Vec3(0.0, 4.0, 0.9), Vec3(4.0, 4.0, 1.1)];
+let plane = fit::plane(&points);
+assert_eq!(Vec3(0.0, 0.0, 1.0), plane.normal * plane.normal.z.signum());
+assert!(plane.signed_distance(Vec3(2.0, 2.0, 1.0)).abs() < 1e-12);
+
+// Samples of the line y = x + 1
+let points = [Vec2(0.0f64, 1.0), Vec2(1.0, 2.0), Vec2(3.0, 4.0)];
+let (point, direction) = fit::line2(&points);
+assert!((direction.x.abs() - 0.5f64.sqrt()).abs() < 1e-12);
+assert!((point.y - point.x - 1.0).abs() < 1e-12);
+```
+*/
+
+use num::Float;
+use vec::{Vec2, Vec3};
+use plane::Plane;
+use error::MathError;
+use stats;
+
+// A fit is degenerate when the variances along the axes that determine it are not clearly separated
+fn is_separated<T: Float>(larger: T, smaller: T, largest: T) -> bool {
+	larger - smaller > largest * T::epsilon().sqrt()
+}
+
+/// Fits a plane through the points.
+///
+/// The plane passes through the centroid and its unit normal is the direction of least variance, its sign is arbitrary.
+/// The normal is arbitrary if the points are collinear and zero if there are no points, see [`try_plane`](fn.try_plane.html).
+pub fn plane<T: Float>(points: &[Vec3<T>]) -> Plane<T> {
+	if points.is_empty() {
+		return Plane::new(Vec3::origin(), T::zero());
+	}
+	let (centroid, axes, _) = stats::pca(points);
+	Plane::from_point_normal(centroid, axes.z())
+}
+
+/// Fits a plane through the points.
+///
+/// Returns `Err(MathError::Degenerate)` if the points don't determine a unique normal,
+/// eg. if there are fewer than three points or the points are coincident or collinear.
+///
+/// ```
+/// use cvmath::prelude::Vec3;
+/// use cvmath::error::MathError;
+/// use cvmath::fit;
+///
+/// let points = [Vec3(0.0f64, 0.0, 1.0), Vec3(1.0, 0.0, 1.0), Vec3(0.0, 1.0, 1.0)];
+/// assert_eq!(Ok(1.0), fit::try_plane(&points).map(|plane| plane.normal.z.abs()));
+///
+/// let points = [Vec3(0.0f64, 0.0, 0.0), Vec3(1.0, 1.0, 1.0), Vec3(3.0, 3.0, 3.0)];
+/// assert_eq!(Err(MathError::Degenerate), fit::try_plane(&points));
+/// assert_eq!(Err(MathError::Degenerate), fit::try_plane::<f64>(&[]));
+/// ```
+pub fn try_plane<T: Float>(points: &[Vec3<T>]) -> Result<Plane<T>, MathError> {
+	let (centroid, axes, variances) = stats::pca(points);
+	if !is_separated(variances.y, variances.z, variances.x) {
+		return Err(MathError::Degenerate);
+	}
+	Ok(Plane::from_point_normal(centroid, axes.z()))
+}
+
+/// Fits a line through the points.
+///
+/// Returns the centroid and the unit direction of most variance, its sign is arbitrary.
+/// The direction is arbitrary if the points are coincident and zero if there are no points, see [`try_line2`](fn.try_line2.html).
+pub fn line2<T: Float>(points: &[Vec2<T>]) -> (Vec2<T>, Vec2<T>) {
+	if points.is_empty() {
+		return (Vec2::origin(), Vec2::origin());
+	}
+	let (centroid, axes, _) = stats::pca2(points);
+	(centroid, axes.x())
+}
+
+/// Fits a line through the points.
+///
+/// Returns `Err(MathError::Degenerate)` if the points don't determine a unique direction,
+/// eg. if there are fewer than two points, the points are coincident or spread equally in every direction.
+///
+/// ```
+/// use cvmath::prelude::Vec2;
+/// use cvmath::error::MathError;
+/// use cvmath::fit;
+///
+/// let points = [Vec2(0.0f64, 1.0), Vec2(2.0, 1.0), Vec2(5.0, 1.0)];
+/// assert_eq!(Ok(1.0), fit::try_line2(&points).map(|(_, direction)| direction.x.abs()));
+///
+/// let points = [Vec2(1.0f64, 1.0), Vec2(1.0, 1.0)];
+/// assert_eq!(Err(MathError::Degenerate), fit::try_line2(&points));
+/// let square = [Vec2(0.0f64, 0.0), Vec2(1.0, 0.0), Vec2(1.0, 1.0), Vec2(0.0, 1.0)];
+/// assert_eq!(Err(MathError::Degenerate), fit::try_line2(&square));
+/// ```
+pub fn try_line2<T: Float>(points: &[Vec2<T>]) -> Result<(Vec2<T>, Vec2<T>), MathError> {
+	let (centroid, axes, variances) = stats::pca2(points);
+	if !is_separated(variances.x, variances.y, variances.x) {
+		return Err(MathError::Degenerate);
+	}
+	Ok((centroid, axes.x()))
+}
@@ -0,0 +1,78 @@
+/*!
+Conversions to and from glam.
+
+```
+# extern crate cvmath;
+# extern crate glam;
+# use cvmath::prelude::{Vec3, Mat2};
+# fn main() {
+let v: glam::Vec3 = Vec3(1.0, 2.0, 3.0).into();
+assert_eq!(glam::Vec3::new(1.0, 2.0, 3.0), v);
+assert_eq!(Vec3(1.0, 2.0, 3.0), v.into());
+
+let m = Mat2::new(1.0, 2.0, 3.0, 4.0);
+let g: glam::Mat2 = m.into();
+assert_eq!(glam::Vec2::new(3.0, 4.0), g.row(1));
+assert_eq!(m, g.into());
+# }
+```
+*/
+
+use vec::{Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+
+macro_rules! convert_vec {
+	($vec:ident $glam:ty, $T:ty { $($field:ident),+ }) => {
+		impl From<$vec<$T>> for $glam {
+			fn from(v: $vec<$T>) -> $glam {
+				<$glam>::new($(v.$field),+)
+			}
+		}
+		impl From<$glam> for $vec<$T> {
+			fn from(v: $glam) -> $vec<$T> {
+				$vec { $($field: v.$field),+ }
+			}
+		}
+	};
+}
+
+convert_vec!(Vec2 glam::Vec2, f32 { x, y });
+convert_vec!(Vec3 glam::Vec3, f32 { x, y, z });
+convert_vec!(Vec3 glam::Vec3A, f32 { x, y, z });
+convert_vec!(Vec4 glam::Vec4, f32 { x, y, z, w });
+convert_vec!(Vec2 glam::DVec2, f64 { x, y });
+convert_vec!(Vec3 glam::DVec3, f64 { x, y, z });
+convert_vec!(Vec4 glam::DVec4, f64 { x, y, z, w });
+convert_vec!(Vec2 glam::IVec2, i32 { x, y });
+convert_vec!(Vec3 glam::IVec3, i32 { x, y, z });
+convert_vec!(Vec4 glam::IVec4, i32 { x, y, z, w });
+convert_vec!(Vec2 glam::UVec2, u32 { x, y });
+convert_vec!(Vec3 glam::UVec3, u32 { x, y, z });
+convert_vec!(Vec4 glam::UVec4, u32 { x, y, z, w });
+
+// glam matrices are stored column-major.
+macro_rules! convert_mat {
+	($mat:ident $glam:ty, $T:ty) => {
+		impl From<$mat<$T>> for $glam {
+			fn from(m: $mat<$T>) -> $glam {
+				<$glam>::from_cols_array_2d(&m.into_column_major())
+			}
+		}
+		impl From<$glam> for $mat<$T> {
+			fn from(m: $glam) -> $mat<$T> {
+				$mat::from_column_major(m.to_cols_array_2d())
+			}
+		}
+	};
+}
+
+convert_mat!(Mat2 glam::Mat2, f32);
+convert_mat!(Mat3 glam::Mat3, f32);
+convert_mat!(Affine2 glam::Affine2, f32);
+convert_mat!(Affine3 glam::Affine3A, f32);
+convert_mat!(Mat4 glam::Mat4, f32);
+convert_mat!(Mat2 glam::DMat2, f64);
+convert_mat!(Mat3 glam::DMat3, f64);
+convert_mat!(Affine2 glam::DAffine2, f64);
+convert_mat!(Affine3 glam::DAffine3, f64);
+convert_mat!(Mat4 glam::DMat4, f64);
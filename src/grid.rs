@@ -0,0 +1,234 @@
+/*!
+Mapping between world coordinates and grid cells.
+
+Cells are indexed by integer coordinates, the cell `(0, 0)` spans from the grid origin to the origin plus the cell size.
+Coordinates are floored so negative world coordinates map to negative cells consistently.
+
+### Examples
+
+```
+use cvmath::prelude::{Vec2, Point2, Rect};
+use cvmath::grid::Grid2;
+
+let grid = Grid2::new(Point2(0.0, 0.0), Vec2(16.0, 16.0));
+assert_eq!(Vec2(0, 0), grid.world_to_cell(Point2(0.0, 15.9)));
+assert_eq!(Vec2(-1, 2), grid.world_to_cell(Point2(-0.5, 32.0)));
+assert_eq!(Point2(-16.0, 32.0), grid.cell_to_world_min(Vec2(-1, 2)));
+assert_eq!(Point2(-8.0, 40.0), grid.cell_to_world_center(Vec2(-1, 2)));
+
+let cells: Vec<_> = grid.cells_overlapping(&Rect(Point2(-4.0, 10.0), Point2(20.0, 16.0))).collect();
+assert_eq!(vec![Vec2(-1, 0), Vec2(0, 0), Vec2(1, 0)], cells);
+
+let cells: Vec<_> = grid.traverse(Point2(8.0, 8.0), Vec2(32.0, 16.0)).take_while(|&(_, t)| t <= 1.0).collect();
+assert_eq!(vec![(Vec2(0, 0), 0.0), (Vec2(1, 0), 0.25), (Vec2(1, 1), 0.5), (Vec2(2, 1), 0.75)], cells);
+```
+*/
+
+use num::{Float, CastFrom};
+use vec::{Vec2, Vec3};
+use point::{Point2, Point3};
+use bounds::{Rect, Cuboid};
+use ray::Ray;
+
+macro_rules! grid {
+	($(#[$meta:meta])* $grid:ident $traverse:ident $vec:ident $point:ident $bounds:ident { $($field:ident),+ }) => {
+		$(#[$meta])*
+		#[derive(Copy, Clone, Debug, Default, PartialEq)]
+		#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+		pub struct $grid<T> {
+			/// World position of the minimum corner of the cell at index zero.
+			pub origin: $point<T>,
+			/// Size of the cells.
+			pub cell_size: $vec<T>,
+		}
+
+		impl<T> $grid<T> {
+			/// Constructs a new grid.
+			pub fn new(origin: $point<T>, cell_size: $vec<T>) -> $grid<T> {
+				$grid { origin, cell_size }
+			}
+		}
+
+		impl<T: Float> $grid<T> where i32: CastFrom<T> {
+			/// Returns the index of the cell containing the world position.
+			pub fn world_to_cell(&self, pt: $point<T>) -> $vec<i32> {
+				$vec { $($field: i32::cast_from(((pt.$field - self.origin.$field) / self.cell_size.$field).floor())),+ }
+			}
+			/// Returns the world position of the minimum corner of the cell.
+			pub fn cell_to_world_min(&self, cell: $vec<i32>) -> $point<T> {
+				$point { $($field: self.origin.$field + T::cast_from(cell.$field as f64) * self.cell_size.$field),+ }
+			}
+			/// Returns the world position of the center of the cell.
+			pub fn cell_to_world_center(&self, cell: $vec<i32>) -> $point<T> {
+				let half = T::one() / (T::one() + T::one());
+				$point { $($field: self.origin.$field + (T::cast_from(cell.$field as f64) + half) * self.cell_size.$field),+ }
+			}
+			/// Returns the inclusive range of cells overlapping the bounds.
+			///
+			/// Bounds ending exactly on a cell boundary don't overlap the next cell.
+			fn cell_range(&self, bounds: &$bounds<T>) -> ($vec<i32>, $vec<i32>) {
+				let mins = self.world_to_cell(bounds.mins);
+				let maxs = $vec { $($field: i32::cast_from(((bounds.maxs.$field - self.origin.$field) / self.cell_size.$field).ceil()) - 1),+ };
+				(mins, $vec { $($field: if maxs.$field < mins.$field { mins.$field } else { maxs.$field }),+ })
+			}
+			/// Iterates over the cells crossed by the ray in order using the Amanatides–Woo algorithm.
+			///
+			/// Yields each cell with the ray parameter `t` where the ray enters it, starting with the cell containing the origin at `t = 0`.
+			/// The parameter is measured in multiples of the direction.
+			/// The iterator is unbounded unless the direction is zero, limit it with `take_while` on the parameter or the cell.
+			///
+			/// When the ray passes exactly through a cell corner the cells are stepped one axis at a time.
+			pub fn traverse(&self, origin: $point<T>, direction: $vec<T>) -> $traverse<T> {
+				let zero = T::zero();
+				let inf = T::one() / zero;
+				let cell = self.world_to_cell(origin);
+				let min = self.cell_to_world_min(cell);
+				$traverse {
+					cell,
+					step: $vec { $($field: if direction.$field > zero { 1 } else if direction.$field < zero { -1 } else { 0 }),+ },
+					t_max: $vec { $($field:
+						if direction.$field > zero { (min.$field + self.cell_size.$field - origin.$field) / direction.$field }
+						else if direction.$field < zero { (min.$field - origin.$field) / direction.$field }
+						else { inf }
+					),+ },
+					t_delta: $vec { $($field: if direction.$field != zero { self.cell_size.$field / direction.$field.abs() } else { inf }),+ },
+					t: Some(zero),
+				}
+			}
+		}
+
+		/// Iterator returned by the grid's `traverse` method.
+		#[derive(Clone, Debug)]
+		pub struct $traverse<T> {
+			cell: $vec<i32>,
+			step: $vec<i32>,
+			t_max: $vec<T>,
+			t_delta: $vec<T>,
+			t: Option<T>,
+		}
+
+		impl<T: Float> Iterator for $traverse<T> {
+			type Item = ($vec<i32>, T);
+			fn next(&mut self) -> Option<($vec<i32>, T)> {
+				let t = self.t?;
+				let cell = self.cell;
+				// Step along the axis whose cell boundary is crossed first
+				let t_next = self.t_max.reduce(T::min);
+				if t_next.is_finite() {
+					let mut stepped = false;
+					$(
+						if !stepped && self.t_max.$field == t_next {
+							self.cell.$field += self.step.$field;
+							self.t_max.$field += self.t_delta.$field;
+							stepped = true;
+						}
+					)+
+					self.t = Some(t_next);
+				}
+				else {
+					self.t = None;
+				}
+				Some((cell, t))
+			}
+		}
+	};
+}
+
+grid! {
+	/// Two-dimensional grid for tile maps.
+	Grid2 Traverse2 Vec2 Point2 Rect { x, y }
+}
+
+grid! {
+	/// Three-dimensional grid for voxels.
+	Grid3 Traverse3 Vec3 Point3 Cuboid { x, y, z }
+}
+
+impl<T: Float> Grid2<T> where i32: CastFrom<T> {
+	/// Iterates over the cells overlapping the rectangle, X varies fastest.
+	pub fn cells_overlapping(&self, rect: &Rect<T>) -> impl Iterator<Item = Vec2<i32>> {
+		let (mins, maxs) = self.cell_range(rect);
+		(mins.y..=maxs.y).flat_map(move |y| (mins.x..=maxs.x).map(move |x| Vec2 { x, y }))
+	}
+}
+
+impl<T: Float> Grid3<T> where i32: CastFrom<T> {
+	/// Iterates over the cells overlapping the bounding box, X varies fastest and Z slowest.
+	pub fn cells_overlapping(&self, bounds: &Cuboid<T>) -> impl Iterator<Item = Vec3<i32>> {
+		let (mins, maxs) = self.cell_range(bounds);
+		(mins.z..=maxs.z).flat_map(move |z| (mins.y..=maxs.y).flat_map(move |y| (mins.x..=maxs.x).map(move |x| Vec3 { x, y, z })))
+	}
+	/// Iterates over the cells crossed by the ray, see [`traverse`](#method.traverse).
+	///
+	/// The parameter `t` matches [`Ray::at`](../ray/struct.Ray.html#method.at).
+	pub fn traverse_ray(&self, ray: &Ray<T>) -> Traverse3<T> {
+		self.traverse(ray.origin, ray.direction)
+	}
+}
+
+/// Iterates over the cells of size `cell_size` crossed by the ray, the cell `(0, 0, 0)` has its minimum corner at the world origin.
+///
+/// Yields each cell with the ray parameter `t` where the ray enters it, see [`Grid3::traverse`](struct.Grid3.html#method.traverse).
+///
+/// ```
+/// use cvmath::prelude::{Ray, Point3, Vec3};
+/// use cvmath::grid;
+///
+/// let ray = Ray::new(Point3(0.5, 0.5, 0.5), Vec3(2.0, 1.0, 0.0));
+/// let cells: Vec<_> = grid::traverse(&ray, Vec3(1.0, 1.0, 1.0)).take_while(|&(_, t)| t <= 1.0).collect();
+/// assert_eq!(vec![(Vec3(0, 0, 0), 0.0), (Vec3(1, 0, 0), 0.25), (Vec3(1, 1, 0), 0.5), (Vec3(2, 1, 0), 0.75)], cells);
+/// ```
+pub fn traverse<T: Float>(ray: &Ray<T>, cell_size: Vec3<T>) -> Traverse3<T> where i32: CastFrom<T> {
+	Grid3::new(Point3::origin(), cell_size).traverse_ray(ray)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn grid3() {
+		let grid = Grid3::new(Point3(1.0, -2.0, 0.5), Vec3(2.0, 2.0, 0.5));
+		assert_eq!(Vec3(-1, 0, -2), grid.world_to_cell(Point3(0.0, -2.0, -0.5)));
+		for &cell in &[Vec3(-3, 7, 0), Vec3(0, 0, 0), Vec3(5, -4, -9)] {
+			assert_eq!(cell, grid.world_to_cell(grid.cell_to_world_min(cell)));
+			assert_eq!(cell, grid.world_to_cell(grid.cell_to_world_center(cell)));
+		}
+		let cells: Vec<_> = grid.cells_overlapping(&Cuboid(Point3(1.0, -2.0, 0.5), Point3(3.5, 0.0, 0.6))).collect();
+		assert_eq!(vec![Vec3(0, 0, 0), Vec3(1, 0, 0)], cells);
+		// Degenerate bounds overlap the cell containing them
+		let cells: Vec<_> = grid.cells_overlapping(&Cuboid(Point3(1.0, -2.0, 0.5), Point3(1.0, -2.0, 0.5))).collect();
+		assert_eq!(vec![Vec3(0, 0, 0)], cells);
+	}
+
+	#[test]
+	fn traverse() {
+		let grid = Grid3::new(Point3(0.0, 0.0, 0.0), Vec3(1.0, 1.0, 1.0));
+		let origin = Point3(0.5, 2.25, -0.75);
+		let direction = Vec3(3.0, -1.5, 2.0);
+		let mut prev = grid.world_to_cell(origin);
+		let mut prev_t = -1.0;
+		let mut count = 0;
+		for (cell, t) in grid.traverse(origin, direction).take_while(|&(_, t)| t <= 1.0) {
+			assert!(t >= prev_t);
+			// Each step moves to a face neighbor and the entry point lies on the cell
+			let d = (cell - prev).map(i32::abs);
+			assert!(count == 0 || d.x + d.y + d.z == 1);
+			let entry = origin + direction * t;
+			let min = grid.cell_to_world_min(cell);
+			assert!((entry - min).map(|e| e >= -1e-9 && e <= 1.0 + 1e-9) == Vec3(true, true, true));
+			prev = cell;
+			prev_t = t;
+			count += 1;
+		}
+		assert_eq!(prev, grid.world_to_cell(origin + direction));
+		assert_eq!(count, 1 + 3 + 2 + 2);
+
+		let ray = Ray::new(origin, direction);
+		assert!(grid.traverse_ray(&ray).take(8).eq(grid.traverse(origin, direction).take(8)));
+		assert!(super::traverse(&ray, grid.cell_size).take(8).eq(grid.traverse(origin, direction).take(8)));
+
+		// Zero direction yields only the starting cell
+		assert_eq!(vec![(Vec3(0, 2, -1), 0.0)], grid.traverse(origin, Vec3(0.0, 0.0, 0.0)).collect::<Vec<_>>());
+	}
+}
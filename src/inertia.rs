@@ -0,0 +1,96 @@
+/*!
+Inertia tensors of solid primitive shapes.
+
+The tensors are for a uniform density and expressed about the center of mass in the shape's local frame.
+Shapes with an axis of symmetry (cylinder and capsule) are aligned with the y axis.
+
+### Examples
+
+```
+use cvmath::prelude::{Mat3, Vec3};
+use cvmath::inertia;
+
+let sphere = inertia::sphere(5.0, 2.0);
+assert_eq!(Mat3::scale(Vec3::dup(8.0)), sphere);
+
+// Move the pivot to the surface of the sphere
+let pivot = inertia::parallel_axis(sphere, 5.0, Vec3(2.0, 0.0, 0.0));
+assert_eq!(Mat3::scale(Vec3(8.0, 28.0, 28.0)), pivot);
+```
+*/
+
+use num::Float;
+use vec::Vec3;
+use mat::Mat3;
+
+/// Solid sphere with the given radius.
+pub fn sphere<T: Float>(mass: T, radius: T) -> Mat3<T> {
+	Mat3::scale(Vec3::dup(T::cast_from(0.4) * mass * radius * radius))
+}
+
+/// Solid box with the given half extents.
+pub fn box3<T: Float>(mass: T, half_extents: Vec3<T>) -> Mat3<T> {
+	let Vec3 { x, y, z } = half_extents.sqr() * (mass / T::cast_from(3.0));
+	Mat3::scale(Vec3(y + z, x + z, x + y))
+}
+
+/// Solid cylinder along the y axis with the given radius and half height.
+pub fn cylinder<T: Float>(mass: T, radius: T, half_height: T) -> Mat3<T> {
+	let r2 = radius * radius;
+	let axial = T::cast_from(0.5) * mass * r2;
+	let lateral = mass * (T::cast_from(3.0) * r2 + T::cast_from(4.0) * half_height * half_height) / T::cast_from(12.0);
+	Mat3::scale(Vec3(lateral, axial, lateral))
+}
+
+/// Solid capsule along the y axis with the given radius and half height of its cylindrical part.
+///
+/// The mass is distributed by volume between the cylinder and the two hemispherical caps.
+pub fn capsule<T: Float>(mass: T, radius: T, half_height: T) -> Mat3<T> {
+	let (r, h) = (radius, half_height + half_height);
+	let r2 = r * r;
+	// Volumes without the common factor pi r^2
+	let cylinder_volume = h;
+	let spheres_volume = T::cast_from(4.0 / 3.0) * r;
+	let m_cylinder = mass * cylinder_volume / (cylinder_volume + spheres_volume);
+	let m_spheres = mass - m_cylinder;
+	let axial = m_cylinder * r2 * T::cast_from(0.5) + m_spheres * r2 * T::cast_from(0.4);
+	let lateral = m_cylinder * (r2 * T::cast_from(0.25) + h * h / T::cast_from(12.0))
+		+ m_spheres * (r2 * T::cast_from(0.4) + h * h * T::cast_from(0.25) + h * r * T::cast_from(0.375));
+	Mat3::scale(Vec3(lateral, axial, lateral))
+}
+
+/// Transfers the inertia tensor about the center of mass to a pivot at the given offset from the center of mass.
+///
+/// Implements the parallel axis theorem: `I + m (|d|² E - d dᵀ)`.
+pub fn parallel_axis<T: Float>(inertia: Mat3<T>, mass: T, offset: Vec3<T>) -> Mat3<T> {
+	let d = offset;
+	let dd = d.len_sqr();
+	let i = inertia;
+	Mat3::new(
+		i.a11 + mass * (dd - d.x * d.x), i.a12 - mass * d.x * d.y, i.a13 - mass * d.x * d.z,
+		i.a21 - mass * d.y * d.x, i.a22 + mass * (dd - d.y * d.y), i.a23 - mass * d.y * d.z,
+		i.a31 - mass * d.z * d.x, i.a32 - mass * d.z * d.y, i.a33 + mass * (dd - d.z * d.z),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: Mat3<f64>, b: Mat3<f64>) -> bool {
+		a.into_row_major().iter().flatten().zip(b.into_row_major().iter().flatten()).all(|(x, y)| (x - y).abs() < 1e-12)
+	}
+
+	#[test]
+	fn degenerate_shapes() {
+		// A capsule without a cylinder is a sphere
+		assert!(close(sphere(3.0, 1.5), capsule(3.0, 1.5, 0.0)));
+		// A cube is isotropic
+		assert!(close(Mat3::scale(Vec3::dup(2.0 / 3.0)), box3(1.0, Vec3::dup(1.0))));
+		// Thin rod about its center
+		assert!(close(Mat3::scale(Vec3(4.0 / 12.0, 0.0, 4.0 / 12.0)), cylinder(1.0, 0.0, 1.0)));
+		// Point mass
+		let point = parallel_axis(Mat3::null(), 2.0, Vec3(1.0, 2.0, 0.0));
+		assert!(close(Mat3::new(8.0, -4.0, 0.0, -4.0, 2.0, 0.0, 0.0, 0.0, 10.0), point));
+	}
+}
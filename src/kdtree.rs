@@ -0,0 +1,198 @@
+/*!
+k-d tree for nearest neighbor queries.
+
+### Examples
+
+```
+use cvmath::prelude::Vec2;
+use cvmath::kdtree::KdTree;
+
+let points = [Vec2(0.0, 0.0), Vec2(5.0, 1.0), Vec2(2.0, 2.0), Vec2(-3.0, 4.0), Vec2(1.0, -1.0)];
+let tree = KdTree::new(&points);
+
+// Queries return indices into the points the tree was built from
+assert_eq!(Some(2), tree.nearest(Vec2(2.5, 2.5)));
+assert_eq!(vec![0, 4, 2], tree.k_nearest(Vec2(0.5, 0.0), 3));
+
+let mut within = tree.within_radius(Vec2(0.0, 0.0), 3.0);
+within.sort();
+assert_eq!(vec![0, 2, 4], within);
+```
+*/
+
+use num::{Float, Zero};
+use vec::{Vec2, Vec3};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Points which can be stored in a [`KdTree`](struct.KdTree.html).
+pub trait KdPoint: Copy {
+	/// The coordinate type.
+	type T: Float;
+	/// The number of dimensions.
+	const DIM: usize;
+	/// Returns the coordinate along the axis.
+	fn coord(&self, axis: usize) -> Self::T;
+	/// Returns the squared distance between the points.
+	fn dist_sqr(&self, other: &Self) -> Self::T;
+}
+
+impl<T: Float> KdPoint for Vec2<T> {
+	type T = T;
+	const DIM: usize = 2;
+	fn coord(&self, axis: usize) -> T {
+		if axis == 0 { self.x } else { self.y }
+	}
+	fn dist_sqr(&self, other: &Vec2<T>) -> T {
+		Vec2::dist_sqr(*self, *other)
+	}
+}
+
+impl<T: Float> KdPoint for Vec3<T> {
+	type T = T;
+	const DIM: usize = 3;
+	fn coord(&self, axis: usize) -> T {
+		match axis { 0 => self.x, 1 => self.y, _ => self.z }
+	}
+	fn dist_sqr(&self, other: &Vec3<T>) -> T {
+		Vec3::dist_sqr(*self, *other)
+	}
+}
+
+/// k-d tree over 2D or 3D points.
+///
+/// The tree is built once from a slice of points and is balanced by splitting at the median.
+/// Queries return the indices of the points in the slice.
+#[derive(Clone, Debug)]
+pub struct KdTree<V> {
+	// Implicit tree, each node is the median of its subslice
+	nodes: Vec<(V, usize)>,
+}
+
+impl<V: KdPoint> KdTree<V> {
+	/// Builds the tree from the points.
+	pub fn new(points: &[V]) -> KdTree<V> {
+		let mut nodes: Vec<(V, usize)> = points.iter().cloned().zip(0..).collect();
+		build(&mut nodes, 0);
+		KdTree { nodes }
+	}
+	/// Returns the number of points.
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+	/// Returns whether the tree is empty.
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+	/// Finds the point nearest to `pt`.
+	///
+	/// Returns `None` if the tree is empty.
+	pub fn nearest(&self, pt: V) -> Option<usize> {
+		self.k_nearest(pt, 1).first().cloned()
+	}
+	/// Finds the `k` points nearest to `pt`, sorted from nearest to farthest.
+	pub fn k_nearest(&self, pt: V, k: usize) -> Vec<usize> {
+		let mut best = Vec::with_capacity(k + 1);
+		if k > 0 {
+			k_nearest(&self.nodes, 0, &pt, k, &mut best);
+		}
+		best.into_iter().map(|(_, index)| index).collect()
+	}
+	/// Finds all the points within `radius` of `pt` in no particular order.
+	pub fn within_radius(&self, pt: V, radius: V::T) -> Vec<usize> {
+		let mut result = Vec::new();
+		within_radius(&self.nodes, 0, &pt, radius * radius, &mut result);
+		result
+	}
+}
+
+fn build<V: KdPoint>(nodes: &mut [(V, usize)], depth: usize) {
+	if nodes.len() <= 1 {
+		return;
+	}
+	let axis = depth % V::DIM;
+	let mid = nodes.len() / 2;
+	nodes.select_nth_unstable_by(mid, |a, b| a.0.coord(axis).partial_cmp(&b.0.coord(axis)).unwrap_or(::std::cmp::Ordering::Equal));
+	let (left, right) = nodes.split_at_mut(mid);
+	build(left, depth + 1);
+	build(&mut right[1..], depth + 1);
+}
+
+fn k_nearest<V: KdPoint>(nodes: &[(V, usize)], depth: usize, pt: &V, k: usize, best: &mut Vec<(V::T, usize)>) {
+	if nodes.is_empty() {
+		return;
+	}
+	let axis = depth % V::DIM;
+	let mid = nodes.len() / 2;
+	let (node, index) = nodes[mid];
+
+	let dist_sqr = node.dist_sqr(pt);
+	if best.len() < k || dist_sqr < best[best.len() - 1].0 {
+		let pos = best.iter().position(|&(d, _)| dist_sqr < d).unwrap_or(best.len());
+		best.insert(pos, (dist_sqr, index));
+		best.truncate(k);
+	}
+
+	let diff = pt.coord(axis) - node.coord(axis);
+	let (near, far) = if diff < V::T::zero() { (&nodes[..mid], &nodes[mid + 1..]) } else { (&nodes[mid + 1..], &nodes[..mid]) };
+	k_nearest(near, depth + 1, pt, k, best);
+	// Only visit the other side if the splitting plane is closer than the current candidates
+	if best.len() < k || diff * diff < best[best.len() - 1].0 {
+		k_nearest(far, depth + 1, pt, k, best);
+	}
+}
+
+fn within_radius<V: KdPoint>(nodes: &[(V, usize)], depth: usize, pt: &V, radius_sqr: V::T, result: &mut Vec<usize>) {
+	if nodes.is_empty() {
+		return;
+	}
+	let axis = depth % V::DIM;
+	let mid = nodes.len() / 2;
+	let (node, index) = nodes[mid];
+
+	if node.dist_sqr(pt) <= radius_sqr {
+		result.push(index);
+	}
+
+	let diff = pt.coord(axis) - node.coord(axis);
+	if diff <= V::T::zero() || diff * diff <= radius_sqr {
+		within_radius(&nodes[..mid], depth + 1, pt, radius_sqr, result);
+	}
+	if diff >= V::T::zero() || diff * diff <= radius_sqr {
+		within_radius(&nodes[mid + 1..], depth + 1, pt, radius_sqr, result);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_brute_force() {
+		let points: Vec<Vec3<f64>> = (0..300).map(|i| {
+			let t = i as f64;
+			Vec3((t * 0.731).sin() * 10.0, (t * 1.37).cos() * 10.0, (t * 0.193).sin() * 10.0)
+		}).collect();
+		let tree = KdTree::new(&points);
+		assert_eq!(300, tree.len());
+
+		for i in 0..20 {
+			let t = i as f64;
+			let query = Vec3(t.cos() * 8.0, t.sin() * 8.0, t - 10.0);
+
+			let mut brute: Vec<usize> = (0..points.len()).collect();
+			brute.sort_by(|&a, &b| points[a].dist_sqr(query).partial_cmp(&points[b].dist_sqr(query)).unwrap());
+			assert_eq!(Some(brute[0]), tree.nearest(query));
+			assert_eq!(&brute[..7], &tree.k_nearest(query, 7)[..]);
+
+			let mut within = tree.within_radius(query, 4.0);
+			within.sort();
+			let mut expected: Vec<usize> = brute.into_iter().filter(|&i| points[i].dist(query) <= 4.0).collect();
+			expected.sort();
+			assert_eq!(expected, within);
+		}
+
+		assert_eq!(None, KdTree::<Vec2<f32>>::new(&[]).nearest(Vec2(0.0, 0.0)));
+	}
+}
@@ -0,0 +1,136 @@
+/*!
+Wrappers to use float vectors as map keys.
+
+### Examples
+
+Deduplicate vertices by position:
+
+```
+use std::collections::HashMap;
+use cvmath::prelude::Vec3;
+use cvmath::key::HashableVec;
+
+let positions = [Vec3(0.0, 1.0, 0.0), Vec3(1.0, 0.0, 0.0), Vec3(-0.0, 1.0, 0.0)];
+let mut unique = HashMap::new();
+let indices: Vec<usize> = positions.iter().map(|&pos| {
+    let next = unique.len();
+    *unique.entry(HashableVec(pos)).or_insert(next)
+}).collect();
+assert_eq!(indices, [0, 1, 0]);
+```
+
+Sort points and key a `BTreeMap` by position:
+
+```
+use std::collections::BTreeMap;
+use cvmath::prelude::Vec2;
+use cvmath::key::OrderedVec;
+
+let mut points = vec![Vec2(1.0, 2.0), Vec2(-1.0, 5.0), Vec2(1.0, -3.0)];
+points.sort_by(Vec2::total_cmp);
+assert_eq!(points, [Vec2(-1.0, 5.0), Vec2(1.0, -3.0), Vec2(1.0, 2.0)]);
+
+let map: BTreeMap<_, _> = points.iter().enumerate().map(|(i, &pt)| (OrderedVec(pt), i)).collect();
+assert_eq!(map.get(&OrderedVec(Vec2(1.0, -3.0))), Some(&1));
+```
+*/
+
+use std::{cmp, hash, fmt};
+
+use num::{CanonicalBits, TotalOrd};
+
+/// Hashes and compares a value by its canonical bit representation.
+///
+/// Negative zero equals positive zero and all NaN values are equal to each other, see [`CanonicalBits`](../num/trait.CanonicalBits.html).
+/// This makes `Eq` and `Hash` consistent, unlike the float `PartialEq`.
+#[derive(Copy, Clone, Default)]
+#[repr(transparent)]
+pub struct HashableVec<V>(pub V);
+
+impl<V: Copy + CanonicalBits> HashableVec<V> {
+	/// Returns the canonical bits used for comparison and hashing.
+	pub fn bits(&self) -> V::Bits {
+		self.0.canonical_bits()
+	}
+}
+impl<V> From<V> for HashableVec<V> {
+	fn from(v: V) -> HashableVec<V> {
+		HashableVec(v)
+	}
+}
+impl<V: Copy + CanonicalBits> PartialEq for HashableVec<V> {
+	fn eq(&self, rhs: &HashableVec<V>) -> bool {
+		self.bits() == rhs.bits()
+	}
+}
+impl<V: Copy + CanonicalBits> Eq for HashableVec<V> {}
+impl<V: Copy + CanonicalBits> hash::Hash for HashableVec<V> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.bits().hash(state)
+	}
+}
+impl<V: fmt::Debug> fmt::Debug for HashableVec<V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+/// Orders a value with a total order, see [`TotalOrd`](../num/trait.TotalOrd.html).
+///
+/// Vectors are ordered lexicographically, so float vectors can be sorted and used as `BTreeMap` keys.
+/// Negative zero and positive zero are distinct, as are NaN values with different bits.
+#[derive(Copy, Clone, Default)]
+#[repr(transparent)]
+pub struct OrderedVec<V>(pub V);
+
+impl<V> From<V> for OrderedVec<V> {
+	fn from(v: V) -> OrderedVec<V> {
+		OrderedVec(v)
+	}
+}
+impl<V: TotalOrd> PartialEq for OrderedVec<V> {
+	fn eq(&self, rhs: &OrderedVec<V>) -> bool {
+		self.0.total_cmp(&rhs.0) == cmp::Ordering::Equal
+	}
+}
+impl<V: TotalOrd> Eq for OrderedVec<V> {}
+impl<V: TotalOrd> PartialOrd for OrderedVec<V> {
+	fn partial_cmp(&self, rhs: &OrderedVec<V>) -> Option<cmp::Ordering> {
+		Some(self.cmp(rhs))
+	}
+}
+impl<V: TotalOrd> Ord for OrderedVec<V> {
+	fn cmp(&self, rhs: &OrderedVec<V>) -> cmp::Ordering {
+		self.0.total_cmp(&rhs.0)
+	}
+}
+impl<V: fmt::Debug> fmt::Debug for OrderedVec<V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use vec::Vec2;
+
+	#[test]
+	fn canonical() {
+		assert_eq!(HashableVec(Vec2(0.0f32, 1.0)), HashableVec(Vec2(-0.0, 1.0)));
+		assert_eq!(HashableVec(Vec2(f64::NAN, 1.0)), HashableVec(Vec2(-f64::NAN, 1.0)));
+		assert!(HashableVec(Vec2(1.0f64, 2.0)) != HashableVec(Vec2(2.0, 1.0)));
+		assert_eq!(Vec2(0u64, 0x3ff0000000000000), Vec2(-0.0f64, 1.0).canonical_bits());
+	}
+
+	#[test]
+	fn total_order() {
+		let mut points = [Vec2(f64::NAN, 0.0), Vec2(0.0, 1.0), Vec2(-0.0, 1.0), Vec2(f64::NEG_INFINITY, 2.0)];
+		points.sort_by_key(|&pt| OrderedVec(pt));
+		assert_eq!(points[0], Vec2(f64::NEG_INFINITY, 2.0));
+		assert_eq!(points[1].x.to_bits(), (-0.0f64).to_bits());
+		assert_eq!(points[2].x.to_bits(), 0.0f64.to_bits());
+		assert!(points[3].x.is_nan());
+		assert!(OrderedVec(Vec2(f64::NAN, 0.0)) == OrderedVec(Vec2(f64::NAN, 0.0)));
+	}
+}
@@ -1,29 +1,124 @@
 /*!
 */
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", test)))]
+extern crate core as std;
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+extern crate libm;
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("cvmath requires either the `std` or the `libm` feature");
+
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+#[cfg(feature = "approx")]
+extern crate approx;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
 mod macros;
 
 pub mod num;
 pub mod angle;
+pub mod fast;
+pub mod error;
+#[macro_use]
+pub mod approx_eq;
 
 pub mod vec;
+pub mod vecn;
 pub mod bools;
 mod packed;
 pub mod mat;
 pub mod euler;
+pub mod std140;
+pub mod wide;
+pub mod batch;
+pub mod tangent;
+pub mod normals;
+pub mod stats;
+pub mod fit;
+pub mod inertia;
+pub mod kdtree;
+pub mod grid;
+pub mod noise;
+pub mod wrap;
+pub mod key;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub use serde_impl::named as serde_named;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+#[cfg(feature = "glam")]
+mod glam_impl;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impl;
+#[cfg(feature = "num-traits")]
+mod num_traits_impl;
+#[cfg(feature = "approx")]
+mod approx_impl;
+#[cfg(feature = "rand")]
+pub mod distributions;
+#[cfg(feature = "rand")]
+pub mod sample;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "simd")]
+pub mod simd;
 
 pub mod point;
 pub mod bounds;
 pub mod line2;
+pub mod plane;
+pub mod ray;
+pub mod sweep;
+pub mod sphere;
+pub mod circle;
+pub mod triangle;
+pub mod polygon;
+pub mod projection;
+pub mod color;
 
 pub mod prelude {
-	pub use angle::{Rad, Deg, Angle};
-	pub use vec::{Vec2, Vec3, Vec4, X, Y, Z, W};
-	pub use bools::{Bool2, Bool3, Bool4};
-	pub use mat::{Mat2, Affine2, Mat3, Affine3};
+	pub use angle::{Rad, Deg, Angle, ArcRange};
+	pub use vec::{Vec1, Vec2, Vec3, Vec3A, Vec4, X, Y, Z, W};
+	pub use vecn::VecN;
+	pub use bools::{Bool1, Bool2, Bool3, Bool4};
+	pub use mat::{Mat2, Affine2, Mat3, Affine3, Mat4, Mat};
 	pub use euler::{Euler};
+	pub use error::MathError;
 
 	pub use point::{Point2, Point3};
 	pub use bounds::{Bounds, Rect, Cuboid};
 	pub use line2::{self, Line2};
+	pub use plane::Plane;
+	pub use ray::Ray;
+	pub use sphere::Sphere;
+	pub use circle::Circle;
+	pub use triangle::{Triangle2, Triangle3};
+	pub use color::{Rgb, Rgba};
 }
@@ -4,6 +4,7 @@ Line 2D segment.
 
 use std::ops::Range;
 use point::Point2;
+use vec::Vec2;
 use num::Float;
 
 pub type Line2<T> = Range<Point2<T>>;
@@ -114,3 +115,200 @@ pub fn x_intercept<T: Float>(line: Line2<T>) -> Option<T> {
 	let x = line.start.x + (line.end.x - line.start.x) * f;
 	Some(x)
 }
+
+//----------------------------------------------------------------
+// Rasterization
+
+// The direction of the steps, the difference between the end points may overflow `i32`
+fn step(from: Vec2<i32>, to: Vec2<i32>) -> Vec2<i32> {
+	Vec2((to.x as i64 - from.x as i64).signum() as i32, (to.y as i64 - from.y as i64).signum() as i32)
+}
+
+/// Iterates over the cells of a line between two integer points using Bresenham's algorithm.
+///
+/// Both end points are included. Consecutive cells may touch diagonally.
+///
+/// ```
+/// use cvmath::prelude::{Vec2, line2};
+///
+/// let cells: Vec<_> = line2::bresenham(Vec2(0, 0), Vec2(5, 2)).collect();
+/// assert_eq!(cells, [Vec2(0, 0), Vec2(1, 0), Vec2(2, 1), Vec2(3, 1), Vec2(4, 2), Vec2(5, 2)]);
+/// ```
+pub fn bresenham(from: Vec2<i32>, to: Vec2<i32>) -> Bresenham {
+	let dx = (to.x as i64 - from.x as i64).abs();
+	let dy = -(to.y as i64 - from.y as i64).abs();
+	Bresenham {
+		pos: from,
+		end: to,
+		delta: (dx, dy),
+		step: step(from, to),
+		err: dx + dy,
+		done: false,
+	}
+}
+
+/// Iterator returned by [`bresenham`](fn.bresenham.html).
+#[derive(Clone, Debug)]
+pub struct Bresenham {
+	pos: Vec2<i32>,
+	end: Vec2<i32>,
+	delta: (i64, i64),
+	step: Vec2<i32>,
+	err: i64,
+	done: bool,
+}
+
+impl Iterator for Bresenham {
+	type Item = Vec2<i32>;
+	fn next(&mut self) -> Option<Vec2<i32>> {
+		if self.done {
+			return None;
+		}
+		let pos = self.pos;
+		if pos == self.end {
+			self.done = true;
+			return Some(pos);
+		}
+		let (dx, dy) = self.delta;
+		let e2 = self.err * 2;
+		if e2 >= dy {
+			self.err += dy;
+			self.pos.x += self.step.x;
+		}
+		if e2 <= dx {
+			self.err += dx;
+			self.pos.y += self.step.y;
+		}
+		Some(pos)
+	}
+}
+
+/// Iterates over every cell touched by the line between the centers of two integer cells.
+///
+/// Unlike [`bresenham`](fn.bresenham.html) consecutive cells share an edge, except where the line passes exactly through a cell corner.
+/// There both cells adjacent to the corner are visited before the diagonal cell, the step between those two cells is diagonal.
+///
+/// ```
+/// use cvmath::prelude::{Vec2, line2};
+///
+/// let cells: Vec<_> = line2::supercover(Vec2(0, 0), Vec2(2, 1)).collect();
+/// assert_eq!(cells, [Vec2(0, 0), Vec2(1, 0), Vec2(1, 1), Vec2(2, 1)]);
+///
+/// let cells: Vec<_> = line2::supercover(Vec2(0, 0), Vec2(1, 1)).collect();
+/// assert_eq!(cells, [Vec2(0, 0), Vec2(1, 0), Vec2(0, 1), Vec2(1, 1)]);
+/// ```
+pub fn supercover(from: Vec2<i32>, to: Vec2<i32>) -> Supercover {
+	Supercover {
+		pos: from,
+		step: step(from, to),
+		n: ((to.x as i64 - from.x as i64).abs(), (to.y as i64 - from.y as i64).abs()),
+		i: (0, 0),
+		corner: None,
+		pending: true,
+	}
+}
+
+/// Iterator returned by [`supercover`](fn.supercover.html).
+#[derive(Clone, Debug)]
+pub struct Supercover {
+	pos: Vec2<i32>,
+	step: Vec2<i32>,
+	n: (i64, i64),
+	i: (i64, i64),
+	corner: Option<Vec2<i32>>,
+	pending: bool,
+}
+
+impl Iterator for Supercover {
+	type Item = Vec2<i32>;
+	fn next(&mut self) -> Option<Vec2<i32>> {
+		if let Some(cell) = self.corner.take() {
+			self.pending = true;
+			return Some(cell);
+		}
+		if self.pending {
+			self.pending = false;
+			return Some(self.pos);
+		}
+		let (nx, ny) = self.n;
+		let (ix, iy) = self.i;
+		if ix >= nx && iy >= ny {
+			return None;
+		}
+		// Compare the distances to the next vertical and horizontal cell boundaries
+		// Products of the full `i32` range overflow `i64`
+		let decision = (1 + 2 * ix as i128) * ny as i128 - (1 + 2 * iy as i128) * nx as i128;
+		if decision == 0 {
+			// Passes through the corner, visit both neighbors before the diagonal cell
+			self.corner = Some(Vec2(self.pos.x, self.pos.y + self.step.y));
+			let side = Vec2(self.pos.x + self.step.x, self.pos.y);
+			self.pos += self.step;
+			self.i = (ix + 1, iy + 1);
+			Some(side)
+		}
+		else if decision < 0 {
+			self.pos.x += self.step.x;
+			self.i.0 += 1;
+			Some(self.pos)
+		}
+		else {
+			self.pos.y += self.step.y;
+			self.i.1 += 1;
+			Some(self.pos)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rasterize_all_octants() {
+		for &to in &[Vec2(7, 3), Vec2(-7, 3), Vec2(3, -7), Vec2(-3, -7), Vec2(0, 5), Vec2(-5, 0), Vec2(4, 4), Vec2(0, 0)] {
+			let from = Vec2(2, -1);
+			let to = from + to;
+
+			let cells: Vec<_> = bresenham(from, to).collect();
+			assert_eq!(cells.first(), Some(&from));
+			assert_eq!(cells.last(), Some(&to));
+			assert_eq!(cells.len() as i32, (to - from).map(i32::abs).reduce(i32::max) + 1);
+			for w in cells.windows(2) {
+				let d = (w[1] - w[0]).map(i32::abs);
+				assert!(d.x <= 1 && d.y <= 1 && d != Vec2(0, 0));
+			}
+
+			let cells: Vec<_> = supercover(from, to).collect();
+			assert_eq!(cells.first(), Some(&from));
+			assert_eq!(cells.last(), Some(&to));
+			let edge = |a: Vec2<i32>, b: Vec2<i32>| (b - a).map(i32::abs).reduce(|x, y| x + y) == 1;
+			for i in 1..cells.len() {
+				// Diagonal steps only happen between the two cells around a corner, followed by a cell sharing an edge with both
+				if !edge(cells[i - 1], cells[i]) {
+					assert_eq!((cells[i] - cells[i - 1]).map(i32::abs), Vec2(1, 1));
+					assert!(edge(cells[i - 1], cells[i + 1]) && edge(cells[i], cells[i + 1]));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn rasterize_extreme_endpoints() {
+		let cells: Vec<_> = bresenham(Vec2(i32::MIN, 0), Vec2(1, 0)).take(3).collect();
+		assert_eq!(cells, [Vec2(i32::MIN, 0), Vec2(i32::MIN + 1, 0), Vec2(i32::MIN + 2, 0)]);
+		let cells: Vec<_> = bresenham(Vec2(i32::MAX, i32::MIN), Vec2(i32::MIN, i32::MAX)).take(2).collect();
+		assert_eq!(cells, [Vec2(i32::MAX, i32::MIN), Vec2(i32::MAX - 1, i32::MIN + 1)]);
+
+		let cells: Vec<_> = supercover(Vec2(i32::MIN, 0), Vec2(1, 0)).take(3).collect();
+		assert_eq!(cells, [Vec2(i32::MIN, 0), Vec2(i32::MIN + 1, 0), Vec2(i32::MIN + 2, 0)]);
+		let cells: Vec<_> = supercover(Vec2(i32::MAX, i32::MIN), Vec2(i32::MIN, i32::MAX)).take(3).collect();
+		assert_eq!(cells, [Vec2(i32::MAX, i32::MIN), Vec2(i32::MAX - 1, i32::MIN), Vec2(i32::MAX, i32::MIN + 1)]);
+		let cells: Vec<_> = supercover(Vec2(i32::MAX - 2, i32::MIN), Vec2(i32::MIN, i32::MAX)).take(3).collect();
+		assert_eq!(cells, [Vec2(i32::MAX - 2, i32::MIN), Vec2(i32::MAX - 2, i32::MIN + 1), Vec2(i32::MAX - 3, i32::MIN + 1)]);
+		// Ends on the largest cell
+		let cells: Vec<_> = supercover(Vec2(i32::MAX - 2, i32::MAX - 1), Vec2(i32::MAX, i32::MAX)).collect();
+		assert_eq!(cells, [Vec2(i32::MAX - 2, i32::MAX - 1), Vec2(i32::MAX - 1, i32::MAX - 1), Vec2(i32::MAX - 1, i32::MAX), Vec2(i32::MAX, i32::MAX)]);
+		let cells: Vec<_> = bresenham(Vec2(i32::MAX - 2, i32::MAX - 1), Vec2(i32::MAX, i32::MAX)).collect();
+		assert_eq!(cells.last(), Some(&Vec2(i32::MAX, i32::MAX)));
+	}
+}
@@ -12,12 +12,12 @@ macro_rules! infix {
 
 /// Expands a list of statements by inserting a statement between them.
 macro_rules! instmt {
-	($tween:stmt; $s:stmt;) => ($s;);
-	($tween:stmt; $s:stmt; $($tail:stmt;)+) => ($s; $tween; instmt!($tween; $($tail;)+));
+	($tween:stmt; $s:stmt;) => ($s);
+	($tween:stmt; $s:stmt; $($tail:stmt;)+) => ($s $tween instmt!($tween; $($tail;)+));
 }
 
 /// Folds a list of expressions.
 macro_rules! fold {
-	($f:expr, $e:expr) => ($e);
+	($f:expr, $e:expr) => ({ let _ = &$f; $e });
 	($f:expr, $acc:expr, $e:expr $(,$tail:expr)*) => (fold!($f, $f($acc, $e) $(,$tail)*));
 }
@@ -2,11 +2,12 @@
 Affine 2D transformation matrix.
 */
 
-use std::ops;
+use std::{mem, ops};
 
 use num::{Scalar, Float};
 use vec::{Vec2, Vec3};
 use angle::Angle;
+use error::MathError;
 
 use super::{Mat2, Transform2};
 
@@ -14,7 +15,8 @@ use super::{Mat2, Transform2};
 ///
 /// A 2x3 row-major matrix.
 #[cfg(feature = "row-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Affine2<T> {
 	pub a11: T, pub a12: T, pub a13: T,
@@ -25,7 +27,8 @@ pub struct Affine2<T> {
 ///
 /// A 2x3 column-major matrix.
 #[cfg(feature = "column-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Affine2<T> {
 	pub a11: T, pub a21: T,
@@ -33,6 +36,8 @@ pub struct Affine2<T> {
 	pub a13: T, pub a23: T,
 }
 
+pretty!(Affine2 3 { a11, a12, a13, a21, a22, a23 });
+
 //----------------------------------------------------------------
 // Constructors
 
@@ -164,6 +169,76 @@ impl<T> Affine2<T> {
 			[self.a13, self.a23],
 		]
 	}
+	/// Imports from a flat array in row major order.
+	pub fn from_rows_array(arr: [T; 6]) -> Affine2<T> where T: Copy {
+		Affine2 {
+			a11: arr[0], a12: arr[1], a13: arr[2],
+			a21: arr[3], a22: arr[4], a23: arr[5],
+		}
+	}
+	/// Imports from a flat array in column major order.
+	pub fn from_cols_array(arr: [T; 6]) -> Affine2<T> where T: Copy {
+		Affine2 {
+			a11: arr[0], a12: arr[2], a13: arr[4],
+			a21: arr[1], a22: arr[3], a23: arr[5],
+		}
+	}
+	/// Exports as a flat array in row major order.
+	pub fn to_rows_array(self) -> [T; 6] {
+		[self.a11, self.a12, self.a13, self.a21, self.a22, self.a23]
+	}
+	/// Exports as a flat array in column major order.
+	pub fn to_cols_array(self) -> [T; 6] {
+		[self.a11, self.a21, self.a12, self.a22, self.a13, self.a23]
+	}
+	/// Constructs the matrix from row vectors.
+	pub fn from_rows(row0: Vec3<T>, row1: Vec3<T>) -> Affine2<T> where T: Copy {
+		Affine2::from_row_major([row0.into(), row1.into()])
+	}
+	/// Constructs the matrix from column vectors.
+	pub fn from_cols(col0: Vec2<T>, col1: Vec2<T>, col2: Vec2<T>) -> Affine2<T> where T: Copy {
+		Affine2::from_column_major([col0.into(), col1.into(), col2.into()])
+	}
+	/// Returns the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn row(self, i: usize) -> Vec3<T> where T: Copy {
+		self.into_row_major()[i].into()
+	}
+	/// Returns the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn col(self, j: usize) -> Vec2<T> where T: Copy {
+		self.into_column_major()[j].into()
+	}
+	/// Sets the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_row(&mut self, i: usize, row: Vec3<T>) where T: Copy {
+		let mut rows = self.into_row_major();
+		rows[i] = row.into();
+		*self = Affine2::from_row_major(rows);
+	}
+	/// Sets the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_col(&mut self, j: usize, col: Vec2<T>) where T: Copy {
+		let mut cols = self.into_column_major();
+		cols[j] = col.into();
+		*self = Affine2::from_column_major(cols);
+	}
+}
+
+/// Views the matrix as a flat array in its memory layout, row major unless the `column-major` feature is enabled.
+impl<T> AsRef<[T; 6]> for Affine2<T> {
+	fn as_ref(&self) -> &[T; 6] {
+		unsafe { mem::transmute(self) }
+	}
+}
+impl<T> AsMut<[T; 6]> for Affine2<T> {
+	fn as_mut(&mut self) -> &mut [T; 6] {
+		unsafe { mem::transmute(self) }
+	}
 }
 
 //----------------------------------------------------------------
@@ -218,6 +293,8 @@ impl<T: Scalar> Affine2<T> {
 	}
 	/// Calculates the inverse matrix.
 	///
+	/// If the matrix is singular it is returned unchanged, see `try_inverse`.
+	///
 	/// Note that even though this matrix isn't strictly square, if an inverse exists, it will also be an affine transform.
 	///
 	/// This can be intuitively realized when you consider matrices as geometric linear transforms.
@@ -240,6 +317,36 @@ impl<T: Scalar> Affine2<T> {
 		}
 		else { *self }
 	}
+	/// Calculates the inverse matrix.
+	///
+	/// Returns `Err(MathError::Singular)` if the determinant is zero or not finite.
+	pub fn try_inverse(&self) -> Result<Affine2<T>, MathError> where T: Float {
+		let det = self.det();
+		if det == T::zero() || !det.is_finite() {
+			return Err(MathError::Singular);
+		}
+		Ok(self.inverse())
+	}
+	/// Transforms the point, the translation is applied.
+	///
+	/// ```
+	/// use cvmath::prelude::{Affine2, Vec2};
+	///
+	/// let transform = Affine2::translate(Vec2(1.0, 2.0)) * Affine2::scale(Vec2(2.0, 4.0));
+	/// assert_eq!(Vec2(3.0, 6.0), transform.transform_point(Vec2(1.0, 1.0)));
+	/// assert_eq!(Vec2(2.0, 4.0), transform.transform_vector(Vec2(1.0, 1.0)));
+	/// assert_eq!(Vec2(1.0, 1.0), transform.inverse().transform_point(Vec2(3.0, 6.0)));
+	/// ```
+	pub fn transform_point(&self, pt: Vec2<T>) -> Vec2<T> {
+		*self * pt
+	}
+	/// Transforms the vector, the translation is ignored.
+	pub fn transform_vector(&self, v: Vec2<T>) -> Vec2<T> {
+		Vec2 {
+			x: v.x * self.a11 + v.y * self.a12,
+			y: v.x * self.a21 + v.y * self.a22,
+		}
+	}
 }
 
 //----------------------------------------------------------------
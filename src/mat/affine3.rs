@@ -2,10 +2,12 @@
 Affine 3D transformation matrix.
 */
 
-use std::ops;
+use std::{mem, ops};
 
-use num::Scalar;
+use num::{Scalar, Float};
 use vec::{Vec3, Vec4};
+use angle::Angle;
+use error::MathError;
 
 use super::{Mat3, Transform3};
 
@@ -13,7 +15,8 @@ use super::{Mat3, Transform3};
 ///
 /// A 3x4 row-major matrix.
 #[cfg(feature = "row-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Affine3<T> {
 	pub a11: T, pub a12: T, pub a13: T, pub a14: T,
@@ -25,7 +28,8 @@ pub struct Affine3<T> {
 ///
 /// A 3x4 column-major matrix.
 #[cfg(feature = "column-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Affine3<T> {
 	pub a11: T, pub a21: T, pub a31: T,
@@ -34,6 +38,8 @@ pub struct Affine3<T> {
 	pub a14: T, pub a24: T, pub a34: T,
 }
 
+pretty!(Affine3 4 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34 });
+
 //----------------------------------------------------------------
 // Constructors
 
@@ -49,9 +55,73 @@ impl<T> Affine3<T> {
 	}
 }
 
+impl<T: Scalar> Affine3<T> {
+	/// Identity matrix.
+	pub fn identity() -> Affine3<T> {
+		Affine3 {
+			a11: T::one(),  a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(), a22: T::one(),  a23: T::zero(), a24: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: T::one(),  a34: T::zero(),
+		}
+	}
+	/// Null matrix.
+	pub fn null() -> Affine3<T> {
+		Affine3 {
+			a11: T::zero(), a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(), a22: T::zero(), a23: T::zero(), a24: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: T::zero(), a34: T::zero(),
+		}
+	}
+	/// Translation matrix.
+	pub fn translate<V>(trans: V) -> Affine3<T> where V: Into<Vec3<T>> {
+		let trans = trans.into();
+		Affine3 {
+			a11: T::one(),  a12: T::zero(), a13: T::zero(), a14: trans.x,
+			a21: T::zero(), a22: T::one(),  a23: T::zero(), a24: trans.y,
+			a31: T::zero(), a32: T::zero(), a33: T::one(),  a34: trans.z,
+		}
+	}
+	/// Scaling matrix.
+	///
+	/// Scales around the origin.
+	pub fn scale<V>(scale: V) -> Affine3<T> where V: Into<Vec3<T>> {
+		Affine3::from_mat(Mat3::scale(scale), Vec3::default())
+	}
+	/// Rotation matrix around an arbitrary axis.
+	///
+	/// See [`Mat3::rotate`](struct.Mat3.html#method.rotate).
+	pub fn rotate<V, A>(axis: V, angle: A) -> Affine3<T> where T: Float, V: Into<Vec3<T>>, A: Angle<T = T> {
+		Affine3::from_mat(Mat3::rotate(axis, angle), Vec3::default())
+	}
+}
+
 //----------------------------------------------------------------
 // Conversions
 
+impl<T> Affine3<T> {
+	pub fn into_mat(self) -> (Mat3<T>, Vec3<T>) {
+		(
+			Mat3 {
+				a11: self.a11, a12: self.a12, a13: self.a13,
+				a21: self.a21, a22: self.a22, a23: self.a23,
+				a31: self.a31, a32: self.a32, a33: self.a33,
+			},
+			Vec3 {
+				x: self.a14,
+				y: self.a24,
+				z: self.a34,
+			}
+		)
+	}
+	pub fn from_mat(mat: Mat3<T>, vec: Vec3<T>) -> Affine3<T> {
+		Affine3 {
+			a11: mat.a11, a12: mat.a12, a13: mat.a13, a14: vec.x,
+			a21: mat.a21, a22: mat.a22, a23: mat.a23, a24: vec.y,
+			a31: mat.a31, a32: mat.a32, a33: mat.a33, a34: vec.z,
+		}
+	}
+}
+
 impl<T> Affine3<T> {
 	pub fn from_row_major(mat: [[T; 4]; 3]) -> Affine3<T> where T: Copy {
 		Affine3 {
@@ -82,6 +152,78 @@ impl<T> Affine3<T> {
 			[self.a14, self.a24, self.a34],
 		]
 	}
+	/// Imports from a flat array in row major order.
+	pub fn from_rows_array(arr: [T; 12]) -> Affine3<T> where T: Copy {
+		Affine3 {
+			a11: arr[0], a12: arr[1], a13: arr[2], a14: arr[3],
+			a21: arr[4], a22: arr[5], a23: arr[6], a24: arr[7],
+			a31: arr[8], a32: arr[9], a33: arr[10], a34: arr[11],
+		}
+	}
+	/// Imports from a flat array in column major order.
+	pub fn from_cols_array(arr: [T; 12]) -> Affine3<T> where T: Copy {
+		Affine3 {
+			a11: arr[0], a12: arr[3], a13: arr[6], a14: arr[9],
+			a21: arr[1], a22: arr[4], a23: arr[7], a24: arr[10],
+			a31: arr[2], a32: arr[5], a33: arr[8], a34: arr[11],
+		}
+	}
+	/// Exports as a flat array in row major order.
+	pub fn to_rows_array(self) -> [T; 12] {
+		[self.a11, self.a12, self.a13, self.a14, self.a21, self.a22, self.a23, self.a24, self.a31, self.a32, self.a33, self.a34]
+	}
+	/// Exports as a flat array in column major order.
+	pub fn to_cols_array(self) -> [T; 12] {
+		[self.a11, self.a21, self.a31, self.a12, self.a22, self.a32, self.a13, self.a23, self.a33, self.a14, self.a24, self.a34]
+	}
+	/// Constructs the matrix from row vectors.
+	pub fn from_rows(row0: Vec4<T>, row1: Vec4<T>, row2: Vec4<T>) -> Affine3<T> where T: Copy {
+		Affine3::from_row_major([row0.into(), row1.into(), row2.into()])
+	}
+	/// Constructs the matrix from column vectors.
+	pub fn from_cols(col0: Vec3<T>, col1: Vec3<T>, col2: Vec3<T>, col3: Vec3<T>) -> Affine3<T> where T: Copy {
+		Affine3::from_column_major([col0.into(), col1.into(), col2.into(), col3.into()])
+	}
+	/// Returns the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn row(self, i: usize) -> Vec4<T> where T: Copy {
+		self.into_row_major()[i].into()
+	}
+	/// Returns the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn col(self, j: usize) -> Vec3<T> where T: Copy {
+		self.into_column_major()[j].into()
+	}
+	/// Sets the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_row(&mut self, i: usize, row: Vec4<T>) where T: Copy {
+		let mut rows = self.into_row_major();
+		rows[i] = row.into();
+		*self = Affine3::from_row_major(rows);
+	}
+	/// Sets the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_col(&mut self, j: usize, col: Vec3<T>) where T: Copy {
+		let mut cols = self.into_column_major();
+		cols[j] = col.into();
+		*self = Affine3::from_column_major(cols);
+	}
+}
+
+/// Views the matrix as a flat array in its memory layout, row major unless the `column-major` feature is enabled.
+impl<T> AsRef<[T; 12]> for Affine3<T> {
+	fn as_ref(&self) -> &[T; 12] {
+		unsafe { mem::transmute(self) }
+	}
+}
+impl<T> AsMut<[T; 12]> for Affine3<T> {
+	fn as_mut(&mut self) -> &mut [T; 12] {
+		unsafe { mem::transmute(self) }
+	}
 }
 
 //----------------------------------------------------------------
@@ -133,11 +275,17 @@ impl<T> Affine3<T> {
 // Operations
 
 impl<T: Scalar> Affine3<T> {
+	/// Calculates the determinant of the linear part.
 	pub fn det(&self) -> T {
 		self.a11 * (self.a22 * self.a33 - self.a23 * self.a32) +
 		self.a12 * (self.a23 * self.a31 - self.a21 * self.a33) +
 		self.a13 * (self.a21 * self.a32 - self.a22 * self.a31)
 	}
+	/// Calculates the inverse matrix.
+	///
+	/// If the matrix is singular it is returned unchanged, see `try_inverse`.
+	///
+	/// The inverse of an affine transform is affine, the last row of the full 4x4 matrix does not need to be inverted.
 	pub fn inverse(&self) -> Affine3<T> {
 		let det = self.det();
 		if det != T::zero() {
@@ -165,6 +313,58 @@ impl<T: Scalar> Affine3<T> {
 		}
 		else { *self }
 	}
+	/// Calculates the inverse matrix.
+	///
+	/// Returns `Err(MathError::Singular)` if the determinant is zero or not finite.
+	pub fn try_inverse(&self) -> Result<Affine3<T>, MathError> where T: Float {
+		let det = self.det();
+		if det == T::zero() || !det.is_finite() {
+			return Err(MathError::Singular);
+		}
+		Ok(self.inverse())
+	}
+	/// Transforms the point, the translation is applied.
+	///
+	/// ```
+	/// use cvmath::prelude::{Affine3, Vec3};
+	///
+	/// let transform = Affine3::translate(Vec3(1.0f64, 2.0, 3.0)) * Affine3::scale(Vec3(2.0, 4.0, 1.0));
+	/// assert_eq!(Vec3(3.0, 6.0, 4.0), transform.transform_point(Vec3(1.0, 1.0, 1.0)));
+	/// assert_eq!(Vec3(2.0, 4.0, 1.0), transform.transform_vector(Vec3(1.0, 1.0, 1.0)));
+	/// assert_eq!(Vec3(1.0, 1.0, 1.0), transform.inverse().transform_point(Vec3(3.0, 6.0, 4.0)));
+	///
+	/// // Normals stay perpendicular to the surface under non-uniform scaling
+	/// let normal = transform.transform_normal(Vec3(1.0, 1.0, 0.0));
+	/// let tangent = transform.transform_vector(Vec3(1.0, -1.0, 0.0));
+	/// assert_eq!(0.0, normal.dot(tangent));
+	/// assert!((normal.len() - 1.0).abs() < 1e-12);
+	/// ```
+	pub fn transform_point(&self, pt: Vec3<T>) -> Vec3<T> {
+		*self * pt
+	}
+	/// Transforms the vector, the translation is ignored.
+	pub fn transform_vector(&self, v: Vec3<T>) -> Vec3<T> {
+		Vec3 {
+			x: v.x * self.a11 + v.y * self.a12 + v.z * self.a13,
+			y: v.x * self.a21 + v.y * self.a22 + v.z * self.a23,
+			z: v.x * self.a31 + v.y * self.a32 + v.z * self.a33,
+		}
+	}
+	/// Calculates the normal matrix, the inverse transpose of the linear part.
+	///
+	/// See [`Mat3::inverse_transpose`](struct.Mat3.html#method.inverse_transpose).
+	pub fn normal_matrix(&self) -> Mat3<T> where T: Float {
+		self.into_mat().0.inverse_transpose()
+	}
+	/// Transforms the surface normal by the inverse transpose of the linear part.
+	///
+	/// The result is normalized, the translation is ignored.
+	pub fn transform_normal(&self, n: Vec3<T>) -> Vec3<T> where T: Float {
+		let (mat, _) = self.into_mat();
+		// The cofactor matrix is the inverse transpose scaled by the determinant
+		let n = mat.adjugate().transpose() * n;
+		if self.det() < T::zero() { -n.norm() } else { n.norm() }
+	}
 }
 
 //----------------------------------------------------------------
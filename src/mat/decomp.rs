@@ -0,0 +1,492 @@
+/*!
+Matrix decompositions and linear solvers.
+
+Small dense systems are solved with the LU decomposition with partial pivoting.
+The QR decomposition is slower but more robust for ill-conditioned systems.
+
+### Examples
+
+```
+use cvmath::prelude::{Mat3, Vec3};
+
+let a = Mat3::new(
+    2.0, 1.0, -1.0,
+    -3.0, -1.0, 2.0,
+    -2.0, 1.0, 2.0,
+);
+assert!(a.solve(Vec3(8.0, -11.0, -3.0)).unwrap().dist(Vec3(2.0, 3.0, -1.0)) < 1e-12);
+
+let qr = a.qr();
+assert!(qr.solve(Vec3(8.0, -11.0, -3.0)).unwrap().dist(Vec3(2.0, 3.0, -1.0)) < 1e-12);
+```
+*/
+
+use num::Float;
+use vec::{Vec2, Vec3, Vec4};
+use error::MathError;
+
+use super::{Mat2, Mat3, Mat4, Affine3};
+
+/// LU decomposition with partial pivoting, `p * a == l * u`.
+///
+/// `l` is lower triangular with a unit diagonal, `u` is upper triangular and `p` is a permutation matrix.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Lu<M> {
+	pub l: M,
+	pub u: M,
+	pub p: M,
+}
+
+/// QR decomposition, `a == q * r`.
+///
+/// `q` is orthogonal and `r` is upper triangular.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Qr<M> {
+	pub q: M,
+	pub r: M,
+}
+
+/// Singular value decomposition, `a == u * Mat::scale(s) * vt`.
+///
+/// `u` and `vt` are orthogonal and the singular values `s` are non-negative and sorted from largest to smallest.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Svd<M, V> {
+	pub u: M,
+	pub s: V,
+	pub vt: M,
+}
+
+/// Translation, rotation and scale decomposition, `a == translate(translation) * rotation * scale(scale)`.
+///
+/// `rotation` is a proper rotation matrix, a reflection is represented by negating `scale.x`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Trs<T> {
+	pub translation: Vec3<T>,
+	pub rotation: Mat3<T>,
+	pub scale: Vec3<T>,
+}
+
+impl<T: Float> Trs<T> {
+	/// Recomposes the affine transform.
+	pub fn into_affine(self) -> Affine3<T> {
+		Affine3::from_mat(self.rotation * Mat3::scale(self.scale), self.translation)
+	}
+}
+
+//----------------------------------------------------------------
+// Implementation
+
+pub(super) fn lu<T: Float, const N: usize>(mut a: [[T; N]; N]) -> Lu<[[T; N]; N]> {
+	let (zero, one) = (T::zero(), T::one());
+	let mut perm: [usize; N] = ::std::array::from_fn(|i| i);
+	for k in 0..N {
+		let pivot = (k..N).fold(k, |p, i| if a[i][k].abs() > a[p][k].abs() { i } else { p });
+		a.swap(k, pivot);
+		perm.swap(k, pivot);
+		if a[k][k] != zero {
+			for i in k + 1..N {
+				let f = a[i][k] / a[k][k];
+				a[i][k] = f;
+				let (top, bottom) = a.split_at_mut(i);
+				for (aij, &akj) in bottom[0].iter_mut().zip(&top[k]).skip(k + 1) {
+					*aij -= f * akj;
+				}
+			}
+		}
+	}
+	Lu {
+		l: ::std::array::from_fn(|i| ::std::array::from_fn(|j| if i == j { one } else if j < i { a[i][j] } else { zero })),
+		u: ::std::array::from_fn(|i| ::std::array::from_fn(|j| if j >= i { a[i][j] } else { zero })),
+		p: ::std::array::from_fn(|i| ::std::array::from_fn(|j| if perm[i] == j { one } else { zero })),
+	}
+}
+
+fn qr<T: Float, const N: usize>(a: [[T; N]; N]) -> Qr<[[T; N]; N]> {
+	let (zero, one) = (T::zero(), T::one());
+	let mut r = a;
+	let mut q: [[T; N]; N] = ::std::array::from_fn(|i| ::std::array::from_fn(|j| if i == j { one } else { zero }));
+	for k in 0..N.saturating_sub(1) {
+		// Householder reflection zeroing the column below the diagonal
+		let norm = (k..N).fold(zero, |acc, i| acc + r[i][k] * r[i][k]).sqrt();
+		let alpha = if r[k][k] > zero { -norm } else { norm };
+		let mut v = [zero; N];
+		for i in k..N {
+			v[i] = r[i][k];
+		}
+		v[k] -= alpha;
+		let v_len_sqr = (k..N).fold(zero, |acc, i| acc + v[i] * v[i]);
+		if v_len_sqr == zero {
+			continue;
+		}
+		let scale = (one + one) / v_len_sqr;
+		for j in 0..N {
+			let dot = (k..N).fold(zero, |acc, i| acc + v[i] * r[i][j]) * scale;
+			for (row, &vi) in r.iter_mut().zip(&v).skip(k) {
+				row[j] -= dot * vi;
+			}
+		}
+		for row in q.iter_mut() {
+			let dot = (k..N).fold(zero, |acc, j| acc + row[j] * v[j]) * scale;
+			for j in k..N {
+				row[j] -= dot * v[j];
+			}
+		}
+		for row in r.iter_mut().skip(k + 1) {
+			row[k] = zero;
+		}
+	}
+	Qr { q, r }
+}
+
+pub(super) fn forward_substitute<T: Float, const N: usize>(l: &[[T; N]; N], b: [T; N]) -> [T; N] {
+	let mut y = b;
+	for i in 0..N {
+		for j in 0..i {
+			y[i] -= l[i][j] * y[j];
+		}
+	}
+	y
+}
+
+pub(super) fn back_substitute<T: Float, const N: usize>(u: &[[T; N]; N], y: [T; N]) -> Option<[T; N]> {
+	// Pivots which are rounding noise relative to the largest entry mean the matrix is singular
+	let max = u.iter().flatten().fold(T::zero(), |acc, &e| acc.max(e.abs()));
+	let tolerance = T::cast_from(N as f64) * T::epsilon() * max;
+	let mut x = y;
+	for i in (0..N).rev() {
+		if u[i][i].abs() <= tolerance {
+			return None;
+		}
+		for j in i + 1..N {
+			x[i] -= u[i][j] * x[j];
+		}
+		x[i] /= u[i][i];
+	}
+	Some(x)
+}
+
+macro_rules! decomp {
+	($mat:ident $vec:ident) => {
+		impl<T: Float> $mat<T> {
+			/// Solves the linear system `self * x == b` for `x`.
+			///
+			/// Returns `None` if the matrix is singular to working precision.
+			pub fn solve(&self, b: $vec<T>) -> Option<$vec<T>> {
+				self.lu().solve(b)
+			}
+			/// LU decomposition with partial pivoting.
+			pub fn lu(&self) -> Lu<$mat<T>> {
+				let Lu { l, u, p } = lu(self.into_row_major());
+				Lu { l: $mat::from_row_major(l), u: $mat::from_row_major(u), p: $mat::from_row_major(p) }
+			}
+			/// QR decomposition using Householder reflections.
+			pub fn qr(&self) -> Qr<$mat<T>> {
+				let Qr { q, r } = qr(self.into_row_major());
+				Qr { q: $mat::from_row_major(q), r: $mat::from_row_major(r) }
+			}
+		}
+		impl<T: Float> Lu<$mat<T>> {
+			/// Solves the linear system `a * x == b` for `x`.
+			///
+			/// Returns `None` if the matrix is singular to working precision.
+			pub fn solve(&self, b: $vec<T>) -> Option<$vec<T>> {
+				let y = forward_substitute(&self.l.into_row_major(), (self.p * b).into());
+				back_substitute(&self.u.into_row_major(), y).map($vec::from)
+			}
+			/// Returns the determinant of the decomposed matrix.
+			pub fn det(&self) -> T {
+				self.p.det() * self.u.into_row_major().iter().enumerate().fold(T::one(), |acc, (i, row)| acc * row[i])
+			}
+		}
+		impl<T: Float> Qr<$mat<T>> {
+			/// Solves the linear system `a * x == b` for `x`.
+			///
+			/// Returns `None` if the matrix is singular to working precision.
+			pub fn solve(&self, b: $vec<T>) -> Option<$vec<T>> {
+				back_substitute(&self.r.into_row_major(), (self.q.transpose() * b).into()).map($vec::from)
+			}
+		}
+	};
+}
+
+impl<T: Float> Mat2<T> {
+	/// Singular value decomposition in closed form.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat2, Vec2};
+	///
+	/// let a = Mat2::new(1.0f64, 2.0, -3.0, 0.5);
+	/// let svd = a.svd();
+	/// let b = svd.u * Mat2::scale(svd.s) * svd.vt;
+	/// for (a, b) in a.into_row_major().iter().flatten().zip(b.into_row_major().iter().flatten()) {
+	///     assert!((a - b).abs() < 1e-12);
+	/// }
+	/// ```
+	pub fn svd(&self) -> Svd<Mat2<T>, Vec2<T>> {
+		let half = T::one() / (T::one() + T::one());
+		// Decompose into a rotation, a scale and another rotation
+		let e = (self.a11 + self.a22) * half;
+		let f = (self.a11 - self.a22) * half;
+		let g = (self.a21 + self.a12) * half;
+		let h = (self.a21 - self.a12) * half;
+		let q = (e * e + h * h).sqrt();
+		let r = (f * f + g * g).sqrt();
+		let (a1, a2) = (g.atan2(f), h.atan2(e));
+		let (sin_theta, cos_theta) = ((a2 - a1) * half).sin_cos();
+		let (sin_phi, cos_phi) = ((a2 + a1) * half).sin_cos();
+		let sy = q - r;
+		// Move the sign of the smallest singular value into the left rotation
+		let flip = if sy < T::zero() { -T::one() } else { T::one() };
+		Svd {
+			u: Mat2::new(cos_phi, -sin_phi * flip, sin_phi, cos_phi * flip),
+			s: Vec2 { x: q + r, y: sy * flip },
+			vt: Mat2::new(cos_theta, -sin_theta, sin_theta, cos_theta),
+		}
+	}
+	/// Eigen decomposition of the symmetric matrix in closed form.
+	///
+	/// Returns the eigenvalues sorted from largest to smallest and the corresponding unit eigenvectors as the columns of a rotation matrix,
+	/// such that `self == vectors * Mat2::scale(values) * vectors.transpose()`.
+	/// Used for fitting oriented bounding boxes, principal stresses and extracting the axes of ellipses.
+	///
+	/// Only the upper triangle of the matrix is read.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat2, Vec2};
+	///
+	/// let m = Mat2::new(2.0f64, 1.0, 1.0, 2.0);
+	/// let (values, vectors) = m.eigen_symmetric();
+	/// assert!(values.dist(Vec2(3.0, 1.0)) < 1e-12);
+	/// assert!((m * vectors.x()).dist(vectors.x() * values.x) < 1e-12);
+	/// assert!((m * vectors.y()).dist(vectors.y() * values.y) < 1e-12);
+	/// assert!((vectors.det() - 1.0).abs() < 1e-12);
+	/// ```
+	pub fn eigen_symmetric(self) -> (Vec2<T>, Mat2<T>) {
+		let half = T::one() / (T::one() + T::one());
+		let mean = (self.a11 + self.a22) * half;
+		let diff = (self.a11 - self.a22) * half;
+		let radius = (diff * diff + self.a12 * self.a12).sqrt();
+		let (sin, cos) = (self.a12.atan2(diff) * half).sin_cos();
+		let values = Vec2 { x: mean + radius, y: mean - radius };
+		(values, Mat2::new(cos, -sin, sin, cos))
+	}
+	/// Eigen decomposition in closed form.
+	///
+	/// Returns the eigenvalues sorted from largest to smallest and the corresponding unit eigenvectors as the columns of the matrix,
+	/// or `None` if the eigenvalues are complex (eg. for rotation matrices).
+	///
+	/// Defective matrices with a repeated eigenvalue (eg. shear matrices) return the same eigenvector twice.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat2, Vec2, Deg};
+	///
+	/// let m = Mat2::new(4.0f64, 1.0, 2.0, 3.0);
+	/// let (values, vectors) = m.eigen().unwrap();
+	/// assert!(values.dist(Vec2(5.0, 2.0)) < 1e-12);
+	/// assert!((m * vectors.x()).dist(vectors.x() * values.x) < 1e-12);
+	/// assert!((m * vectors.y()).dist(vectors.y() * values.y) < 1e-12);
+	///
+	/// assert_eq!(None, Mat2::rotate(Deg(30.0f64)).eigen());
+	/// ```
+	pub fn eigen(self) -> Option<(Vec2<T>, Mat2<T>)> {
+		let half = T::one() / (T::one() + T::one());
+		let mean = (self.a11 + self.a22) * half;
+		let diff = (self.a11 - self.a22) * half;
+		let disc = diff * diff + self.a12 * self.a21;
+		if disc < T::zero() {
+			return None;
+		}
+		let root = disc.sqrt();
+		let values = Vec2 { x: mean + root, y: mean - root };
+		// The eigenvector is perpendicular to the rows of `self - value * I`, use the longer row for stability
+		let vector = |value: T, fallback: Vec2<T>| {
+			let a = Vec2 { x: -self.a12, y: self.a11 - value };
+			let b = Vec2 { x: self.a22 - value, y: -self.a21 };
+			let v = if a.len_sqr() >= b.len_sqr() { a } else { b };
+			if v.len_sqr() > T::zero() { v.norm() } else { fallback }
+		};
+		let vectors = Mat2::from_cols(vector(values.x, Vec2::unit_x()), vector(values.y, Vec2::unit_y()));
+		Some((values, vectors))
+	}
+}
+
+impl<T: Float> Mat3<T> {
+	/// Singular value decomposition.
+	///
+	/// The right singular vectors are the eigenvectors of `selfᵀ * self`, the left singular vectors are found
+	/// by a QR decomposition which keeps them orthogonal even for (nearly) singular matrices.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat3, Vec3};
+	///
+	/// let a = Mat3::new(
+	///     2.0f64, -1.0, 0.5,
+	///     0.0, 3.0, 1.0,
+	///     1.0, 1.0, -2.0,
+	/// );
+	/// let svd = a.svd();
+	/// assert!(svd.s.x >= svd.s.y && svd.s.y >= svd.s.z && svd.s.z >= 0.0);
+	/// let b = svd.u * Mat3::scale(svd.s) * svd.vt;
+	/// for (a, b) in a.into_row_major().iter().flatten().zip(b.into_row_major().iter().flatten()) {
+	///     assert!((a - b).abs() < 1e-12);
+	/// }
+	/// ```
+	pub fn svd(&self) -> Svd<Mat3<T>, Vec3<T>> {
+		let (_, v) = (self.transpose() * *self).eigen_symmetric();
+		let Qr { q, r } = (*self * v).qr();
+		// Make the diagonal of R non-negative, it holds the singular values
+		let (mut u, r) = (q.into_row_major(), r.into_row_major());
+		let mut s = [T::zero(); 3];
+		for i in 0..3 {
+			s[i] = r[i][i].abs();
+			if r[i][i] < T::zero() {
+				for row in u.iter_mut() {
+					row[i] = -row[i];
+				}
+			}
+		}
+		Svd { u: Mat3::from_row_major(u), s: Vec3::from(s), vt: v.transpose() }
+	}
+}
+
+impl<T: Float> Affine3<T> {
+	/// Decomposes the transform into translation, rotation and scale.
+	///
+	/// Returns `Err(MathError::Singular)` if any of the scale factors is zero
+	/// and `Err(MathError::Degenerate)` if the transform contains shear and cannot be represented as translation, rotation and scale.
+	///
+	/// ```
+	/// # #[macro_use] extern crate cvmath;
+	/// use cvmath::prelude::{Affine3, Vec3, Deg};
+	/// use cvmath::error::MathError;
+	///
+	/// # fn main() {
+	/// let rotation = Affine3::rotate(Vec3(1.0, 2.0, 3.0), Deg(40.0f64));
+	/// let transform = Affine3::translate(Vec3(1.0, -2.0, 3.0)) * rotation * Affine3::scale(Vec3(2.0, 0.5, 3.0));
+	/// let trs = transform.decompose().unwrap();
+	/// assert_approx_eq!(Vec3(1.0, -2.0, 3.0), trs.translation);
+	/// assert_approx_eq!(Vec3(2.0, 0.5, 3.0), trs.scale);
+	/// assert_approx_eq!(rotation.into_mat().0, trs.rotation);
+	/// assert_approx_eq!(transform, trs.into_affine());
+	///
+	/// let sheared = Affine3::new(1.0, 0.5, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0);
+	/// assert_eq!(Err(MathError::Degenerate), sheared.decompose());
+	/// # }
+	/// ```
+	pub fn decompose(&self) -> Result<Trs<T>, MathError> {
+		let (mat, translation) = self.into_mat();
+		let (x, y, z) = (mat.x(), mat.y(), mat.z());
+		let mut scale = Vec3 { x: x.len(), y: y.len(), z: z.len() };
+		let max_scale = scale.x.max(scale.y).max(scale.z);
+		if scale.x.min(scale.y).min(scale.z) <= max_scale * T::epsilon() {
+			return Err(MathError::Singular);
+		}
+		let (mut x, y, z) = (x / scale.x, y / scale.y, z / scale.z);
+		// The columns of a rotation scaled along its axes remain orthogonal
+		let tolerance = T::epsilon().sqrt();
+		if x.dot(y).abs() > tolerance || y.dot(z).abs() > tolerance || z.dot(x).abs() > tolerance {
+			return Err(MathError::Degenerate);
+		}
+		if mat.det() < T::zero() {
+			scale.x = -scale.x;
+			x = -x;
+		}
+		Ok(Trs { translation, rotation: Mat3::from_cols(x, y, z), scale })
+	}
+}
+
+impl<T: Float> Mat4<T> {
+	/// Decomposes the transform into translation, rotation and scale.
+	///
+	/// Returns `Err(MathError::Degenerate)` if the matrix contains a projection, otherwise see [`Affine3::decompose`](struct.Affine3.html#method.decompose).
+	pub fn decompose(&self) -> Result<Trs<T>, MathError> {
+		if self.a41 != T::zero() || self.a42 != T::zero() || self.a43 != T::zero() || self.a44 == T::zero() {
+			return Err(MathError::Degenerate);
+		}
+		let inv_w = T::one() / self.a44;
+		let affine = Affine3::new(
+			self.a11 * inv_w, self.a12 * inv_w, self.a13 * inv_w, self.a14 * inv_w,
+			self.a21 * inv_w, self.a22 * inv_w, self.a23 * inv_w, self.a24 * inv_w,
+			self.a31 * inv_w, self.a32 * inv_w, self.a33 * inv_w, self.a34 * inv_w,
+		);
+		affine.decompose()
+	}
+}
+
+decomp!(Mat2 Vec2);
+decomp!(Mat3 Vec3);
+decomp!(Mat4 Vec4);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(a: Mat4<f64>, b: Mat4<f64>) {
+		for (a, b) in a.into_row_major().iter().flatten().zip(b.into_row_major().iter().flatten()) {
+			assert!((a - b).abs() < 1e-12, "{:?} != {:?}", a, b);
+		}
+	}
+
+	#[test]
+	fn decompositions() {
+		let a = Mat4::new(
+			0.0, 2.0, 1.0, -1.0,
+			3.0, 1.0, 0.0, 2.0,
+			1.0, -2.0, 4.0, 0.5,
+			2.0, 0.0, 1.0, 1.0,
+		);
+		let lu = a.lu();
+		assert_close(lu.p * a, lu.l * lu.u);
+		assert!((lu.det() - a.det()).abs() < 1e-12);
+
+		let qr = a.qr();
+		assert_close(a, qr.q * qr.r);
+		assert_close(Mat4::identity(), qr.q.transpose() * qr.q);
+
+		let x = Vec4(1.0, -2.0, 0.5, 3.0);
+		let b = a * x;
+		assert!(a.solve(b).unwrap().dist(x) < 1e-12);
+		assert!(qr.solve(b).unwrap().dist(x) < 1e-12);
+
+		let singular = Mat3::new(1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0);
+		assert_eq!(None, singular.solve(Vec3(1.0, 2.0, 3.0)));
+
+		// Rounding leaves tiny nonzero pivots
+		let singular = Mat3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+		assert_eq!(None, singular.solve(Vec3(1.0, 0.0, 0.0)));
+		assert_eq!(None, singular.lu().solve(Vec3(1.0, 0.0, 0.0)));
+		assert_eq!(None, singular.qr().solve(Vec3(1.0, 0.0, 0.0)));
+	}
+
+	#[test]
+	fn trs_reflection() {
+		use angle::Deg;
+		let transform = Mat4::translate(Vec3(4.0, 5.0, 6.0)) * Mat4::rotate_y(Deg(30.0)) * Mat4::scale(Vec3(2.0, -3.0, 1.0));
+		let trs = transform.decompose().unwrap();
+		assert!(trs.rotation.det() > 0.0);
+		assert!(trs.scale.x * trs.scale.y * trs.scale.z < 0.0);
+		assert_close(transform, Mat4::from(trs.into_affine()));
+
+		assert_eq!(Err(MathError::Singular), Mat4::<f64>::scale(Vec3(1.0, 0.0, 1.0)).decompose());
+		assert_eq!(Err(MathError::Degenerate), Mat4::perspective(Deg(90.0), 1.0, 0.1, 10.0).decompose());
+	}
+
+	#[test]
+	fn svd_rank_deficient() {
+		let a = Mat3::new(
+			1.0, 2.0, 3.0,
+			2.0, 4.0, 6.0,
+			-1.0, -2.0, -3.0,
+		);
+		let svd = a.svd();
+		assert!((svd.s.x - 84f64.sqrt()).abs() < 1e-12);
+		assert!(svd.s.y < 1e-12 && svd.s.z < 1e-12);
+		let flat = |m: Mat3<f64>| m.into_row_major().iter().flatten().cloned().collect::<Vec<_>>();
+		for (a, b) in flat(svd.u.transpose() * svd.u).iter().zip(flat(Mat3::identity())) {
+			assert!((a - b).abs() < 1e-12);
+		}
+		for (a, b) in flat(svd.u * Mat3::scale(svd.s) * svd.vt).iter().zip(flat(a)) {
+			assert!((a - b).abs() < 1e-12);
+		}
+	}
+}
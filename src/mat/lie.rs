@@ -0,0 +1,179 @@
+/*!
+Exponential and logarithm maps of rotations and rigid transforms.
+
+A rotation is represented in the tangent space by its rotation vector `omega`: the axis of rotation scaled by the angle in radians.
+A rigid transform additionally has a translational velocity `v`, together `(omega, v)` form a twist.
+The exponential map integrates a constant twist over unit time, the logarithm recovers the twist from the transform.
+
+This enables geodesic interpolation (constant angular and linear velocity along a screw motion) and pose integration `pose * exp(twist * dt)`.
+
+### Examples
+
+```
+use cvmath::prelude::{Mat3, Affine3, Vec3};
+
+// Quarter turn around the z axis
+let rot = Mat3::exp(Vec3(0.0, 0.0, std::f64::consts::FRAC_PI_2));
+assert!((rot * Vec3(1.0, 0.0, 0.0)).dist(Vec3(0.0, 1.0, 0.0)) < 1e-12);
+assert!(rot.ln().dist(Vec3(0.0, 0.0, std::f64::consts::FRAC_PI_2)) < 1e-12);
+
+// Integrate a pose moving forward while turning
+let mut pose = Affine3::identity();
+let (omega, v) = (Vec3(0.0, 0.0, 1.0), Vec3(1.0, 0.0, 0.0));
+for _ in 0..100 {
+    pose = pose * Affine3::exp(omega * 0.01, v * 0.01);
+}
+let (omega1, v1) = pose.ln();
+assert!(omega1.dist(omega) < 1e-12 && v1.dist(v) < 1e-12);
+```
+*/
+
+use num::Float;
+use vec::Vec3;
+
+use super::{Mat3, Affine3};
+
+/// Returns `a I + b [w]x + c w wT` where `[w]x` is the cross product matrix.
+fn combine<T: Float>(a: T, b: T, c: T, w: Vec3<T>) -> Mat3<T> {
+	Mat3::new(
+		a + c * w.x * w.x, c * w.x * w.y - b * w.z, c * w.x * w.z + b * w.y,
+		c * w.y * w.x + b * w.z, a + c * w.y * w.y, c * w.y * w.z - b * w.x,
+		c * w.z * w.x - b * w.y, c * w.z * w.y + b * w.x, a + c * w.z * w.z,
+	)
+}
+
+/// Returns the coefficients `sin θ / θ`, `(1 - cos θ) / θ²` and `(θ - sin θ) / θ³` with their series near zero.
+fn coefficients<T: Float>(theta_sqr: T) -> (T, T, T) {
+	let theta = theta_sqr.sqrt();
+	if theta < T::cast_from(1e-4) {
+		let a = T::one() - theta_sqr / T::cast_from(6.0);
+		let b = T::cast_from(0.5) - theta_sqr / T::cast_from(24.0);
+		let c = T::cast_from(1.0 / 6.0) - theta_sqr / T::cast_from(120.0);
+		(a, b, c)
+	}
+	else {
+		let (sin, cos) = theta.sin_cos();
+		(sin / theta, (T::one() - cos) / theta_sqr, (theta - sin) / (theta_sqr * theta))
+	}
+}
+
+impl<T: Float> Mat3<T> {
+	/// Exponential map, the rotation matrix of the rotation vector.
+	///
+	/// Implements the Rodrigues formula, the rotation is counter-clockwise around the axis by the length of the vector in radians.
+	pub fn exp(omega: Vec3<T>) -> Mat3<T> {
+		let theta_sqr = omega.len_sqr();
+		let (a, b, _) = coefficients(theta_sqr);
+		combine(T::one() - b * theta_sqr, a, b, omega)
+	}
+	/// Logarithm map, the rotation vector of the rotation matrix.
+	///
+	/// The matrix must be a rotation matrix, the angle of the result is in `[0, π]`.
+	/// At exactly `π` the sign of the axis is arbitrary.
+	pub fn ln(self) -> Vec3<T> {
+		let cos = ((self.a11 + self.a22 + self.a33 - T::one()) * T::cast_from(0.5)).max(-T::one()).min(T::one());
+		let theta = cos.acos();
+		// Twice the axis scaled by sin θ
+		let vee = Vec3(self.a32 - self.a23, self.a13 - self.a31, self.a21 - self.a12);
+		if cos >= T::zero() {
+			let (a, _, _) = coefficients(theta * theta);
+			return vee * (T::cast_from(0.5) / a);
+		}
+		// Near π the antisymmetric part vanishes, recover the axis from the symmetric part R = cos I + sin [n]x + (1 - cos) n nT
+		let k = T::one() - cos;
+		let diag = [self.a11, self.a22, self.a33];
+		let i = if diag[0] >= diag[1] && diag[0] >= diag[2] { 0 } else if diag[1] >= diag[2] { 1 } else { 2 };
+		let rows = self.into_row_major();
+		let mut ni = ((diag[i] - cos) / k).max(T::zero()).sqrt();
+		if [vee.x, vee.y, vee.z][i] < T::zero() {
+			ni = -ni;
+		}
+		let mut n = [T::zero(); 3];
+		for (j, nj) in n.iter_mut().enumerate() {
+			*nj = if j == i { ni } else { (rows[i][j] + rows[j][i]) / (k * ni + k * ni) };
+		}
+		Vec3::from(n) * theta
+	}
+	/// Interpolates the rotation along the shortest arc with constant angular velocity.
+	///
+	/// Both matrices must be rotation matrices.
+	pub fn interpolate(self, rhs: Mat3<T>, t: T) -> Mat3<T> {
+		self * Mat3::exp((self.transpose() * rhs).ln() * t)
+	}
+}
+
+impl<T: Float> Affine3<T> {
+	/// Exponential map, the rigid transform of the twist `(omega, v)`.
+	///
+	/// The rotation is the exponential of `omega` and the translation is `v` integrated along the rotating frame.
+	pub fn exp(omega: Vec3<T>, v: Vec3<T>) -> Affine3<T> {
+		let theta_sqr = omega.len_sqr();
+		let (a, b, c) = coefficients(theta_sqr);
+		let r = combine(T::one() - b * theta_sqr, a, b, omega);
+		let jacobian = combine(T::one() - c * theta_sqr, b, c, omega);
+		Affine3::from_mat(r, jacobian * v)
+	}
+	/// Logarithm map, the twist `(omega, v)` of the rigid transform.
+	///
+	/// The transform must consist of a rotation and translation only.
+	pub fn ln(self) -> (Vec3<T>, Vec3<T>) {
+		let (r, t) = self.into_mat();
+		let omega = r.ln();
+		let theta_sqr = omega.len_sqr();
+		let (a, b, _) = coefficients(theta_sqr);
+		// Inverse of the left jacobian: I - ½ [w]x + d [w]x²
+		let d = if theta_sqr < T::cast_from(1e-8) {
+			T::cast_from(1.0 / 12.0) + theta_sqr / T::cast_from(720.0)
+		}
+		else {
+			(T::one() - a / (b + b)) / theta_sqr
+		};
+		let jacobian_inv = combine(T::one() - d * theta_sqr, -T::cast_from(0.5), d, omega);
+		(omega, jacobian_inv * t)
+	}
+	/// Interpolates the rigid transform along the screw motion with constant angular and linear velocity.
+	///
+	/// Both transforms must consist of a rotation and translation only.
+	pub fn interpolate(self, rhs: Affine3<T>, t: T) -> Affine3<T> {
+		let (omega, v) = (self.inverse() * rhs).ln();
+		self * Affine3::exp(omega * t, v * t)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn roundtrip() {
+		let pi = ::std::f64::consts::PI;
+		let cases = [
+			Vec3(0.0, 0.0, 0.0),
+			Vec3(1e-9, -2e-9, 3e-9),
+			Vec3(0.3, -0.2, 0.1),
+			Vec3(1.0, 2.0, -0.5).norm() * 2.5,
+			Vec3(0.0, 1.0, 0.0) * (pi - 1e-7),
+			Vec3(1.0, 1.0, 1.0).norm() * (pi - 1e-3),
+			Vec3(-1.0, 0.5, 0.25).norm() * 3.0,
+		];
+		for &omega in &cases {
+			let r = Mat3::exp(omega);
+			assert!((r * r.transpose()).into_row_major().iter().flatten().zip(Mat3::<f64>::identity().into_row_major().iter().flatten()).all(|(a, b)| (a - b).abs() < 1e-12));
+			assert!(r.ln().dist(omega) < 1e-6, "{:?} {:?}", omega, r.ln());
+			let v = Vec3(0.5, -1.0, 2.0);
+			let (w1, v1) = Affine3::exp(omega, v).ln();
+			assert!(w1.dist(omega) < 1e-6 && v1.dist(v) < 1e-6, "{:?} {:?}", w1, v1);
+		}
+		// Half a turn
+		let r = Mat3::exp(Vec3(0.0, 0.0, pi));
+		assert!((r.ln().len() - pi).abs() < 1e-12);
+		// Interpolation reaches both ends and the midpoint of the screw motion
+		let a = Affine3::exp(Vec3(0.1, 0.2, 0.3), Vec3(1.0, 0.0, 0.0));
+		let b = Affine3::exp(Vec3(-0.4, 0.5, 1.0), Vec3(0.0, 2.0, -1.0));
+		let end = a.interpolate(b, 1.0);
+		assert!(Vec3(end.a14, end.a24, end.a34).dist(Vec3(b.a14, b.a24, b.a34)) < 1e-12);
+		let mid = a.interpolate(b, 0.5);
+		let twice = mid * (a.inverse() * mid);
+		assert!(Vec3(twice.a14, twice.a24, twice.a34).dist(Vec3(b.a14, b.a24, b.a34)) < 1e-12);
+	}
+}
@@ -2,11 +2,12 @@
 2D transformation matrix.
 */
 
-use std::ops;
+use std::{mem, ops};
 
 use num::{Scalar, Float};
 use vec::Vec2;
 use angle::Angle;
+use error::MathError;
 
 use super::{Affine2, Transform2};
 
@@ -14,7 +15,8 @@ use super::{Affine2, Transform2};
 ///
 /// A 2x2 row-major matrix.
 #[cfg(feature = "row-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Mat2<T> {
 	pub a11: T, pub a12: T,
@@ -25,13 +27,16 @@ pub struct Mat2<T> {
 ///
 /// A 2x2 column-major matrix.
 #[cfg(feature = "column-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Mat2<T> {
 	pub a11: T, pub a21: T,
 	pub a12: T, pub a22: T,
 }
 
+pretty!(Mat2 2 { a11, a12, a21, a22 });
+
 //----------------------------------------------------------------
 // Constructors
 
@@ -165,6 +170,76 @@ impl<T> Mat2<T> {
 			[self.a12, self.a22],
 		]
 	}
+	/// Imports from a flat array in row major order.
+	pub fn from_rows_array(arr: [T; 4]) -> Mat2<T> where T: Copy {
+		Mat2 {
+			a11: arr[0], a12: arr[1],
+			a21: arr[2], a22: arr[3],
+		}
+	}
+	/// Imports from a flat array in column major order.
+	pub fn from_cols_array(arr: [T; 4]) -> Mat2<T> where T: Copy {
+		Mat2 {
+			a11: arr[0], a12: arr[2],
+			a21: arr[1], a22: arr[3],
+		}
+	}
+	/// Exports as a flat array in row major order.
+	pub fn to_rows_array(self) -> [T; 4] {
+		[self.a11, self.a12, self.a21, self.a22]
+	}
+	/// Exports as a flat array in column major order.
+	pub fn to_cols_array(self) -> [T; 4] {
+		[self.a11, self.a21, self.a12, self.a22]
+	}
+	/// Constructs the matrix from row vectors.
+	pub fn from_rows(row0: Vec2<T>, row1: Vec2<T>) -> Mat2<T> where T: Copy {
+		Mat2::from_row_major([row0.into(), row1.into()])
+	}
+	/// Constructs the matrix from column vectors.
+	pub fn from_cols(col0: Vec2<T>, col1: Vec2<T>) -> Mat2<T> where T: Copy {
+		Mat2::from_column_major([col0.into(), col1.into()])
+	}
+	/// Returns the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn row(self, i: usize) -> Vec2<T> where T: Copy {
+		self.into_row_major()[i].into()
+	}
+	/// Returns the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn col(self, j: usize) -> Vec2<T> where T: Copy {
+		self.into_column_major()[j].into()
+	}
+	/// Sets the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_row(&mut self, i: usize, row: Vec2<T>) where T: Copy {
+		let mut rows = self.into_row_major();
+		rows[i] = row.into();
+		*self = Mat2::from_row_major(rows);
+	}
+	/// Sets the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_col(&mut self, j: usize, col: Vec2<T>) where T: Copy {
+		let mut cols = self.into_column_major();
+		cols[j] = col.into();
+		*self = Mat2::from_column_major(cols);
+	}
+}
+
+/// Views the matrix as a flat array in its memory layout, row major unless the `column-major` feature is enabled.
+impl<T> AsRef<[T; 4]> for Mat2<T> {
+	fn as_ref(&self) -> &[T; 4] {
+		unsafe { mem::transmute(self) }
+	}
+}
+impl<T> AsMut<[T; 4]> for Mat2<T> {
+	fn as_mut(&mut self) -> &mut [T; 4] {
+		unsafe { mem::transmute(self) }
+	}
 }
 
 //----------------------------------------------------------------
@@ -211,6 +286,8 @@ impl<T: Scalar> Mat2<T> {
 		self.a11 * self.a22 - self.a21 * self.a12
 	}
 	/// Calculates the inverse matrix.
+	///
+	/// If the matrix is singular it is returned unchanged, see `try_inverse`.
 	pub fn inverse(&self) -> Mat2<T> where T: Float {
 		let det = self.det();
 		if det != T::zero() {
@@ -218,6 +295,27 @@ impl<T: Scalar> Mat2<T> {
 		}
 		else { *self }
 	}
+	/// Calculates the inverse matrix.
+	///
+	/// Returns `Err(MathError::Singular)` if the determinant is zero or not finite.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat2};
+	/// use cvmath::error::MathError;
+	///
+	/// let mat = Mat2::new(1.0, 2.0, 2.0, 4.0);
+	/// assert_eq!(Err(MathError::Singular), mat.try_inverse());
+	///
+	/// let mat = Mat2::new(2.0, 0.0, 0.0, 4.0);
+	/// assert_eq!(Ok(Mat2::new(0.5, 0.0, 0.0, 0.25)), mat.try_inverse());
+	/// ```
+	pub fn try_inverse(&self) -> Result<Mat2<T>, MathError> where T: Float {
+		let det = self.det();
+		if det == T::zero() || !det.is_finite() {
+			return Err(MathError::Singular);
+		}
+		Ok(self.inverse())
+	}
 	/// Calculates the transposed matrix.
 	pub fn transpose(&self) -> Mat2<T> {
 		Mat2 {
@@ -237,6 +335,58 @@ impl<T: Scalar> Mat2<T> {
 //----------------------------------------------------------------
 // Operators
 
+impl<T: ops::Add<Output = T>> ops::Add<Mat2<T>> for Mat2<T> {
+	type Output = Mat2<T>;
+	fn add(self, rhs: Mat2<T>) -> Mat2<T> {
+		Mat2 {
+			a11: self.a11 + rhs.a11,
+			a12: self.a12 + rhs.a12,
+			a21: self.a21 + rhs.a21,
+			a22: self.a22 + rhs.a22,
+		}
+	}
+}
+impl<T: ops::AddAssign> ops::AddAssign<Mat2<T>> for Mat2<T> {
+	fn add_assign(&mut self, rhs: Mat2<T>) {
+		self.a11 += rhs.a11;
+		self.a12 += rhs.a12;
+		self.a21 += rhs.a21;
+		self.a22 += rhs.a22;
+	}
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub<Mat2<T>> for Mat2<T> {
+	type Output = Mat2<T>;
+	fn sub(self, rhs: Mat2<T>) -> Mat2<T> {
+		Mat2 {
+			a11: self.a11 - rhs.a11,
+			a12: self.a12 - rhs.a12,
+			a21: self.a21 - rhs.a21,
+			a22: self.a22 - rhs.a22,
+		}
+	}
+}
+impl<T: ops::SubAssign> ops::SubAssign<Mat2<T>> for Mat2<T> {
+	fn sub_assign(&mut self, rhs: Mat2<T>) {
+		self.a11 -= rhs.a11;
+		self.a12 -= rhs.a12;
+		self.a21 -= rhs.a21;
+		self.a22 -= rhs.a22;
+	}
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Mat2<T> {
+	type Output = Mat2<T>;
+	fn neg(self) -> Mat2<T> {
+		Mat2 {
+			a11: -self.a11,
+			a12: -self.a12,
+			a21: -self.a21,
+			a22: -self.a22,
+		}
+	}
+}
+
 impl<T: Copy + ops::Mul<Output = T>> ops::Mul<T> for Mat2<T> {
 	type Output = Mat2<T>;
 	fn mul(self, rhs: T) -> Mat2<T> {
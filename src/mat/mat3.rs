@@ -3,11 +3,12 @@
 3D transformation matrix.
 */
 
-use std::ops;
+use std::{mem, ops};
 
 use num::{Scalar, Float};
 use vec::Vec3;
 use angle::Angle;
+use error::MathError;
 
 use super::{Affine3, Transform3};
 
@@ -15,7 +16,8 @@ use super::{Affine3, Transform3};
 ///
 /// A 3x3 row-major matrix.
 #[cfg(feature = "row-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Mat3<T> {
 	pub a11: T, pub a12: T, pub a13: T,
@@ -27,7 +29,8 @@ pub struct Mat3<T> {
 ///
 /// A 3x3 column-major matrix.
 #[cfg(feature = "column-major")]
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Mat2<T> {
 	pub a11: T, pub a21: T, pub a31: T,
@@ -35,6 +38,8 @@ pub struct Mat2<T> {
 	pub a13: T, pub a23: T, pub a33: T,
 }
 
+pretty!(Mat3 3 { a11, a12, a13, a21, a22, a23, a31, a32, a33 });
+
 //----------------------------------------------------------------
 // Constructors
 
@@ -77,14 +82,20 @@ impl<T: Scalar> Mat3<T> {
 			a31: T::zero(), a32: T::zero(), a33: scale.z,
 		}
 	}
+	/// Rotation matrix around the X axis.
+	///
+	/// Rotates counter-clockwise when looking down the axis towards the origin.
 	pub fn rotate_x<A>(angle: A) -> Mat3<T> where T: Float, A: Angle<T = T> {
 		let (sin, cos) = angle.sin_cos();
 		Mat3 {
 			a11: T::one(),  a12: T::zero(), a13: T::zero(),
-			a21: T::zero(), a22: cos,       a23: sin,
-			a31: T::zero(), a32: -sin,      a33: cos,
+			a21: T::zero(), a22: cos,       a23: -sin,
+			a31: T::zero(), a32: sin,       a33: cos,
 		}
 	}
+	/// Rotation matrix around the Y axis.
+	///
+	/// Rotates counter-clockwise when looking down the axis towards the origin.
 	pub fn rotate_y<A>(angle: A) -> Mat3<T> where T: Float, A: Angle<T = T> {
 		let (sin, cos) = angle.sin_cos();
 		Mat3 {
@@ -93,12 +104,42 @@ impl<T: Scalar> Mat3<T> {
 			a31: -sin,       a32: T::zero(), a33: cos,
 		}
 	}
+	/// Rotation matrix around the Z axis.
+	///
+	/// Rotates counter-clockwise when looking down the axis towards the origin.
 	pub fn rotate_z<A>(angle: A) -> Mat3<T> where T: Float, A: Angle<T = T> {
 		let (sin, cos) = angle.sin_cos();
 		Mat3 {
-			a11: cos,        a22: sin,      a23: T::zero(),
-			a21: -sin,       a32: cos,      a33: T::zero(),
-			a31: T::zero(), a12: T::zero(), a13: T::one(),
+			a11: cos,       a12: -sin,      a13: T::zero(),
+			a21: sin,       a22: cos,       a23: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: T::one(),
+		}
+	}
+	/// Rotation matrix around an arbitrary axis.
+	///
+	/// Rotates counter-clockwise when looking down the axis towards the origin.
+	/// The axis does not need to be normalized, the zero vector results in the identity matrix.
+	///
+	/// ```
+	/// # #[macro_use] extern crate cvmath;
+	/// use cvmath::prelude::{Mat3, Vec3, Deg};
+	///
+	/// # fn main() {
+	/// let mat = Mat3::rotate(Vec3(0.0, 0.0, 2.0), Deg(90.0f64));
+	/// assert_approx_eq!(Vec3(0.0, 1.0, 0.0), mat * Vec3(1.0, 0.0, 0.0));
+	///
+	/// let x = Mat3::rotate(Vec3(1.0, 0.0, 0.0), Deg(30.0f64));
+	/// assert_approx_eq!(Mat3::rotate_x(Deg(30.0)), x);
+	/// # }
+	/// ```
+	pub fn rotate<V, A>(axis: V, angle: A) -> Mat3<T> where T: Float, V: Into<Vec3<T>>, A: Angle<T = T> {
+		let Vec3 { x, y, z } = axis.into().norm();
+		let (sin, cos) = angle.sin_cos();
+		let t = T::one() - cos;
+		Mat3 {
+			a11: t * x * x + cos,     a12: t * x * y - sin * z, a13: t * x * z + sin * y,
+			a21: t * x * y + sin * z, a22: t * y * y + cos,     a23: t * y * z - sin * x,
+			a31: t * x * z - sin * y, a32: t * y * z + sin * x, a33: t * z * z + cos,
 		}
 	}
 }
@@ -139,6 +180,78 @@ impl<T> Mat3<T> {
 			[self.a13, self.a23, self.a33],
 		]
 	}
+	/// Imports from a flat array in row major order.
+	pub fn from_rows_array(arr: [T; 9]) -> Mat3<T> where T: Copy {
+		Mat3 {
+			a11: arr[0], a12: arr[1], a13: arr[2],
+			a21: arr[3], a22: arr[4], a23: arr[5],
+			a31: arr[6], a32: arr[7], a33: arr[8],
+		}
+	}
+	/// Imports from a flat array in column major order.
+	pub fn from_cols_array(arr: [T; 9]) -> Mat3<T> where T: Copy {
+		Mat3 {
+			a11: arr[0], a12: arr[3], a13: arr[6],
+			a21: arr[1], a22: arr[4], a23: arr[7],
+			a31: arr[2], a32: arr[5], a33: arr[8],
+		}
+	}
+	/// Exports as a flat array in row major order.
+	pub fn to_rows_array(self) -> [T; 9] {
+		[self.a11, self.a12, self.a13, self.a21, self.a22, self.a23, self.a31, self.a32, self.a33]
+	}
+	/// Exports as a flat array in column major order.
+	pub fn to_cols_array(self) -> [T; 9] {
+		[self.a11, self.a21, self.a31, self.a12, self.a22, self.a32, self.a13, self.a23, self.a33]
+	}
+	/// Constructs the matrix from row vectors.
+	pub fn from_rows(row0: Vec3<T>, row1: Vec3<T>, row2: Vec3<T>) -> Mat3<T> where T: Copy {
+		Mat3::from_row_major([row0.into(), row1.into(), row2.into()])
+	}
+	/// Constructs the matrix from column vectors.
+	pub fn from_cols(col0: Vec3<T>, col1: Vec3<T>, col2: Vec3<T>) -> Mat3<T> where T: Copy {
+		Mat3::from_column_major([col0.into(), col1.into(), col2.into()])
+	}
+	/// Returns the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn row(self, i: usize) -> Vec3<T> where T: Copy {
+		self.into_row_major()[i].into()
+	}
+	/// Returns the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn col(self, j: usize) -> Vec3<T> where T: Copy {
+		self.into_column_major()[j].into()
+	}
+	/// Sets the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_row(&mut self, i: usize, row: Vec3<T>) where T: Copy {
+		let mut rows = self.into_row_major();
+		rows[i] = row.into();
+		*self = Mat3::from_row_major(rows);
+	}
+	/// Sets the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_col(&mut self, j: usize, col: Vec3<T>) where T: Copy {
+		let mut cols = self.into_column_major();
+		cols[j] = col.into();
+		*self = Mat3::from_column_major(cols);
+	}
+}
+
+/// Views the matrix as a flat array in its memory layout, row major unless the `column-major` feature is enabled.
+impl<T> AsRef<[T; 9]> for Mat3<T> {
+	fn as_ref(&self) -> &[T; 9] {
+		unsafe { mem::transmute(self) }
+	}
+}
+impl<T> AsMut<[T; 9]> for Mat3<T> {
+	fn as_mut(&mut self) -> &mut [T; 9] {
+		unsafe { mem::transmute(self) }
+	}
 }
 
 //----------------------------------------------------------------
@@ -194,6 +307,16 @@ impl<T: Scalar> Mat3<T> {
 		}
 		else { *self }
 	}
+	/// Calculates the inverse matrix.
+	///
+	/// Returns `Err(MathError::Singular)` if the determinant is zero or not finite.
+	pub fn try_inverse(&self) -> Result<Mat3<T>, MathError> where T: Float {
+		let det = self.det();
+		if det == T::zero() || !det.is_finite() {
+			return Err(MathError::Singular);
+		}
+		Ok(self.inverse())
+	}
 	pub fn transpose(&self) -> Mat3<T> {
 		Mat3 {
 			a11: self.a11, a12: self.a21, a13: self.a31,
@@ -216,6 +339,176 @@ impl<T: Scalar> Mat3<T> {
 			a33: self.a11 * self.a22 - self.a12 * self.a21,
 		}
 	}
+	/// Calculates the inverse transpose matrix.
+	///
+	/// Transforms surface normals such that they remain perpendicular to the transformed surface under non-uniform scaling.
+	/// If the matrix is singular it is returned unchanged, see `inverse`.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat3, Vec3};
+	///
+	/// let mat = Mat3::scale(Vec3(2.0, 4.0, 1.0));
+	/// let tangent = mat * Vec3(1.0, -1.0, 0.0);
+	/// let normal = mat.inverse_transpose() * Vec3(1.0, 1.0, 0.0);
+	/// assert_eq!(0.0, normal.dot(tangent));
+	/// ```
+	pub fn inverse_transpose(&self) -> Mat3<T> where T: Float {
+		let det = self.det();
+		if det != T::zero() {
+			self.adjugate().transpose() * (T::one() / det)
+		}
+		else { *self }
+	}
+}
+
+impl<T: Float> Mat3<T> {
+	/// Orthonormalizes the rotation matrix.
+	///
+	/// Rotation matrices accumulated from many incremental rotations drift away from being orthonormal due to rounding errors.
+	/// Renormalizes using Gram-Schmidt on the columns: the X axis keeps its direction, the Y axis is made perpendicular to it
+	/// and the Z axis is recomputed from their cross product.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat3, Deg};
+	///
+	/// let step = Mat3::rotate_x(Deg(0.1f32)) * Mat3::rotate_y(Deg(0.3f32));
+	/// let mut rot = Mat3::identity();
+	/// for _ in 0..100_000 {
+	///     rot = step * rot;
+	/// }
+	/// let error = |m: Mat3<f32>| (m.x().len() - 1.0).abs().max(m.x().dot(m.y()).abs());
+	/// assert!(error(rot) > 1e-4);
+	/// assert!(error(rot.orthonormalize()) < 1e-6);
+	/// ```
+	pub fn orthonormalize(self) -> Mat3<T> {
+		let x = self.x().norm();
+		let y = self.y();
+		let y = (y - x * x.dot(y)).norm();
+		let z = x.cross(y);
+		Mat3::from_cols(x, y, z)
+	}
+	/// Polar decomposition into a rotation and a symmetric stretch, `self == rotation * stretch`.
+	///
+	/// Extracts the rotation closest to the matrix, used to recover the rotation of deformed frames in physics and skinning.
+	/// Iteratively averages the matrix with its inverse-transpose (Higham's method).
+	///
+	/// The rotation contains a reflection if the determinant is negative. Singular matrices have no unique decomposition,
+	/// the returned rotation is not orthogonal in that case.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat3, Vec3, Deg};
+	///
+	/// let rotation = Mat3::rotate_x(Deg(30.0f64)) * Mat3::rotate_y(Deg(-50.0));
+	/// let stretch = Mat3::new(
+	///     2.0, 0.5, 0.0,
+	///     0.5, 1.0, 0.2,
+	///     0.0, 0.2, 3.0,
+	/// );
+	/// let (r, s) = (rotation * stretch).polar_decompose();
+	/// for (a, b) in r.into_row_major().iter().flatten().zip(rotation.into_row_major().iter().flatten()) {
+	///     assert!((a - b).abs() < 1e-12);
+	/// }
+	/// for (a, b) in s.into_row_major().iter().flatten().zip(stretch.into_row_major().iter().flatten()) {
+	///     assert!((a - b).abs() < 1e-12);
+	/// }
+	/// ```
+	pub fn polar_decompose(self) -> (Mat3<T>, Mat3<T>) {
+		let half = T::one() / (T::one() + T::one());
+		let tolerance = T::epsilon() * T::cast_from(8.0);
+		let (mut x, mut y, mut z) = (self.x(), self.y(), self.z());
+		for _ in 0..64 {
+			// The columns of the inverse-transpose are the cross products of the columns divided by the determinant
+			let (cx, cy, cz) = (y.cross(z), z.cross(x), x.cross(y));
+			let det = x.dot(cx);
+			if det == T::zero() {
+				break;
+			}
+			let inv_det = T::one() / det;
+			let (nx, ny, nz) = ((x + cx * inv_det) * half, (y + cy * inv_det) * half, (z + cz * inv_det) * half);
+			let change = (nx - x).abs().reduce(T::max).max((ny - y).abs().reduce(T::max)).max((nz - z).abs().reduce(T::max));
+			x = nx;
+			y = ny;
+			z = nz;
+			if change <= tolerance {
+				break;
+			}
+		}
+		let rotation = Mat3::from_cols(x, y, z);
+		let stretch = rotation.transpose() * self;
+		(rotation, stretch)
+	}
+	/// Eigen decomposition of a symmetric matrix.
+	///
+	/// Returns the eigenvalues sorted from largest to smallest and the corresponding unit eigenvectors as the columns of an orthogonal matrix,
+	/// such that `self == vectors * Mat3::scale(values) * vectors.transpose()`.
+	/// Used for principal component analysis, diagonalizing inertia tensors and fitting oriented bounding boxes.
+	///
+	/// Uses the cyclic Jacobi method, only the upper triangle of the matrix is read.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat3, Vec3};
+	///
+	/// let m = Mat3::new(
+	///     2.0, 1.0, 0.0,
+	///     1.0, 2.0, 0.0,
+	///     0.0, 0.0, 5.0,
+	/// );
+	/// let (values, vectors) = m.eigen_symmetric();
+	/// assert!(values.dist(Vec3(5.0, 3.0, 1.0)) < 1e-12);
+	/// for (i, &value) in [values.x, values.y, values.z].iter().enumerate() {
+	///     let v = [vectors.x(), vectors.y(), vectors.z()][i];
+	///     assert!((m * v).dist(v * value) < 1e-12);
+	/// }
+	/// ```
+	pub fn eigen_symmetric(self) -> (Vec3<T>, Mat3<T>) {
+		let (zero, one) = (T::zero(), T::one());
+		let mut a = self.into_row_major();
+		a[1][0] = a[0][1];
+		a[2][0] = a[0][2];
+		a[2][1] = a[1][2];
+		let mut v = Mat3::<T>::identity().into_row_major();
+		let scale = (0..3).flat_map(|i| (i..3).map(move |j| (i, j))).fold(zero, |acc, (i, j)| acc + a[i][j] * a[i][j]);
+		let tolerance = scale * T::epsilon() * T::epsilon();
+		for _ in 0..32 {
+			let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+			if off <= tolerance {
+				break;
+			}
+			for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+				let apq = a[p][q];
+				if apq == zero {
+					continue;
+				}
+				// Jacobi rotation annihilating the off-diagonal element
+				let theta = (a[q][q] - a[p][p]) / (apq + apq);
+				let t = one / (theta.abs() + (theta * theta + one).sqrt());
+				let t = if theta < zero { -t } else { t };
+				let c = one / (t * t + one).sqrt();
+				let s = t * c;
+				let r = 3 - p - q;
+				let (arp, arq) = (a[r][p], a[r][q]);
+				a[r][p] = c * arp - s * arq;
+				a[r][q] = s * arp + c * arq;
+				a[p][r] = a[r][p];
+				a[q][r] = a[r][q];
+				a[p][p] -= t * apq;
+				a[q][q] += t * apq;
+				a[p][q] = zero;
+				a[q][p] = zero;
+				for row in v.iter_mut() {
+					let (vkp, vkq) = (row[p], row[q]);
+					row[p] = c * vkp - s * vkq;
+					row[q] = s * vkp + c * vkq;
+				}
+			}
+		}
+		// Sort from largest to smallest eigenvalue
+		let mut order = [0, 1, 2];
+		order.sort_by(|&i, &j| a[j][j].partial_cmp(&a[i][i]).unwrap_or(::std::cmp::Ordering::Equal));
+		let values = Vec3 { x: a[order[0]][order[0]], y: a[order[1]][order[1]], z: a[order[2]][order[2]] };
+		let col = |i: usize| Vec3 { x: v[0][i], y: v[1][i], z: v[2][i] };
+		(values, Mat3::from_cols(col(order[0]), col(order[1]), col(order[2])))
+	}
 }
 
 //----------------------------------------------------------------
@@ -296,3 +589,39 @@ impl<T: Copy + ops::Add<Output = T> + ops::Mul<Output = T>> ops::MulAssign<Mat3<
 }
 
 impl<T: Copy + ops::Add<Output = T> + ops::Mul<Output = T>> Transform3<T> for Mat3<T> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn eigen_symmetric() {
+		let m = Mat3::new(
+			4.0, -2.0, 0.5,
+			-2.0, 1.0, 3.0,
+			0.5, 3.0, -6.0,
+		);
+		let (values, vectors) = m.eigen_symmetric();
+		assert!(values.x >= values.y && values.y >= values.z);
+		let identity = vectors.transpose() * vectors;
+		let restored = vectors * Mat3::scale(values) * vectors.transpose();
+		let flat = |m: Mat3<f64>| m.into_row_major().iter().flatten().cloned().collect::<Vec<_>>();
+		for (a, b) in flat(identity).iter().zip(flat(Mat3::identity())) {
+			assert!((a - b).abs() < 1e-12);
+		}
+		for (a, b) in flat(restored).iter().zip(flat(m)) {
+			assert!((a - b).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn rows_cols() {
+		let mut m = Mat3::from_rows(Vec3(1, 2, 3), Vec3(4, 5, 6), Vec3(7, 8, 9));
+		assert_eq!(Vec3(4, 5, 6), m.row(1));
+		assert_eq!(Vec3(3, 6, 9), m.col(2));
+		assert_eq!(m.transpose(), Mat3::from_cols(m.row(0), m.row(1), m.row(2)));
+		m.set_row(0, Vec3(-1, -2, -3));
+		m.set_col(1, Vec3(0, 0, 0));
+		assert_eq!(Mat3::new(-1, 0, -3, 4, 0, 6, 7, 0, 9), m);
+	}
+}
@@ -0,0 +1,1010 @@
+/*!
+3D projective transformation matrix.
+*/
+
+use std::{mem, ops};
+
+use num::{Scalar, Float, Zero, One};
+use vec::{Vec2, Vec3, Vec4};
+use angle::Angle;
+use plane::Plane;
+use projection::{Viewport, DepthRange};
+use error::MathError;
+
+use super::{Mat3, Affine3};
+
+/// 3D projective transformation matrix.
+///
+/// A 4x4 row-major matrix.
+#[cfg(feature = "row-major")]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Mat4<T> {
+	pub a11: T, pub a12: T, pub a13: T, pub a14: T,
+	pub a21: T, pub a22: T, pub a23: T, pub a24: T,
+	pub a31: T, pub a32: T, pub a33: T, pub a34: T,
+	pub a41: T, pub a42: T, pub a43: T, pub a44: T,
+}
+
+/// 3D projective transformation matrix.
+///
+/// A 4x4 column-major matrix.
+#[cfg(feature = "column-major")]
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Mat4<T> {
+	pub a11: T, pub a21: T, pub a31: T, pub a41: T,
+	pub a12: T, pub a22: T, pub a32: T, pub a42: T,
+	pub a13: T, pub a23: T, pub a33: T, pub a43: T,
+	pub a14: T, pub a24: T, pub a34: T, pub a44: T,
+}
+
+pretty!(Mat4 4 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34, a41, a42, a43, a44 });
+
+//----------------------------------------------------------------
+// Constructors
+
+impl<T> Mat4<T> {
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		a11: T, a12: T, a13: T, a14: T,
+		a21: T, a22: T, a23: T, a24: T,
+		a31: T, a32: T, a33: T, a34: T,
+		a41: T, a42: T, a43: T, a44: T,
+	) -> Mat4<T> {
+		Mat4 {
+			a11, a12, a13, a14,
+			a21, a22, a23, a24,
+			a31, a32, a33, a34,
+			a41, a42, a43, a44,
+		}
+	}
+}
+impl<T: Scalar> Mat4<T> {
+	/// Identity matrix.
+	pub fn identity() -> Mat4<T> {
+		Mat4 {
+			a11: T::one(),  a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(), a22: T::one(),  a23: T::zero(), a24: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: T::one(),  a34: T::zero(),
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+	/// Null matrix.
+	pub fn null() -> Mat4<T> {
+		Mat4 {
+			a11: T::zero(), a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(), a22: T::zero(), a23: T::zero(), a24: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: T::zero(), a34: T::zero(),
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::zero(),
+		}
+	}
+	/// Translation matrix.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Vec3};
+	///
+	/// let mat = Mat4::translate(Vec3(1, 2, 3));
+	/// assert_eq!(Vec3(5, 7, 9), mat.transform_point(Vec3(4, 5, 6)));
+	/// ```
+	pub fn translate<V>(trans: V) -> Mat4<T> where V: Into<Vec3<T>> {
+		let trans = trans.into();
+		Mat4 {
+			a11: T::one(),  a12: T::zero(), a13: T::zero(), a14: trans.x,
+			a21: T::zero(), a22: T::one(),  a23: T::zero(), a24: trans.y,
+			a31: T::zero(), a32: T::zero(), a33: T::one(),  a34: trans.z,
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+	/// Scaling matrix.
+	///
+	/// Scales around the origin.
+	pub fn scale<V>(scale: V) -> Mat4<T> where V: Into<Vec3<T>> {
+		let scale = scale.into();
+		Mat4 {
+			a11: scale.x,   a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(), a22: scale.y,   a23: T::zero(), a24: T::zero(),
+			a31: T::zero(), a32: T::zero(), a33: scale.z,   a34: T::zero(),
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+	/// Rotation matrix around the X axis.
+	///
+	/// See [`Mat3::rotate_x`](struct.Mat3.html#method.rotate_x).
+	pub fn rotate_x<A>(angle: A) -> Mat4<T> where T: Float, A: Angle<T = T> {
+		Mat3::rotate_x(angle).into()
+	}
+	/// Rotation matrix around the Y axis.
+	///
+	/// See [`Mat3::rotate_y`](struct.Mat3.html#method.rotate_y).
+	pub fn rotate_y<A>(angle: A) -> Mat4<T> where T: Float, A: Angle<T = T> {
+		Mat3::rotate_y(angle).into()
+	}
+	/// Rotation matrix around the Z axis.
+	///
+	/// See [`Mat3::rotate_z`](struct.Mat3.html#method.rotate_z).
+	pub fn rotate_z<A>(angle: A) -> Mat4<T> where T: Float, A: Angle<T = T> {
+		Mat3::rotate_z(angle).into()
+	}
+	/// Rotation matrix around an arbitrary axis.
+	///
+	/// See [`Mat3::rotate`](struct.Mat3.html#method.rotate).
+	pub fn rotate<V, A>(axis: V, angle: A) -> Mat4<T> where T: Float, V: Into<Vec3<T>>, A: Angle<T = T> {
+		Mat3::rotate(axis, angle).into()
+	}
+}
+
+//----------------------------------------------------------------
+// Projections
+
+/// Perspective projections.
+///
+/// The projections are right-handed, the camera looks down the negative Z axis and
+/// clip space depth ranges from `0` to `1` (Direct3D, Vulkan, Metal and WebGPU conventions).
+///
+/// Reversed-Z projections map the near plane to depth `1` and the far plane to depth `0`,
+/// combined with a floating point depth buffer this distributes precision much more evenly.
+/// Use a `GREATER` depth test and clear the depth buffer to `0`.
+///
+/// ```
+/// use cvmath::prelude::{Mat4, Vec4, Deg};
+///
+/// let (near, far) = (0.1, 100.0);
+/// let depth = |proj: Mat4<f64>, z: f64| (proj * Vec4(0.0, 0.0, z, 1.0)).hdiv().z;
+///
+/// let proj = Mat4::perspective(Deg(90.0), 1.0, near, far);
+/// assert!((depth(proj, -near) - 0.0).abs() < 1e-12);
+/// assert!((depth(proj, -far) - 1.0).abs() < 1e-12);
+///
+/// let proj = Mat4::perspective_reverse(Deg(90.0), 1.0, near, far);
+/// assert!((depth(proj, -near) - 1.0).abs() < 1e-12);
+/// assert!((depth(proj, -far) - 0.0).abs() < 1e-12);
+///
+/// let proj = Mat4::perspective_infinite_reverse(Deg(90.0), 1.0, near);
+/// assert!((depth(proj, -near) - 1.0).abs() < 1e-12);
+/// assert!(depth(proj, -1.0e30) < 1e-30);
+/// ```
+impl<T: Float> Mat4<T> {
+	fn perspective_base<A: Angle<T = T>>(fovy: A, aspect: T, a33: T, a34: T) -> Mat4<T> {
+		let f = T::one() / (fovy / T::cast_from(2.0)).tan();
+		Mat4 {
+			a11: f / aspect, a12: T::zero(), a13: T::zero(), a14: T::zero(),
+			a21: T::zero(),  a22: f,         a23: T::zero(), a24: T::zero(),
+			a31: T::zero(),  a32: T::zero(), a33,            a34,
+			a41: T::zero(),  a42: T::zero(), a43: -T::one(), a44: T::zero(),
+		}
+	}
+	/// Perspective projection mapping the near plane to depth `0` and the far plane to depth `1`.
+	///
+	/// The vertical field of view `fovy` is the full angle, `aspect` is the width divided by the height.
+	pub fn perspective<A: Angle<T = T>>(fovy: A, aspect: T, near: T, far: T) -> Mat4<T> {
+		let range = near - far;
+		Mat4::perspective_base(fovy, aspect, far / range, near * far / range)
+	}
+	/// Reversed-Z perspective projection mapping the near plane to depth `1` and the far plane to depth `0`.
+	pub fn perspective_reverse<A: Angle<T = T>>(fovy: A, aspect: T, near: T, far: T) -> Mat4<T> {
+		let range = far - near;
+		Mat4::perspective_base(fovy, aspect, near / range, near * far / range)
+	}
+	/// Perspective projection with the far plane at infinity, the near plane maps to depth `0`.
+	pub fn perspective_infinite<A: Angle<T = T>>(fovy: A, aspect: T, near: T) -> Mat4<T> {
+		Mat4::perspective_base(fovy, aspect, -T::one(), -near)
+	}
+	/// Reversed-Z perspective projection with the far plane at infinity, the near plane maps to depth `1`.
+	pub fn perspective_infinite_reverse<A: Angle<T = T>>(fovy: A, aspect: T, near: T) -> Mat4<T> {
+		Mat4::perspective_base(fovy, aspect, T::zero(), near)
+	}
+	/// Offsets the projection by a sub-pixel amount.
+	///
+	/// The `jitter` is given in pixels for a viewport of the given size in pixels,
+	/// positive values move the image right and up in normalized device coordinates.
+	/// Used with temporal anti-aliasing, see [`Vec2::halton`](../vec/struct.Vec2.html#method.halton) for a jitter sequence.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Vec2, Vec4, Deg};
+	///
+	/// let viewport = Vec2(1920.0f64, 1080.0);
+	/// let jitter = Vec2::<f64>::halton(1) - Vec2(0.5, 0.5);
+	/// let proj = Mat4::perspective(Deg(60.0), viewport.x / viewport.y, 0.1, 100.0);
+	///
+	/// let pt = Vec4(1.0, 2.0, -10.0, 1.0);
+	/// let offset = (proj.jittered(jitter, viewport) * pt).hdiv() - (proj * pt).hdiv();
+	/// assert!((offset.x - 2.0 * jitter.x / viewport.x).abs() < 1e-12);
+	/// assert!((offset.y - 2.0 * jitter.y / viewport.y).abs() < 1e-12);
+	/// ```
+	pub fn jittered(self, jitter: Vec2<T>, viewport: Vec2<T>) -> Mat4<T> {
+		let two = T::one() + T::one();
+		let offset = jitter * two / viewport;
+		Mat4 {
+			a11: self.a11 + offset.x * self.a41,
+			a12: self.a12 + offset.x * self.a42,
+			a13: self.a13 + offset.x * self.a43,
+			a14: self.a14 + offset.x * self.a44,
+			a21: self.a21 + offset.y * self.a41,
+			a22: self.a22 + offset.y * self.a42,
+			a23: self.a23 + offset.y * self.a43,
+			a24: self.a24 + offset.y * self.a44,
+			..self
+		}
+	}
+	/// Pick matrix narrowing the projection to a region of the screen.
+	///
+	/// The region is centered at `center` with the given `size`, both in pixels of the viewport.
+	/// Multiply the projection with the pick matrix on the left so the region fills the whole viewport,
+	/// used for selection rendering and zooming into a region of the screen.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Vec2, Vec3, Point2, Deg};
+	/// use cvmath::projection::Viewport;
+	///
+	/// let viewport = Viewport::new(Point2(0.0, 0.0), Vec2(800.0, 600.0));
+	/// let proj = Mat4::perspective(Deg(90.0f64), 800.0 / 600.0, 1.0, 100.0);
+	/// let pick = Mat4::pick(Vec2(600.0, 100.0), Vec2(4.0, 4.0), &viewport);
+	///
+	/// // The region's corners map to the corners of the viewport
+	/// let world = cvmath::projection::unproject(Vec2(598.0, 102.0), 0.5, &viewport, &proj.inverse());
+	/// let screen = viewport.ndc_to_screen((pick * proj).transform_point(world));
+	/// assert!(screen.dist(Vec3(0.0, 600.0, 0.5)) < 1e-9);
+	/// ```
+	pub fn pick(center: Vec2<T>, size: Vec2<T>, viewport: &Viewport<T>) -> Mat4<T> {
+		let (zero, one) = (T::zero(), T::one());
+		let center = viewport.screen_to_ndc(center.vec3(viewport.min_depth));
+		let scale = viewport.size / size;
+		Mat4 {
+			a11: scale.x, a12: zero,    a13: zero, a14: -center.x * scale.x,
+			a21: zero,    a22: scale.y, a23: zero, a24: -center.y * scale.y,
+			a31: zero,    a32: zero,    a33: one,  a34: zero,
+			a41: zero,    a42: zero,    a43: zero, a44: one,
+		}
+	}
+	/// Replaces the near plane of the projection with an arbitrary clipping plane.
+	///
+	/// Implements [Lengyel's oblique near-plane clipping](http://www.terathon.com/lengyel/Lengyel-Oblique.pdf),
+	/// used for planar reflections to clip away the geometry behind the mirror or water surface.
+	/// The far plane is adjusted to keep the depth range tight which reduces depth precision.
+	///
+	/// The plane is given in view space and must face away from the camera (the camera is in its negative half-space).
+	/// Requires a standard (not reversed-Z) projection with clip space depth ranging from `0` to `1`.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Plane, Vec3, Vec4, Deg};
+	///
+	/// let proj = Mat4::perspective(Deg(60.0), 1.5, 0.1, 100.0);
+	/// let clip = Plane::from_point_normal(Vec3(0.0, 0.0, -5.0f64), Vec3(0.0, 0.5, -1.0));
+	/// let oblique = proj.obliquely_clipped(clip);
+	///
+	/// // Points on the clipping plane map to depth 0
+	/// let pt = Vec3(1.0, 2.0, -4.0);
+	/// assert_eq!(0.0, clip.signed_distance(pt));
+	/// assert!((oblique * pt.vec4(1.0)).hdiv().z.abs() < 1e-12);
+	/// ```
+	pub fn obliquely_clipped(self, plane: Plane<T>) -> Mat4<T> {
+		let sgn = |v: T| if v > T::zero() { T::one() } else if v < T::zero() { -T::one() } else { T::zero() };
+		let c = plane.vec4();
+		// The frustum corner opposite to the plane lies on the new far plane
+		let q = self.inverse() * Vec4 { x: sgn(c.x), y: sgn(c.y), z: T::one(), w: T::one() };
+		let c = c * (T::one() / c.dot(q));
+		Mat4 { a31: c.x, a32: c.y, a33: c.z, a34: c.w, ..self }
+	}
+}
+
+/// Projections with explicit conventions.
+///
+/// The `_rh` variants are right-handed with the camera looking down the negative Z axis (OpenGL, Vulkan),
+/// the `_lh` variants are left-handed with the camera looking down the positive Z axis (Direct3D).
+/// The [`DepthRange`](../projection/enum.DepthRange.html) selects the clip space depth range.
+///
+/// `frustum` and `ortho` without a suffix are right-handed with depth ranging from `0` to `1`, same as `perspective`.
+///
+/// ```
+/// # #[macro_use] extern crate cvmath;
+/// use cvmath::prelude::{Mat4, Vec3, Deg};
+/// use cvmath::projection::DepthRange;
+///
+/// # fn main() {
+/// let (near, far) = (0.5, 10.0);
+///
+/// // OpenGL convention
+/// let proj = Mat4::frustum_rh(-1.0, 3.0, -2.0, 2.0, near, far, DepthRange::NegOneToOne);
+/// assert_approx_eq!(Vec3(-1.0, -1.0, -1.0), proj.transform_point(Vec3(-1.0, -2.0, -near)));
+/// assert_approx_eq!(Vec3(1.0, 1.0, 1.0), proj.transform_point(Vec3(3.0, 2.0, -near) * (far / near)));
+///
+/// // Direct3D convention
+/// let proj = Mat4::ortho_lh(-1.0, 3.0, -2.0, 2.0, near, far, DepthRange::ZeroToOne);
+/// assert_approx_eq!(Vec3(-1.0, -1.0, 0.0), proj.transform_point(Vec3(-1.0, -2.0, near)));
+/// assert_approx_eq!(Vec3(1.0, 1.0, 1.0), proj.transform_point(Vec3(3.0, 2.0, far)));
+///
+/// // Symmetric frustums are perspective projections
+/// let proj = Mat4::perspective_rh(Deg(90.0), 2.0, near, far, DepthRange::ZeroToOne);
+/// assert_approx_eq!(Mat4::perspective(Deg(90.0), 2.0, near, far), proj);
+/// assert_approx_eq!(Mat4::frustum(-1.0, 1.0, -0.5, 0.5, near, far), proj);
+/// # }
+/// ```
+impl<T: Float> Mat4<T> {
+	/// Converts between right-handed and left-handed view space by flipping the Z axis.
+	fn flip_z(self) -> Mat4<T> {
+		Mat4 {
+			a13: -self.a13,
+			a23: -self.a23,
+			a33: -self.a33,
+			a43: -self.a43,
+			..self
+		}
+	}
+	/// Right-handed perspective projection.
+	///
+	/// The vertical field of view `fovy` is the full angle, `aspect` is the width divided by the height.
+	pub fn perspective_rh<A: Angle<T = T>>(fovy: A, aspect: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		let range = near - far;
+		match depth {
+			DepthRange::ZeroToOne => Mat4::perspective_base(fovy, aspect, far / range, near * far / range),
+			DepthRange::NegOneToOne => Mat4::perspective_base(fovy, aspect, (far + near) / range, (far + far) * near / range),
+		}
+	}
+	/// Left-handed perspective projection.
+	///
+	/// The vertical field of view `fovy` is the full angle, `aspect` is the width divided by the height.
+	pub fn perspective_lh<A: Angle<T = T>>(fovy: A, aspect: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		Mat4::perspective_rh(fovy, aspect, near, far, depth).flip_z()
+	}
+	/// Right-handed off-center perspective projection with depth ranging from `0` to `1`.
+	///
+	/// The extents `left`, `right`, `bottom` and `top` are measured on the near plane.
+	pub fn frustum(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T> {
+		Mat4::frustum_rh(left, right, bottom, top, near, far, DepthRange::ZeroToOne)
+	}
+	/// Right-handed off-center perspective projection.
+	///
+	/// The extents `left`, `right`, `bottom` and `top` are measured on the near plane.
+	pub fn frustum_rh(left: T, right: T, bottom: T, top: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		let two = T::one() + T::one();
+		let width = right - left;
+		let height = top - bottom;
+		let range = near - far;
+		let (a33, a34) = match depth {
+			DepthRange::ZeroToOne => (far / range, near * far / range),
+			DepthRange::NegOneToOne => ((far + near) / range, two * near * far / range),
+		};
+		Mat4 {
+			a11: two * near / width, a12: T::zero(),          a13: (right + left) / width,  a14: T::zero(),
+			a21: T::zero(),          a22: two * near / height, a23: (top + bottom) / height, a24: T::zero(),
+			a31: T::zero(),          a32: T::zero(),           a33,                          a34,
+			a41: T::zero(),          a42: T::zero(),           a43: -T::one(),               a44: T::zero(),
+		}
+	}
+	/// Left-handed off-center perspective projection.
+	///
+	/// The extents `left`, `right`, `bottom` and `top` are measured on the near plane.
+	pub fn frustum_lh(left: T, right: T, bottom: T, top: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		Mat4::frustum_rh(left, right, bottom, top, near, far, depth).flip_z()
+	}
+	/// Right-handed orthographic projection with depth ranging from `0` to `1`.
+	pub fn ortho(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T> {
+		Mat4::ortho_rh(left, right, bottom, top, near, far, DepthRange::ZeroToOne)
+	}
+	/// Right-handed orthographic projection.
+	pub fn ortho_rh(left: T, right: T, bottom: T, top: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		let two = T::one() + T::one();
+		let width = right - left;
+		let height = top - bottom;
+		let range = near - far;
+		let (a33, a34) = match depth {
+			DepthRange::ZeroToOne => (T::one() / range, near / range),
+			DepthRange::NegOneToOne => (two / range, (far + near) / range),
+		};
+		Mat4 {
+			a11: two / width, a12: T::zero(),     a13: T::zero(), a14: -(right + left) / width,
+			a21: T::zero(),   a22: two / height,  a23: T::zero(), a24: -(top + bottom) / height,
+			a31: T::zero(),   a32: T::zero(),     a33,            a34,
+			a41: T::zero(),   a42: T::zero(),     a43: T::zero(), a44: T::one(),
+		}
+	}
+	/// Left-handed orthographic projection.
+	pub fn ortho_lh(left: T, right: T, bottom: T, top: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+		Mat4::ortho_rh(left, right, bottom, top, near, far, depth).flip_z()
+	}
+}
+
+//----------------------------------------------------------------
+// Billboards
+
+/// Returns a unit vector perpendicular to the unit vector `v`.
+fn perpendicular<T: Float>(v: Vec3<T>) -> Vec3<T> {
+	// Cross with the world axis least aligned with the vector
+	let (ax, ay, az) = (v.x.abs(), v.y.abs(), v.z.abs());
+	let axis = if ax <= ay && ax <= az { Vec3::unit_x() } else if ay <= az { Vec3::unit_y() } else { Vec3::unit_z() };
+	v.cross(axis).norm()
+}
+
+/// Billboards.
+///
+/// The billboard matrices transform from model space to world space, the billboard faces its local +Z axis towards the camera
+/// with the local +Y axis as up. Scale and rotate the quad in model space before applying the billboard matrix.
+///
+/// ```
+/// use cvmath::prelude::{Mat4, Vec3};
+///
+/// let position = Vec3(1.0, 2.0, 3.0);
+/// let camera_pos = Vec3(1.0, 2.0, 13.0);
+/// let camera_up = Vec3(0.0, 1.0, 0.0);
+///
+/// // Facing the camera the billboard is untransformed
+/// let billboard = Mat4::billboard(position, camera_pos, camera_up);
+/// assert_eq!(Vec3(1.0, 3.0, 3.0), billboard.transform_point(Vec3(0.0, 1.0, 0.0)));
+///
+/// // Cylindrical billboards stay upright when the camera looks down on them from nearly overhead
+/// let camera_pos = Vec3(1.0, 12.0, 3.0 + 1e-3);
+/// let billboard = Mat4::billboard_cylindrical(position, camera_pos, Vec3(0.0, 1.0, 0.0));
+/// assert_eq!(Vec3(1.0, 3.0, 3.0), billboard.transform_point(Vec3(0.0, 1.0, 0.0)));
+///
+/// // Spherical billboards face the camera looking straight down the up vector
+/// let billboard = Mat4::billboard(position, Vec3(1.0, 12.0, 3.0), camera_up);
+/// assert_eq!(Vec3(1.0, 3.0, 3.0), billboard.transform_point(Vec3(0.0, 0.0, 1.0)));
+/// ```
+impl<T: Float> Mat4<T> {
+	fn billboard_base(position: Vec3<T>, x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Mat4<T> {
+		Mat4::compose(x.vec4(T::zero()), y.vec4(T::zero()), z.vec4(T::zero()), position.vec4(T::one()))
+	}
+	/// Spherical billboard rotating freely to face the camera.
+	///
+	/// The `camera_up` vector orients the billboard around its facing direction.
+	/// When the camera looks along the up vector an arbitrary but stable orientation is chosen.
+	/// When the camera is at the billboard position the billboard is not rotated.
+	pub fn billboard(position: Vec3<T>, camera_pos: Vec3<T>, camera_up: Vec3<T>) -> Mat4<T> {
+		let eps = T::cast_from(1e-12);
+		let to_camera = camera_pos - position;
+		if to_camera.len_sqr() <= eps {
+			return Mat4::billboard_base(position, Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z());
+		}
+		let z = to_camera.norm();
+		let x = camera_up.norm().cross(z);
+		let x = if x.len_sqr() > eps { x.norm() } else { perpendicular(z) };
+		let y = z.cross(x);
+		Mat4::billboard_base(position, x, y, z)
+	}
+	/// Cylindrical billboard rotating only around the `axis` to face the camera.
+	///
+	/// The local +Y axis of the billboard is aligned with the `axis`.
+	/// When the camera lies on the axis an arbitrary but stable orientation is chosen.
+	pub fn billboard_cylindrical(position: Vec3<T>, camera_pos: Vec3<T>, axis: Vec3<T>) -> Mat4<T> {
+		let eps = T::cast_from(1e-12);
+		let y = axis.norm();
+		let to_camera = camera_pos - position;
+		let z = to_camera - y * to_camera.dot(y);
+		let z = if z.len_sqr() > eps * to_camera.len_sqr() && z.len_sqr() > T::zero() { z.norm() } else { perpendicular(y) };
+		let x = y.cross(z);
+		Mat4::billboard_base(position, x, y, z)
+	}
+}
+
+//----------------------------------------------------------------
+// View matrices
+
+/// View matrices.
+///
+/// The view matrices transform from world space to view space, placing the camera at the origin.
+/// The right-handed variants look down the negative Z axis, the left-handed variants look down the positive Z axis.
+/// `look_at` and `look_to` without a suffix are right-handed, matching `perspective`.
+///
+/// When the `up` vector is parallel to the view direction an arbitrary but stable orientation is chosen.
+///
+/// ```
+/// # #[macro_use] extern crate cvmath;
+/// use cvmath::prelude::{Mat4, Vec3};
+///
+/// # fn main() {
+/// let eye = Vec3(1.0, 2.0, 3.0);
+/// let target = Vec3(1.0, 2.0, -7.0);
+/// let up = Vec3(0.0, 1.0, 0.0);
+///
+/// let view = Mat4::look_at(eye, target, up);
+/// assert_approx_eq!(Vec3(0.0, 0.0, 0.0), view.transform_point(eye));
+/// assert_approx_eq!(Vec3(0.0, 0.0, -10.0), view.transform_point(target));
+/// assert_approx_eq!(Vec3(0.0, 1.0, -10.0), view.transform_point(target + up));
+///
+/// let view = Mat4::look_at_lh(eye, target, up);
+/// assert_approx_eq!(Vec3(0.0, 0.0, 10.0), view.transform_point(target));
+/// assert_approx_eq!(Vec3(0.0, 1.0, 10.0), view.transform_point(target + up));
+///
+/// let view = Mat4::look_to(eye, Vec3(0.0, 0.0, -2.0), up);
+/// assert_approx_eq!(Mat4::look_at(eye, target, up), view);
+/// # }
+/// ```
+impl<T: Float> Mat4<T> {
+	fn view_base(eye: Vec3<T>, x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Mat4<T> {
+		Mat4 {
+			a11: x.x,       a12: x.y,       a13: x.z,       a14: -x.dot(eye),
+			a21: y.x,       a22: y.y,       a23: y.z,       a24: -y.dot(eye),
+			a31: z.x,       a32: z.y,       a33: z.z,       a34: -z.dot(eye),
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+	/// Right-handed view matrix looking from `eye` towards `target`.
+	pub fn look_at(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		Mat4::look_to_rh(eye, target - eye, up)
+	}
+	/// Right-handed view matrix looking from `eye` in the direction `dir`.
+	pub fn look_to(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		Mat4::look_to_rh(eye, dir, up)
+	}
+	/// Right-handed view matrix looking from `eye` towards `target`.
+	pub fn look_at_rh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		Mat4::look_to_rh(eye, target - eye, up)
+	}
+	/// Left-handed view matrix looking from `eye` towards `target`.
+	pub fn look_at_lh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		Mat4::look_to_lh(eye, target - eye, up)
+	}
+	/// Right-handed view matrix looking from `eye` in the direction `dir`.
+	pub fn look_to_rh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		let forward = dir.norm();
+		let x = forward.cross(up);
+		let x = if x.len_sqr() > T::cast_from(1e-12) * up.len_sqr() { x.norm() } else { perpendicular(forward) };
+		let y = x.cross(forward);
+		Mat4::view_base(eye, x, y, -forward)
+	}
+	/// Left-handed view matrix looking from `eye` in the direction `dir`.
+	pub fn look_to_lh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Mat4<T> {
+		let forward = dir.norm();
+		let x = up.cross(forward);
+		let x = if x.len_sqr() > T::cast_from(1e-12) * up.len_sqr() { x.norm() } else { perpendicular(forward) };
+		let y = forward.cross(x);
+		Mat4::view_base(eye, x, y, forward)
+	}
+}
+
+//----------------------------------------------------------------
+// Planar projection
+
+impl<T: Scalar> Mat4<T> {
+	/// Projects points onto the plane along the lines through the light, used for planar shadows.
+	///
+	/// The `light` is a position with `w = 1` for point lights or the direction towards the light with `w = 0` for directional lights.
+	/// The resulting points need a homogeneous divide.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Plane, Vec3, Vec4};
+	///
+	/// let ground = Plane::new(Vec3(0.0, 1.0, 0.0), 0.0);
+	///
+	/// // Point light above the origin
+	/// let shadow = Mat4::project_onto_plane(&ground, Vec4(0.0, 4.0, 0.0, 1.0));
+	/// assert_eq!(Vec3(2.0, 0.0, -4.0), shadow.transform_point(Vec3(1.0, 2.0, -2.0)));
+	///
+	/// // Directional light shining straight down
+	/// let shadow = Mat4::project_onto_plane(&ground, Vec4(0.0, 1.0, 0.0, 0.0));
+	/// assert_eq!(Vec3(1.0, 0.0, -2.0), shadow.transform_point(Vec3(1.0, 2.0, -2.0)));
+	/// ```
+	pub fn project_onto_plane(plane: &Plane<T>, light: Vec4<T>) -> Mat4<T> {
+		let p = plane.vec4();
+		let d = p.dot(light);
+		Mat4 {
+			a11: d - light.x * p.x, a12: -light.x * p.y,    a13: -light.x * p.z,    a14: -light.x * p.w,
+			a21: -light.y * p.x,    a22: d - light.y * p.y, a23: -light.y * p.z,    a24: -light.y * p.w,
+			a31: -light.z * p.x,    a32: -light.z * p.y,    a33: d - light.z * p.z, a34: -light.z * p.w,
+			a41: -light.w * p.x,    a42: -light.w * p.y,    a43: -light.w * p.z,    a44: d - light.w * p.w,
+		}
+	}
+}
+
+//----------------------------------------------------------------
+// Conversions
+
+impl<T> Mat4<T> {
+	/// Imports as row major.
+	pub fn from_row_major(mat: [[T; 4]; 4]) -> Mat4<T> where T: Copy {
+		Mat4 {
+			a11: mat[0][0], a12: mat[0][1], a13: mat[0][2], a14: mat[0][3],
+			a21: mat[1][0], a22: mat[1][1], a23: mat[1][2], a24: mat[1][3],
+			a31: mat[2][0], a32: mat[2][1], a33: mat[2][2], a34: mat[2][3],
+			a41: mat[3][0], a42: mat[3][1], a43: mat[3][2], a44: mat[3][3],
+		}
+	}
+	/// Imports as column major.
+	pub fn from_column_major(mat: [[T; 4]; 4]) -> Mat4<T> where T: Copy {
+		Mat4 {
+			a11: mat[0][0], a12: mat[1][0], a13: mat[2][0], a14: mat[3][0],
+			a21: mat[0][1], a22: mat[1][1], a23: mat[2][1], a24: mat[3][1],
+			a31: mat[0][2], a32: mat[1][2], a33: mat[2][2], a34: mat[3][2],
+			a41: mat[0][3], a42: mat[1][3], a43: mat[2][3], a44: mat[3][3],
+		}
+	}
+	/// Exports as row major.
+	pub fn into_row_major(self) -> [[T; 4]; 4] {
+		[
+			[self.a11, self.a12, self.a13, self.a14],
+			[self.a21, self.a22, self.a23, self.a24],
+			[self.a31, self.a32, self.a33, self.a34],
+			[self.a41, self.a42, self.a43, self.a44],
+		]
+	}
+	/// Exports as column major.
+	pub fn into_column_major(self) -> [[T; 4]; 4] {
+		[
+			[self.a11, self.a21, self.a31, self.a41],
+			[self.a12, self.a22, self.a32, self.a42],
+			[self.a13, self.a23, self.a33, self.a43],
+			[self.a14, self.a24, self.a34, self.a44],
+		]
+	}
+	/// Imports from a flat array in row major order.
+	pub fn from_rows_array(arr: [T; 16]) -> Mat4<T> where T: Copy {
+		Mat4 {
+			a11: arr[0], a12: arr[1], a13: arr[2], a14: arr[3],
+			a21: arr[4], a22: arr[5], a23: arr[6], a24: arr[7],
+			a31: arr[8], a32: arr[9], a33: arr[10], a34: arr[11],
+			a41: arr[12], a42: arr[13], a43: arr[14], a44: arr[15],
+		}
+	}
+	/// Imports from a flat array in column major order.
+	pub fn from_cols_array(arr: [T; 16]) -> Mat4<T> where T: Copy {
+		Mat4 {
+			a11: arr[0], a12: arr[4], a13: arr[8], a14: arr[12],
+			a21: arr[1], a22: arr[5], a23: arr[9], a24: arr[13],
+			a31: arr[2], a32: arr[6], a33: arr[10], a34: arr[14],
+			a41: arr[3], a42: arr[7], a43: arr[11], a44: arr[15],
+		}
+	}
+	/// Exports as a flat array in row major order.
+	pub fn to_rows_array(self) -> [T; 16] {
+		[self.a11, self.a12, self.a13, self.a14, self.a21, self.a22, self.a23, self.a24, self.a31, self.a32, self.a33, self.a34, self.a41, self.a42, self.a43, self.a44]
+	}
+	/// Exports as a flat array in column major order.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Vec3};
+	///
+	/// let mat = Mat4::translate(Vec3(1.0f32, 2.0, 3.0));
+	/// let cols = mat.to_cols_array();
+	/// assert_eq!([1.0, 2.0, 3.0, 1.0], cols[12..]);
+	/// assert_eq!(mat, Mat4::from_cols_array(cols));
+	///
+	/// let rows: &[f32; 16] = mat.as_ref();
+	/// assert_eq!(&mat.to_rows_array(), rows);
+	/// ```
+	pub fn to_cols_array(self) -> [T; 16] {
+		[self.a11, self.a21, self.a31, self.a41, self.a12, self.a22, self.a32, self.a42, self.a13, self.a23, self.a33, self.a43, self.a14, self.a24, self.a34, self.a44]
+	}
+	/// Constructs the matrix from row vectors.
+	pub fn from_rows(row0: Vec4<T>, row1: Vec4<T>, row2: Vec4<T>, row3: Vec4<T>) -> Mat4<T> where T: Copy {
+		Mat4::from_row_major([row0.into(), row1.into(), row2.into(), row3.into()])
+	}
+	/// Constructs the matrix from column vectors.
+	pub fn from_cols(col0: Vec4<T>, col1: Vec4<T>, col2: Vec4<T>, col3: Vec4<T>) -> Mat4<T> where T: Copy {
+		Mat4::from_column_major([col0.into(), col1.into(), col2.into(), col3.into()])
+	}
+	/// Returns the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn row(self, i: usize) -> Vec4<T> where T: Copy {
+		self.into_row_major()[i].into()
+	}
+	/// Returns the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn col(self, j: usize) -> Vec4<T> where T: Copy {
+		self.into_column_major()[j].into()
+	}
+	/// Sets the row at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_row(&mut self, i: usize, row: Vec4<T>) where T: Copy {
+		let mut rows = self.into_row_major();
+		rows[i] = row.into();
+		*self = Mat4::from_row_major(rows);
+	}
+	/// Sets the column at the index.
+	///
+	/// Panics if the index is out of bounds.
+	pub fn set_col(&mut self, j: usize, col: Vec4<T>) where T: Copy {
+		let mut cols = self.into_column_major();
+		cols[j] = col.into();
+		*self = Mat4::from_column_major(cols);
+	}
+}
+
+/// Views the matrix as a flat array in its memory layout, row major unless the `column-major` feature is enabled.
+impl<T> AsRef<[T; 16]> for Mat4<T> {
+	fn as_ref(&self) -> &[T; 16] {
+		unsafe { mem::transmute(self) }
+	}
+}
+impl<T> AsMut<[T; 16]> for Mat4<T> {
+	fn as_mut(&mut self) -> &mut [T; 16] {
+		unsafe { mem::transmute(self) }
+	}
+}
+
+impl<T: Zero + One> From<Mat3<T>> for Mat4<T> {
+	fn from(mat: Mat3<T>) -> Mat4<T> {
+		Mat4 {
+			a11: mat.a11,   a12: mat.a12,   a13: mat.a13,   a14: T::zero(),
+			a21: mat.a21,   a22: mat.a22,   a23: mat.a23,   a24: T::zero(),
+			a31: mat.a31,   a32: mat.a32,   a33: mat.a33,   a34: T::zero(),
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+}
+impl<T: Zero + One> From<Affine3<T>> for Mat4<T> {
+	fn from(mat: Affine3<T>) -> Mat4<T> {
+		Mat4 {
+			a11: mat.a11,   a12: mat.a12,   a13: mat.a13,   a14: mat.a14,
+			a21: mat.a21,   a22: mat.a22,   a23: mat.a23,   a24: mat.a24,
+			a31: mat.a31,   a32: mat.a32,   a33: mat.a33,   a34: mat.a34,
+			a41: T::zero(), a42: T::zero(), a43: T::zero(), a44: T::one(),
+		}
+	}
+}
+
+//----------------------------------------------------------------
+// Decomposition
+
+impl<T> Mat4<T> {
+	pub fn compose(x: Vec4<T>, y: Vec4<T>, z: Vec4<T>, w: Vec4<T>) -> Mat4<T> {
+		Mat4 {
+			a11: x.x, a12: y.x, a13: z.x, a14: w.x,
+			a21: x.y, a22: y.y, a23: z.y, a24: w.y,
+			a31: x.z, a32: y.z, a33: z.z, a34: w.z,
+			a41: x.w, a42: y.w, a43: z.w, a44: w.w,
+		}
+	}
+	/// Gets the transformed X unit vector.
+	pub fn x(self) -> Vec4<T> {
+		Vec4 {
+			x: self.a11,
+			y: self.a21,
+			z: self.a31,
+			w: self.a41,
+		}
+	}
+	/// Gets the transformed Y unit vector.
+	pub fn y(self) -> Vec4<T> {
+		Vec4 {
+			x: self.a12,
+			y: self.a22,
+			z: self.a32,
+			w: self.a42,
+		}
+	}
+	/// Gets the transformed Z unit vector.
+	pub fn z(self) -> Vec4<T> {
+		Vec4 {
+			x: self.a13,
+			y: self.a23,
+			z: self.a33,
+			w: self.a43,
+		}
+	}
+	/// Gets the fourth column.
+	pub fn w(self) -> Vec4<T> {
+		Vec4 {
+			x: self.a14,
+			y: self.a24,
+			z: self.a34,
+			w: self.a44,
+		}
+	}
+}
+
+//----------------------------------------------------------------
+// Operations
+
+impl<T: Scalar> Mat4<T> {
+	pub fn det(&self) -> T {
+		let s0 = self.a11 * self.a22 - self.a21 * self.a12;
+		let s1 = self.a11 * self.a23 - self.a21 * self.a13;
+		let s2 = self.a11 * self.a24 - self.a21 * self.a14;
+		let s3 = self.a12 * self.a23 - self.a22 * self.a13;
+		let s4 = self.a12 * self.a24 - self.a22 * self.a14;
+		let s5 = self.a13 * self.a24 - self.a23 * self.a14;
+		let c5 = self.a33 * self.a44 - self.a43 * self.a34;
+		let c4 = self.a32 * self.a44 - self.a42 * self.a34;
+		let c3 = self.a32 * self.a43 - self.a42 * self.a33;
+		let c2 = self.a31 * self.a44 - self.a41 * self.a34;
+		let c1 = self.a31 * self.a43 - self.a41 * self.a33;
+		let c0 = self.a31 * self.a42 - self.a41 * self.a32;
+		s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+	}
+	pub fn inverse(&self) -> Mat4<T> where T: Float {
+		let det = self.det();
+		if det != T::zero() {
+			self.adjugate() * (T::one() / det)
+		}
+		else { *self }
+	}
+	/// Calculates the inverse matrix.
+	///
+	/// Returns `Err(MathError::Singular)` if the determinant is zero or not finite.
+	pub fn try_inverse(&self) -> Result<Mat4<T>, MathError> where T: Float {
+		let det = self.det();
+		if det == T::zero() || !det.is_finite() {
+			return Err(MathError::Singular);
+		}
+		Ok(self.inverse())
+	}
+	pub fn transpose(&self) -> Mat4<T> {
+		Mat4 {
+			a11: self.a11, a12: self.a21, a13: self.a31, a14: self.a41,
+			a21: self.a12, a22: self.a22, a23: self.a32, a24: self.a42,
+			a31: self.a13, a32: self.a23, a33: self.a33, a34: self.a43,
+			a41: self.a14, a42: self.a24, a43: self.a34, a44: self.a44,
+		}
+	}
+	pub fn adjugate(&self) -> Mat4<T> {
+		// Shared 2x2 sub-determinants of the upper and lower two rows
+		let s0 = self.a11 * self.a22 - self.a21 * self.a12;
+		let s1 = self.a11 * self.a23 - self.a21 * self.a13;
+		let s2 = self.a11 * self.a24 - self.a21 * self.a14;
+		let s3 = self.a12 * self.a23 - self.a22 * self.a13;
+		let s4 = self.a12 * self.a24 - self.a22 * self.a14;
+		let s5 = self.a13 * self.a24 - self.a23 * self.a14;
+		let c5 = self.a33 * self.a44 - self.a43 * self.a34;
+		let c4 = self.a32 * self.a44 - self.a42 * self.a34;
+		let c3 = self.a32 * self.a43 - self.a42 * self.a33;
+		let c2 = self.a31 * self.a44 - self.a41 * self.a34;
+		let c1 = self.a31 * self.a43 - self.a41 * self.a33;
+		let c0 = self.a31 * self.a42 - self.a41 * self.a32;
+		Mat4 {
+			a11: self.a22 * c5 - self.a23 * c4 + self.a24 * c3,
+			a12: self.a13 * c4 - self.a12 * c5 - self.a14 * c3,
+			a13: self.a42 * s5 - self.a43 * s4 + self.a44 * s3,
+			a14: self.a33 * s4 - self.a32 * s5 - self.a34 * s3,
+
+			a21: self.a23 * c2 - self.a21 * c5 - self.a24 * c1,
+			a22: self.a11 * c5 - self.a13 * c2 + self.a14 * c1,
+			a23: self.a43 * s2 - self.a41 * s5 - self.a44 * s1,
+			a24: self.a31 * s5 - self.a33 * s2 + self.a34 * s1,
+
+			a31: self.a21 * c4 - self.a22 * c2 + self.a24 * c0,
+			a32: self.a12 * c2 - self.a11 * c4 - self.a14 * c0,
+			a33: self.a41 * s4 - self.a42 * s2 + self.a44 * s0,
+			a34: self.a32 * s2 - self.a31 * s4 - self.a34 * s0,
+
+			a41: self.a22 * c1 - self.a21 * c3 - self.a23 * c0,
+			a42: self.a11 * c3 - self.a12 * c1 + self.a13 * c0,
+			a43: self.a42 * s1 - self.a41 * s3 - self.a43 * s0,
+			a44: self.a31 * s3 - self.a32 * s1 + self.a33 * s0,
+		}
+	}
+	/// Transforms the point including the homogeneous divide.
+	pub fn transform_point(&self, pt: Vec3<T>) -> Vec3<T> {
+		(*self * pt.vec4(T::one())).hdiv()
+	}
+	/// Calculates the normal matrix, the inverse transpose of the upper left 3x3 matrix.
+	///
+	/// See [`Mat3::inverse_transpose`](struct.Mat3.html#method.inverse_transpose).
+	pub fn normal_matrix(&self) -> Mat3<T> where T: Float {
+		let mat = Mat3 {
+			a11: self.a11, a12: self.a12, a13: self.a13,
+			a21: self.a21, a22: self.a22, a23: self.a23,
+			a31: self.a31, a32: self.a32, a33: self.a33,
+		};
+		mat.inverse_transpose()
+	}
+}
+
+//----------------------------------------------------------------
+// Operators
+
+impl<T: Copy + ops::Mul<Output = T>> ops::Mul<T> for Mat4<T> {
+	type Output = Mat4<T>;
+	fn mul(self, rhs: T) -> Mat4<T> {
+		Mat4 {
+			a11: self.a11 * rhs, a12: self.a12 * rhs, a13: self.a13 * rhs, a14: self.a14 * rhs,
+			a21: self.a21 * rhs, a22: self.a22 * rhs, a23: self.a23 * rhs, a24: self.a24 * rhs,
+			a31: self.a31 * rhs, a32: self.a32 * rhs, a33: self.a33 * rhs, a34: self.a34 * rhs,
+			a41: self.a41 * rhs, a42: self.a42 * rhs, a43: self.a43 * rhs, a44: self.a44 * rhs,
+		}
+	}
+}
+impl<T: Copy + ops::MulAssign> ops::MulAssign<T> for Mat4<T> {
+	fn mul_assign(&mut self, rhs: T) {
+		self.a11 *= rhs; self.a12 *= rhs; self.a13 *= rhs; self.a14 *= rhs;
+		self.a21 *= rhs; self.a22 *= rhs; self.a23 *= rhs; self.a24 *= rhs;
+		self.a31 *= rhs; self.a32 *= rhs; self.a33 *= rhs; self.a34 *= rhs;
+		self.a41 *= rhs; self.a42 *= rhs; self.a43 *= rhs; self.a44 *= rhs;
+	}
+}
+
+impl<T: Copy + ops::Add<Output = T> + ops::Mul<Output = T>> ops::Mul<Vec4<T>> for Mat4<T> {
+	type Output = Vec4<T>;
+	fn mul(self, rhs: Vec4<T>) -> Vec4<T> {
+		Vec4 {
+			x: self.a11 * rhs.x + self.a12 * rhs.y + self.a13 * rhs.z + self.a14 * rhs.w,
+			y: self.a21 * rhs.x + self.a22 * rhs.y + self.a23 * rhs.z + self.a24 * rhs.w,
+			z: self.a31 * rhs.x + self.a32 * rhs.y + self.a33 * rhs.z + self.a34 * rhs.w,
+			w: self.a41 * rhs.x + self.a42 * rhs.y + self.a43 * rhs.z + self.a44 * rhs.w,
+		}
+	}
+}
+
+impl<T: Copy + ops::Add<Output = T> + ops::Mul<Output = T>> ops::Mul<Mat4<T>> for Mat4<T> {
+	type Output = Mat4<T>;
+	fn mul(self, rhs: Mat4<T>) -> Mat4<T> {
+		Mat4 {
+			a11: self.a11 * rhs.a11 + self.a12 * rhs.a21 + self.a13 * rhs.a31 + self.a14 * rhs.a41,
+			a12: self.a11 * rhs.a12 + self.a12 * rhs.a22 + self.a13 * rhs.a32 + self.a14 * rhs.a42,
+			a13: self.a11 * rhs.a13 + self.a12 * rhs.a23 + self.a13 * rhs.a33 + self.a14 * rhs.a43,
+			a14: self.a11 * rhs.a14 + self.a12 * rhs.a24 + self.a13 * rhs.a34 + self.a14 * rhs.a44,
+
+			a21: self.a21 * rhs.a11 + self.a22 * rhs.a21 + self.a23 * rhs.a31 + self.a24 * rhs.a41,
+			a22: self.a21 * rhs.a12 + self.a22 * rhs.a22 + self.a23 * rhs.a32 + self.a24 * rhs.a42,
+			a23: self.a21 * rhs.a13 + self.a22 * rhs.a23 + self.a23 * rhs.a33 + self.a24 * rhs.a43,
+			a24: self.a21 * rhs.a14 + self.a22 * rhs.a24 + self.a23 * rhs.a34 + self.a24 * rhs.a44,
+
+			a31: self.a31 * rhs.a11 + self.a32 * rhs.a21 + self.a33 * rhs.a31 + self.a34 * rhs.a41,
+			a32: self.a31 * rhs.a12 + self.a32 * rhs.a22 + self.a33 * rhs.a32 + self.a34 * rhs.a42,
+			a33: self.a31 * rhs.a13 + self.a32 * rhs.a23 + self.a33 * rhs.a33 + self.a34 * rhs.a43,
+			a34: self.a31 * rhs.a14 + self.a32 * rhs.a24 + self.a33 * rhs.a34 + self.a34 * rhs.a44,
+
+			a41: self.a41 * rhs.a11 + self.a42 * rhs.a21 + self.a43 * rhs.a31 + self.a44 * rhs.a41,
+			a42: self.a41 * rhs.a12 + self.a42 * rhs.a22 + self.a43 * rhs.a32 + self.a44 * rhs.a42,
+			a43: self.a41 * rhs.a13 + self.a42 * rhs.a23 + self.a43 * rhs.a33 + self.a44 * rhs.a43,
+			a44: self.a41 * rhs.a14 + self.a42 * rhs.a24 + self.a43 * rhs.a34 + self.a44 * rhs.a44,
+		}
+	}
+}
+impl<T: Copy + ops::Add<Output = T> + ops::Mul<Output = T>> ops::MulAssign<Mat4<T>> for Mat4<T> {
+	fn mul_assign(&mut self, rhs: Mat4<T>) {
+		*self = *self * rhs;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn inverse() {
+		let m = Mat4::new(
+			2.0, 0.0, 1.0, 3.0,
+			1.0, 1.0, 0.0, -1.0,
+			0.0, 4.0, 1.0, 2.0,
+			1.0, 0.0, 2.0, 1.0,
+		);
+		let p = m * m.inverse();
+		let i = Mat4::<f64>::identity();
+		for (row, expected) in p.into_row_major().iter().zip(i.into_row_major().iter()) {
+			for (a, b) in row.iter().zip(expected.iter()) {
+				assert!((a - b).abs() < 1e-12);
+			}
+		}
+		assert_eq!(-39.0, m.det());
+	}
+
+	#[test]
+	fn projection_conventions() {
+		use angle::Deg;
+		let (near, far) = (0.25, 50.0);
+		let depth = |proj: Mat4<f64>, z: f64| proj.transform_point(Vec3(0.0, 0.0, z)).z;
+		let cases = [
+			(Mat4::perspective_rh(Deg(60.0), 1.5, near, far, DepthRange::ZeroToOne), -1.0, 0.0),
+			(Mat4::perspective_rh(Deg(60.0), 1.5, near, far, DepthRange::NegOneToOne), -1.0, -1.0),
+			(Mat4::perspective_lh(Deg(60.0), 1.5, near, far, DepthRange::ZeroToOne), 1.0, 0.0),
+			(Mat4::perspective_lh(Deg(60.0), 1.5, near, far, DepthRange::NegOneToOne), 1.0, -1.0),
+			(Mat4::ortho_rh(-1.0, 1.0, -1.0, 1.0, near, far, DepthRange::NegOneToOne), -1.0, -1.0),
+			(Mat4::ortho_lh(-1.0, 1.0, -1.0, 1.0, near, far, DepthRange::NegOneToOne), 1.0, -1.0),
+			(Mat4::frustum_lh(-1.0, 1.0, -1.0, 1.0, near, far, DepthRange::ZeroToOne), 1.0, 0.0),
+		];
+		for &(proj, forward, min_depth) in cases.iter() {
+			assert!((depth(proj, forward * near) - min_depth).abs() < 1e-12);
+			assert!((depth(proj, forward * far) - 1.0).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn billboard_overhead() {
+		// The camera lies exactly on the axis, the facing direction falls back to a stable perpendicular
+		let (position, camera_pos, axis) = (Vec3(1.0, 2.0, 3.0), Vec3(1.0, 12.0, 3.0), Vec3(0.0, 1.0, 0.0));
+		let billboard = Mat4::billboard_cylindrical(position, camera_pos, axis);
+		assert_eq!(billboard, Mat4::billboard_cylindrical(position, camera_pos, axis));
+		assert_eq!(Vec3(1.0, 3.0, 3.0), billboard.transform_point(Vec3(0.0, 1.0, 0.0)));
+		let (x, y, z) = (billboard.col(0).xyz(), billboard.col(1).xyz(), billboard.col(2).xyz());
+		assert_eq!(0.0, x.dot(axis));
+		assert_eq!(0.0, z.dot(axis));
+		assert_eq!(z, x.cross(y));
+		assert_eq!(1.0, x.len() * z.len());
+	}
+}
@@ -0,0 +1,304 @@
+/*!
+Matrix with const generic dimensions.
+*/
+
+use std::{array, fmt, ops};
+
+use num::{Scalar, Zero, One, Float};
+use vecn::VecN;
+use error::MathError;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::{Mat2, Mat3, Mat4};
+use super::decomp::{lu, forward_substitute, back_substitute};
+
+/// Matrix with `R` rows and `C` columns.
+///
+/// Stored in row-major order, `Mat([[1, 2, 3], [4, 5, 6]])` has two rows and three columns.
+/// Covers the odd shapes the fixed size matrices don't, such as Jacobians and camera projection matrices.
+///
+/// ```
+/// use cvmath::prelude::{Mat, VecN};
+///
+/// // Projects homogeneous points with a 3x4 camera matrix
+/// let camera = Mat([
+///     [2.0, 0.0, 1.0, 0.0],
+///     [0.0, 2.0, 1.0, 0.0],
+///     [0.0, 0.0, 1.0, 0.0],
+/// ]);
+/// let p = camera * VecN([1.0, 2.0, 4.0, 1.0]);
+/// assert_eq!(VecN([6.0, 8.0, 4.0]), p);
+///
+/// let a = Mat([[4.0, 1.0], [2.0, 3.0]]);
+/// assert_eq!(Some(VecN([1.0, 1.0])), a.solve(VecN([5.0, 5.0])));
+/// assert_eq!(Mat([[4.0, 2.0], [1.0, 3.0]]), a.transpose());
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Mat<T, const R: usize, const C: usize>(pub [[T; C]; R]);
+
+//----------------------------------------------------------------
+// Constructors
+
+impl<T, const R: usize, const C: usize> Mat<T, R, C> {
+	/// Constructs a new matrix from rows.
+	pub fn new(rows: [[T; C]; R]) -> Mat<T, R, C> {
+		Mat(rows)
+	}
+	/// Constructs a matrix from row vectors.
+	pub fn from_rows(rows: [VecN<T, C>; R]) -> Mat<T, R, C> {
+		Mat(rows.map(|row| row.0))
+	}
+	/// Constructs a matrix from column vectors.
+	pub fn from_cols(cols: [VecN<T, R>; C]) -> Mat<T, R, C> where T: Copy {
+		Mat(array::from_fn(|i| array::from_fn(|j| cols[j][i])))
+	}
+	/// Zero matrix.
+	pub fn zero() -> Mat<T, R, C> where T: Zero {
+		Mat(array::from_fn(|_| array::from_fn(|_| T::zero())))
+	}
+	/// Matrix with ones on the diagonal and zeros elsewhere.
+	pub fn identity() -> Mat<T, R, C> where T: Zero + One {
+		Mat(array::from_fn(|i| array::from_fn(|j| if i == j { T::one() } else { T::zero() })))
+	}
+}
+
+impl<T: Zero, const R: usize, const C: usize> Default for Mat<T, R, C> {
+	fn default() -> Mat<T, R, C> {
+		Mat::zero()
+	}
+}
+
+//----------------------------------------------------------------
+// Operations
+
+impl<T: Copy, const R: usize, const C: usize> Mat<T, R, C> {
+	/// Returns the row.
+	pub fn row(&self, i: usize) -> VecN<T, C> {
+		VecN(self.0[i])
+	}
+	/// Returns the column.
+	pub fn col(&self, j: usize) -> VecN<T, R> {
+		VecN(array::from_fn(|i| self.0[i][j]))
+	}
+	/// Sets the row.
+	pub fn set_row(&mut self, i: usize, row: VecN<T, C>) {
+		self.0[i] = row.0;
+	}
+	/// Sets the column.
+	pub fn set_col(&mut self, j: usize, col: VecN<T, R>) {
+		for i in 0..R {
+			self.0[i][j] = col[i];
+		}
+	}
+	/// Transposes the matrix.
+	pub fn transpose(&self) -> Mat<T, C, R> {
+		Mat(array::from_fn(|j| array::from_fn(|i| self.0[i][j])))
+	}
+	/// Maps a callable over the elements.
+	pub fn map<U, F: FnMut(T) -> U>(&self, mut f: F) -> Mat<U, R, C> {
+		Mat(self.0.map(|row| row.map(&mut f)))
+	}
+}
+
+impl<T: Float, const N: usize> Mat<T, N, N> {
+	/// Solves the linear system `self * x == b` for `x` using the LU decomposition with partial pivoting.
+	///
+	/// Returns `None` if the matrix is singular.
+	pub fn solve(&self, b: VecN<T, N>) -> Option<VecN<T, N>> {
+		let lu = lu(self.0);
+		let pb = Mat(lu.p) * b;
+		let y = forward_substitute(&lu.l, pb.0);
+		back_substitute(&lu.u, y).map(VecN)
+	}
+	/// Calculates the determinant.
+	pub fn det(&self) -> T {
+		let lu = lu(self.0);
+		// Parity of the permutation from its cycles
+		let mut sign = T::one();
+		let mut visited = [false; N];
+		for start in 0..N {
+			let mut len = 0;
+			let mut i = start;
+			while !visited[i] {
+				visited[i] = true;
+				i = lu.p[i].iter().position(|&v| v != T::zero()).unwrap_or(i);
+				len += 1;
+			}
+			if len > 0 && len % 2 == 0 {
+				sign = -sign;
+			}
+		}
+		(0..N).fold(sign, |acc, i| acc * lu.u[i][i])
+	}
+	/// Calculates the inverse by solving for the columns of the identity.
+	///
+	/// Returns `None` if the matrix is singular.
+	pub fn inverse(&self) -> Option<Mat<T, N, N>> {
+		let mut cols = [VecN([T::zero(); N]); N];
+		for (j, col) in cols.iter_mut().enumerate() {
+			*col = self.solve(VecN::unit(j))?;
+		}
+		Some(Mat::from_cols(cols))
+	}
+	/// Calculates the inverse.
+	///
+	/// Returns `Err(MathError::Singular)` if the matrix is singular.
+	pub fn try_inverse(&self) -> Result<Mat<T, N, N>, MathError> {
+		self.inverse().ok_or(MathError::Singular)
+	}
+}
+
+impl<T: Float, const R: usize, const C: usize> Mat<T, R, C> {
+	/// Solves the overdetermined system `self * x == b` in the least squares sense.
+	///
+	/// Solves the normal equations `transpose(self) * self * x == transpose(self) * b`.
+	/// Returns `None` if the columns are linearly dependent.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat, VecN};
+	///
+	/// // Fits a line through three points
+	/// let a = Mat([[0.0, 1.0], [1.0, 1.0], [2.0, 1.0]]);
+	/// let x = a.solve_least_squares(VecN([1.0, 2.0, 4.0])).unwrap();
+	/// assert!(x.dist(VecN([1.5, 5.0 / 6.0])) < 1e-12);
+	/// ```
+	pub fn solve_least_squares(&self, b: VecN<T, R>) -> Option<VecN<T, C>> {
+		let at = self.transpose();
+		(at * *self).solve(at * b)
+	}
+}
+
+//----------------------------------------------------------------
+// Conversions
+
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Mat<T, R, C> {
+	fn from(rows: [[T; C]; R]) -> Mat<T, R, C> {
+		Mat(rows)
+	}
+}
+impl<T, const R: usize, const C: usize> From<Mat<T, R, C>> for [[T; C]; R] {
+	fn from(mat: Mat<T, R, C>) -> [[T; C]; R] {
+		mat.0
+	}
+}
+impl<T, const R: usize, const C: usize> ops::Index<(usize, usize)> for Mat<T, R, C> {
+	type Output = T;
+	fn index(&self, (i, j): (usize, usize)) -> &T {
+		&self.0[i][j]
+	}
+}
+impl<T, const R: usize, const C: usize> ops::IndexMut<(usize, usize)> for Mat<T, R, C> {
+	fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+		&mut self.0[i][j]
+	}
+}
+
+macro_rules! convert {
+	($mat:ident $N:tt) => {
+		impl<T> From<$mat<T>> for Mat<T, $N, $N> {
+			fn from(mat: $mat<T>) -> Mat<T, $N, $N> {
+				Mat(mat.into_row_major())
+			}
+		}
+		impl<T: Copy> From<Mat<T, $N, $N>> for $mat<T> {
+			fn from(mat: Mat<T, $N, $N>) -> $mat<T> {
+				$mat::from_row_major(mat.0)
+			}
+		}
+	};
+}
+
+convert!(Mat2 2);
+convert!(Mat3 3);
+convert!(Mat4 4);
+
+//----------------------------------------------------------------
+// Formatting
+
+impl<T: fmt::Debug, const R: usize, const C: usize> fmt::Debug for Mat<T, R, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if f.alternate() {
+			let cells: Vec<_> = self.0.iter().flatten().map(|x| super::pretty::debug_cell(f, x)).collect();
+			super::pretty::write_rows(f, Some("Mat"), &cells, C)
+		}
+		else {
+			f.debug_tuple("Mat").field(&self.0).finish()
+		}
+	}
+}
+impl<T: fmt::Display, const R: usize, const C: usize> fmt::Display for Mat<T, R, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let cells: Vec<_> = self.0.iter().flatten().map(|x| super::pretty::display_cell(f, x)).collect();
+		super::pretty::write_rows(f, None, &cells, C)
+	}
+}
+
+//----------------------------------------------------------------
+// Operators
+
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> ops::Mul<Mat<T, C, K>> for Mat<T, R, C> {
+	type Output = Mat<T, R, K>;
+	fn mul(self, rhs: Mat<T, C, K>) -> Mat<T, R, K> {
+		Mat(array::from_fn(|i| array::from_fn(|j| (0..C).fold(T::zero(), |acc, k| acc + self.0[i][k] * rhs.0[k][j]))))
+	}
+}
+impl<T: Scalar, const R: usize, const C: usize> ops::Mul<VecN<T, C>> for Mat<T, R, C> {
+	type Output = VecN<T, R>;
+	fn mul(self, rhs: VecN<T, C>) -> VecN<T, R> {
+		VecN(array::from_fn(|i| self.row(i).dot(rhs)))
+	}
+}
+impl<T: Scalar, const R: usize, const C: usize> ops::Mul<T> for Mat<T, R, C> {
+	type Output = Mat<T, R, C>;
+	fn mul(self, rhs: T) -> Mat<T, R, C> {
+		self.map(|a| a * rhs)
+	}
+}
+impl<T: Scalar, const R: usize, const C: usize> ops::Add for Mat<T, R, C> {
+	type Output = Mat<T, R, C>;
+	fn add(self, rhs: Mat<T, R, C>) -> Mat<T, R, C> {
+		Mat(array::from_fn(|i| array::from_fn(|j| self.0[i][j] + rhs.0[i][j])))
+	}
+}
+impl<T: Scalar, const R: usize, const C: usize> ops::Sub for Mat<T, R, C> {
+	type Output = Mat<T, R, C>;
+	fn sub(self, rhs: Mat<T, R, C>) -> Mat<T, R, C> {
+		Mat(array::from_fn(|i| array::from_fn(|j| self.0[i][j] - rhs.0[i][j])))
+	}
+}
+impl<T: Scalar, const R: usize, const C: usize> ops::Neg for Mat<T, R, C> {
+	type Output = Mat<T, R, C>;
+	fn neg(self) -> Mat<T, R, C> {
+		self.map(|a| -a)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_mat3() {
+		let m = Mat3::<f64>::new(
+			2.0, -1.0, 0.5,
+			1.0, 3.0, -2.0,
+			0.0, 4.0, 1.0,
+		);
+		let n = Mat::from(m);
+		assert!((n.det() - m.det()).abs() < 1e-12);
+		let inv = Mat3::from(n.inverse().unwrap());
+		for (a, b) in inv.into_row_major().iter().flatten().zip(m.inverse().into_row_major().iter().flatten()) {
+			assert!((a - b).abs() < 1e-12f64);
+		}
+		assert_eq!(Mat::from(m * m), n * n);
+		assert_eq!(Mat::from(m.transpose()), n.transpose());
+
+		// Odd permutation of the rows flips the sign
+		let swapped = Mat([[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+		assert_eq!(-2.0, swapped.det());
+		assert_eq!(None, Mat([[1.0, 2.0], [2.0, 4.0]]).solve(VecN([1.0, 1.0])));
+	}
+}
@@ -2,18 +2,35 @@
 Transformation matrices.
 */
 
+#[macro_use]
+mod pretty;
+
 mod affine2;
 mod mat2;
 
 mod affine3;
 mod mat3;
 
+mod mat4;
+
 mod transform;
 
+mod decomp;
+
+mod lie;
+
+mod matn;
+
 pub use self::affine2::Affine2;
 pub use self::mat2::Mat2;
 
 pub use self::affine3::Affine3;
 pub use self::mat3::Mat3;
 
+pub use self::mat4::Mat4;
+
 pub use self::transform::{Transform2, Transform3};
+
+pub use self::decomp::{Lu, Qr, Svd, Trs};
+
+pub use self::matn::Mat;
@@ -0,0 +1,106 @@
+/*!
+Matrix formatting.
+
+The alternate flag prints one row per line with the columns aligned, the precision is applied to every element:
+
+```
+use cvmath::prelude::Mat2;
+
+let mat = Mat2::new(1.0, -0.5, 12.25, 2.0);
+assert_eq!(format!("{:#.2}", mat), "[ 1.00, -0.50]\n[12.25,  2.00]");
+assert_eq!(format!("{:#?}", mat), "Mat2 [\n    [  1.0, -0.5],\n    [12.25,  2.0],\n]");
+assert_eq!(format!("{}", mat), "[[1, -0.5], [12.25, 2]]");
+```
+*/
+
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// Formats the element with the precision of the formatter.
+pub(super) fn debug_cell<T: fmt::Debug>(f: &fmt::Formatter, x: &T) -> String {
+	match f.precision() {
+		Some(precision) => format!("{:.*?}", precision, x),
+		None => format!("{:?}", x),
+	}
+}
+/// Formats the element with the precision of the formatter.
+pub(super) fn display_cell<T: fmt::Display>(f: &fmt::Formatter, x: &T) -> String {
+	match f.precision() {
+		Some(precision) => format!("{:.*}", precision, x),
+		None => format!("{}", x),
+	}
+}
+
+/// Writes the row-major cells.
+///
+/// Prints `[[a, b], [c, d]]` on a single line, or one right-aligned row per line in alternate mode.
+/// Debug output is prefixed by the type name and indented like the standard library's pretty printing.
+pub(super) fn write_rows(f: &mut fmt::Formatter, name: Option<&str>, cells: &[String], cols: usize) -> fmt::Result {
+	if !f.alternate() {
+		f.write_str("[")?;
+		for (i, row) in cells.chunks(cols).enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "[{}]", row.join(", "))?;
+		}
+		return f.write_str("]");
+	}
+	let mut widths = Vec::with_capacity(cols);
+	for j in 0..cols {
+		widths.push(cells.iter().skip(j).step_by(cols).map(|cell| cell.chars().count()).max().unwrap_or(0));
+	}
+	if let Some(name) = name {
+		writeln!(f, "{} [", name)?;
+	}
+	for (i, row) in cells.chunks(cols).enumerate() {
+		if name.is_some() {
+			f.write_str("    ")?;
+		}
+		else if i > 0 {
+			f.write_str("\n")?;
+		}
+		f.write_str("[")?;
+		for (j, cell) in row.iter().enumerate() {
+			if j > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{:>width$}", cell, width = widths[j])?;
+		}
+		f.write_str("]")?;
+		if name.is_some() {
+			f.write_str(",\n")?;
+		}
+	}
+	if name.is_some() {
+		f.write_str("]")?;
+	}
+	Ok(())
+}
+
+/// Implements Debug and Display for the fixed size matrices.
+///
+/// Without the alternate flag Debug prints the fields like the derived implementation.
+macro_rules! pretty {
+	($ty:ident $cols:literal { $($field:ident),+ }) => {
+		impl<T: ::std::fmt::Debug> ::std::fmt::Debug for $ty<T> {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+				if f.alternate() {
+					let cells = [$(super::pretty::debug_cell(f, &self.$field)),+];
+					super::pretty::write_rows(f, Some(stringify!($ty)), &cells, $cols)
+				}
+				else {
+					f.debug_struct(stringify!($ty))$(.field(stringify!($field), &self.$field))+.finish()
+				}
+			}
+		}
+		impl<T: ::std::fmt::Display> ::std::fmt::Display for $ty<T> {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+				let cells = [$(super::pretty::display_cell(f, &self.$field)),+];
+				super::pretty::write_rows(f, None, &cells, $cols)
+			}
+		}
+	};
+}
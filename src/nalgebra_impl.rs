@@ -0,0 +1,95 @@
+/*!
+Conversions to and from nalgebra.
+
+```
+# extern crate cvmath;
+# extern crate nalgebra;
+# use cvmath::prelude::{Vec3, Mat2};
+# fn main() {
+let v: nalgebra::Vector3<f32> = Vec3(1.0, 2.0, 3.0).into();
+assert_eq!(nalgebra::Vector3::new(1.0, 2.0, 3.0), v);
+assert_eq!(Vec3(1.0, 2.0, 3.0), v.into());
+
+let m = Mat2::new(1, 2, 3, 4);
+let n: nalgebra::Matrix2<i32> = m.into();
+assert_eq!(3, n[(1, 0)]);
+assert_eq!(m, n.into());
+# }
+```
+*/
+
+use nalgebra as na;
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+
+macro_rules! convert_vec {
+	($vec:ident $na:ident $point:ident { $($field:ident),+ }) => {
+		impl<T: na::Scalar + Copy> From<$vec<T>> for na::$na<T> {
+			fn from(v: $vec<T>) -> na::$na<T> {
+				na::$na::new($(v.$field),+)
+			}
+		}
+		impl<T: na::Scalar + Copy> From<na::$na<T>> for $vec<T> {
+			fn from(v: na::$na<T>) -> $vec<T> {
+				$vec { $($field: v.$field),+ }
+			}
+		}
+		impl<T: na::Scalar + Copy> From<$vec<T>> for na::$point<T> {
+			fn from(v: $vec<T>) -> na::$point<T> {
+				na::$point::new($(v.$field),+)
+			}
+		}
+		impl<T: na::Scalar + Copy> From<na::$point<T>> for $vec<T> {
+			fn from(v: na::$point<T>) -> $vec<T> {
+				$vec { $($field: v.$field),+ }
+			}
+		}
+	};
+}
+
+convert_vec!(Vec1 Vector1 Point1 { x });
+convert_vec!(Vec2 Vector2 Point2 { x, y });
+convert_vec!(Vec3 Vector3 Point3 { x, y, z });
+convert_vec!(Vec4 Vector4 Point4 { x, y, z, w });
+
+// nalgebra's constructors take the elements in row-major order.
+macro_rules! convert_mat {
+	($mat:ident $na:ident { $($field:ident $row:tt $col:tt),+ }) => {
+		impl<T: na::Scalar + Copy> From<$mat<T>> for na::$na<T> {
+			fn from(m: $mat<T>) -> na::$na<T> {
+				na::$na::new($(m.$field),+)
+			}
+		}
+		impl<T: na::Scalar + Copy> From<na::$na<T>> for $mat<T> {
+			fn from(m: na::$na<T>) -> $mat<T> {
+				$mat { $($field: m[($row, $col)]),+ }
+			}
+		}
+	};
+}
+
+convert_mat!(Mat2 Matrix2 {
+	a11 0 0, a12 0 1,
+	a21 1 0, a22 1 1
+});
+convert_mat!(Affine2 Matrix2x3 {
+	a11 0 0, a12 0 1, a13 0 2,
+	a21 1 0, a22 1 1, a23 1 2
+});
+convert_mat!(Mat3 Matrix3 {
+	a11 0 0, a12 0 1, a13 0 2,
+	a21 1 0, a22 1 1, a23 1 2,
+	a31 2 0, a32 2 1, a33 2 2
+});
+convert_mat!(Affine3 Matrix3x4 {
+	a11 0 0, a12 0 1, a13 0 2, a14 0 3,
+	a21 1 0, a22 1 1, a23 1 2, a24 1 3,
+	a31 2 0, a32 2 1, a33 2 2, a34 2 3
+});
+convert_mat!(Mat4 Matrix4 {
+	a11 0 0, a12 0 1, a13 0 2, a14 0 3,
+	a21 1 0, a22 1 1, a23 1 2, a24 1 3,
+	a31 2 0, a32 2 1, a33 2 2, a34 2 3,
+	a41 3 0, a42 3 1, a43 3 2, a44 3 3
+});
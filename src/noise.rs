@@ -0,0 +1,300 @@
+/*!
+Procedural noise.
+
+Value noise interpolates random values at the integer lattice points, gradient (Perlin) noise interpolates random gradients
+and simplex noise sums the contributions of the corners of a simplex grid, which has fewer directional artifacts and scales better to 3D.
+
+All noise functions are deterministic for a given `seed` and return values in approximately the range `[-1, 1]`.
+Perlin and simplex noise are zero at the integer lattice points.
+
+Combine octaves of noise with [`fbm`](fn.fbm.html) and [`turbulence`](fn.turbulence.html).
+
+### Examples
+
+```
+use cvmath::prelude::{Vec2, Vec3};
+use cvmath::noise;
+
+let height = noise::fbm(|pt| noise::simplex2(pt, 42), Vec2(1.5, -3.25), 5, 2.0, 0.5);
+assert!(height >= -1.0 && height <= 1.0);
+
+let density = noise::turbulence(|pt| noise::perlin3(pt, 7), Vec3(0.1, 0.2, 0.3), 4, 2.0, 0.5);
+assert!(density >= 0.0 && density <= 1.0);
+
+assert_eq!(0.0, noise::perlin2(Vec2(3.0, -2.0), 1));
+assert_eq!(noise::value3(Vec3(0.5, 1.5, 2.5), 9), noise::value3(Vec3(0.5, 1.5, 2.5), 9));
+```
+*/
+
+use std::ops;
+
+use num::{Float, CastFrom};
+use vec::{Vec2, Vec3};
+
+//----------------------------------------------------------------
+// Hashing
+
+fn hash(mut h: u32) -> u32 {
+	h ^= h >> 16;
+	h = h.wrapping_mul(0x7feb_352d);
+	h ^= h >> 15;
+	h = h.wrapping_mul(0x846c_a68b);
+	h ^= h >> 16;
+	h
+}
+
+fn hash2(x: i32, y: i32, seed: u32) -> u32 {
+	hash(x as u32 ^ hash(y as u32 ^ hash(seed)))
+}
+
+fn hash3(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+	hash(x as u32 ^ hash(y as u32 ^ hash(z as u32 ^ hash(seed))))
+}
+
+/// Maps the hash to the range `[-1, 1]`.
+fn unit<T: Float>(h: u32) -> T {
+	T::cast_from(h as f64 / u32::MAX as f64 * 2.0 - 1.0)
+}
+
+fn floor<T: Float>(x: T) -> (i32, T) where i32: CastFrom<T> {
+	let f = x.floor();
+	(i32::cast_from(f), x - f)
+}
+
+/// Quintic interpolation curve with zero first and second derivatives at the ends.
+fn fade<T: Float>(t: T) -> T {
+	t * t * t * (t * (t * T::cast_from(6.0) - T::cast_from(15.0)) + T::cast_from(10.0))
+}
+
+fn lerp<T: Float>(a: T, b: T, t: T) -> T {
+	a + (b - a) * t
+}
+
+//----------------------------------------------------------------
+// Value noise
+
+/// 2D value noise.
+pub fn value2<T: Float>(pt: Vec2<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let (ix, fx) = floor(pt.x);
+	let (iy, fy) = floor(pt.y);
+	let v = |dx: i32, dy: i32| unit::<T>(hash2(ix.wrapping_add(dx), iy.wrapping_add(dy), seed));
+	let (u, w) = (fade(fx), fade(fy));
+	lerp(lerp(v(0, 0), v(1, 0), u), lerp(v(0, 1), v(1, 1), u), w)
+}
+
+/// 3D value noise.
+pub fn value3<T: Float>(pt: Vec3<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let (ix, fx) = floor(pt.x);
+	let (iy, fy) = floor(pt.y);
+	let (iz, fz) = floor(pt.z);
+	let v = |dx: i32, dy: i32, dz: i32| unit::<T>(hash3(ix.wrapping_add(dx), iy.wrapping_add(dy), iz.wrapping_add(dz), seed));
+	let (u, w, s) = (fade(fx), fade(fy), fade(fz));
+	lerp(
+		lerp(lerp(v(0, 0, 0), v(1, 0, 0), u), lerp(v(0, 1, 0), v(1, 1, 0), u), w),
+		lerp(lerp(v(0, 0, 1), v(1, 0, 1), u), lerp(v(0, 1, 1), v(1, 1, 1), u), w),
+		s,
+	)
+}
+
+//----------------------------------------------------------------
+// Gradient noise
+
+/// Dot product of the offset with one of 12 gradients, the 2D projections of the cube edge directions.
+fn grad2<T: Float>(h: u32, x: T, y: T) -> T {
+	match h % 12 {
+		0 => x + y, 1 => -x + y, 2 => x - y, 3 => -x - y,
+		4 | 6 => x, 5 | 7 => -x,
+		8 | 10 => y, _ => -y,
+	}
+}
+
+/// Dot product of the offset with one of the 12 cube edge directions.
+fn grad3<T: Float>(h: u32, x: T, y: T, z: T) -> T {
+	match h % 12 {
+		0 => x + y, 1 => -x + y, 2 => x - y, 3 => -x - y,
+		4 => x + z, 5 => -x + z, 6 => x - z, 7 => -x - z,
+		8 => y + z, 9 => -y + z, 10 => y - z, _ => -y - z,
+	}
+}
+
+/// 2D Perlin gradient noise.
+pub fn perlin2<T: Float>(pt: Vec2<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let one = T::one();
+	let (ix, fx) = floor(pt.x);
+	let (iy, fy) = floor(pt.y);
+	let g = |dx: i32, dy: i32| {
+		let h = hash2(ix.wrapping_add(dx), iy.wrapping_add(dy), seed);
+		grad2(h, fx - T::cast_from(dx as f64), fy - T::cast_from(dy as f64))
+	};
+	let (u, w) = (fade(fx), fade(fy));
+	let n = lerp(lerp(g(0, 0), g(1, 0), u), lerp(g(0, 1), g(1, 1), u), w);
+	// The diagonal gradients reach a little over one
+	n * (one / T::cast_from(1.0625))
+}
+
+/// 3D Perlin gradient noise.
+pub fn perlin3<T: Float>(pt: Vec3<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let (ix, fx) = floor(pt.x);
+	let (iy, fy) = floor(pt.y);
+	let (iz, fz) = floor(pt.z);
+	let g = |dx: i32, dy: i32, dz: i32| {
+		let h = hash3(ix.wrapping_add(dx), iy.wrapping_add(dy), iz.wrapping_add(dz), seed);
+		grad3(h, fx - T::cast_from(dx as f64), fy - T::cast_from(dy as f64), fz - T::cast_from(dz as f64))
+	};
+	let (u, w, s) = (fade(fx), fade(fy), fade(fz));
+	lerp(
+		lerp(lerp(g(0, 0, 0), g(1, 0, 0), u), lerp(g(0, 1, 0), g(1, 1, 0), u), w),
+		lerp(lerp(g(0, 0, 1), g(1, 0, 1), u), lerp(g(0, 1, 1), g(1, 1, 1), u), w),
+		s,
+	)
+}
+
+//----------------------------------------------------------------
+// Simplex noise
+
+/// 2D simplex noise.
+pub fn simplex2<T: Float>(pt: Vec2<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let sqrt3 = 1.7320508075688772;
+	let f2 = T::cast_from(0.5 * (sqrt3 - 1.0));
+	let g2 = T::cast_from((3.0 - sqrt3) / 6.0);
+	let (zero, one, half) = (T::zero(), T::one(), T::cast_from(0.5));
+
+	// Skew the input space to find the simplex cell
+	let s = (pt.x + pt.y) * f2;
+	let (i, _) = floor(pt.x + s);
+	let (j, _) = floor(pt.y + s);
+	let t = T::cast_from((i as f64 + j as f64) * ((3.0 - sqrt3) / 6.0));
+	let x0 = pt.x - (T::cast_from(i as f64) - t);
+	let y0 = pt.y - (T::cast_from(j as f64) - t);
+
+	// Lower or upper triangle of the skewed cell
+	let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+	let x1 = x0 - T::cast_from(i1 as f64) + g2;
+	let y1 = y0 - T::cast_from(j1 as f64) + g2;
+	let x2 = x0 - one + g2 + g2;
+	let y2 = y0 - one + g2 + g2;
+
+	let corner = |x: T, y: T, di: i32, dj: i32| {
+		let t = half - x * x - y * y;
+		if t < zero { zero } else {
+			let t2 = t * t;
+			t2 * t2 * grad2(hash2(i.wrapping_add(di), j.wrapping_add(dj), seed), x, y)
+		}
+	};
+	let n = corner(x0, y0, 0, 0) + corner(x1, y1, i1, j1) + corner(x2, y2, 1, 1);
+	n * T::cast_from(70.0)
+}
+
+/// 3D simplex noise.
+pub fn simplex3<T: Float>(pt: Vec3<T>, seed: u32) -> T where i32: CastFrom<T> {
+	let (zero, one) = (T::zero(), T::one());
+	let f3 = T::cast_from(1.0 / 3.0);
+	let g3 = T::cast_from(1.0 / 6.0);
+
+	// Skew the input space to find the simplex cell
+	let s = (pt.x + pt.y + pt.z) * f3;
+	let (i, _) = floor(pt.x + s);
+	let (j, _) = floor(pt.y + s);
+	let (k, _) = floor(pt.z + s);
+	let t = T::cast_from((i as f64 + j as f64 + k as f64) / 6.0);
+	let x0 = pt.x - (T::cast_from(i as f64) - t);
+	let y0 = pt.y - (T::cast_from(j as f64) - t);
+	let z0 = pt.z - (T::cast_from(k as f64) - t);
+
+	// Determine which of the six tetrahedra contains the point
+	let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+		if y0 >= z0 { ((1, 0, 0), (1, 1, 0)) }
+		else if x0 >= z0 { ((1, 0, 0), (1, 0, 1)) }
+		else { ((0, 0, 1), (1, 0, 1)) }
+	}
+	else {
+		if y0 < z0 { ((0, 0, 1), (0, 1, 1)) }
+		else if x0 < z0 { ((0, 1, 0), (0, 1, 1)) }
+		else { ((0, 1, 0), (1, 1, 0)) }
+	};
+	let offset = |x: T, d: i32, n: T| x - T::cast_from(d as f64) + g3 * n;
+	let (n1, n2, n3) = (one, one + one, one + one + one);
+
+	let corner = |x: T, y: T, z: T, di: i32, dj: i32, dk: i32| {
+		let t = T::cast_from(0.6) - x * x - y * y - z * z;
+		if t < zero { zero } else {
+			let t2 = t * t;
+			t2 * t2 * grad3(hash3(i.wrapping_add(di), j.wrapping_add(dj), k.wrapping_add(dk), seed), x, y, z)
+		}
+	};
+	let n = corner(x0, y0, z0, 0, 0, 0)
+		+ corner(offset(x0, i1, n1), offset(y0, j1, n1), offset(z0, k1, n1), i1, j1, k1)
+		+ corner(offset(x0, i2, n2), offset(y0, j2, n2), offset(z0, k2, n2), i2, j2, k2)
+		+ corner(offset(x0, 1, n3), offset(y0, 1, n3), offset(z0, 1, n3), 1, 1, 1);
+	n * T::cast_from(32.0)
+}
+
+//----------------------------------------------------------------
+// Combinators
+
+/// Fractal Brownian motion, sums octaves of noise with increasing frequency and decreasing amplitude.
+///
+/// Each octave multiplies the frequency by the `lacunarity` (typically `2`) and the amplitude by the `gain` (typically `0.5`).
+/// The result is normalized by the total amplitude so it stays in the range of the noise function.
+pub fn fbm<T, V, F>(noise: F, pt: V, octaves: u32, lacunarity: T, gain: T) -> T
+	where T: Float, V: Copy + ops::Mul<T, Output = V>, F: Fn(V) -> T
+{
+	octaves_sum(noise, pt, octaves, lacunarity, gain)
+}
+
+/// Turbulence, like [`fbm`](fn.fbm.html) but sums the absolute values of the octaves.
+///
+/// The result is in the range `[0, 1]` for noise in the range `[-1, 1]`, with sharp creases where the noise crosses zero.
+pub fn turbulence<T, V, F>(noise: F, pt: V, octaves: u32, lacunarity: T, gain: T) -> T
+	where T: Float, V: Copy + ops::Mul<T, Output = V>, F: Fn(V) -> T
+{
+	octaves_sum(|pt| noise(pt).abs(), pt, octaves, lacunarity, gain)
+}
+
+fn octaves_sum<T, V, F>(noise: F, pt: V, octaves: u32, lacunarity: T, gain: T) -> T
+	where T: Float, V: Copy + ops::Mul<T, Output = V>, F: Fn(V) -> T
+{
+	let (mut sum, mut total) = (T::zero(), T::zero());
+	let (mut amplitude, mut frequency) = (T::one(), T::one());
+	for _ in 0..octaves {
+		sum += noise(pt * frequency) * amplitude;
+		total += amplitude;
+		amplitude *= gain;
+		frequency *= lacunarity;
+	}
+	if total > T::zero() { sum / total } else { T::zero() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ranges() {
+		let (mut min, mut max) = (0.0f64, 0.0f64);
+		for i in 0..200 {
+			for j in 0..200 {
+				let pt = Vec3(i as f64 * 0.173 - 17.0, j as f64 * 0.131 - 13.0, (i + j) as f64 * 0.05);
+				for &n in &[value2(pt.xy(), 3), value3(pt, 3), perlin2(pt.xy(), 3), perlin3(pt, 3), simplex2(pt.xy(), 3), simplex3(pt, 3)] {
+					min = min.min(n);
+					max = max.max(n);
+				}
+			}
+		}
+		assert!(min >= -1.0 && max <= 1.0, "{} {}", min, max);
+		assert!(min < -0.7 && max > 0.7, "{} {}", min, max);
+	}
+
+	#[test]
+	fn continuity() {
+		let eps = 1e-6;
+		for i in 0..1000 {
+			let pt = Vec3(i as f64 * 0.0371, i as f64 * -0.0173, i as f64 * 0.0093);
+			let d = Vec3(eps, eps, eps);
+			assert!((simplex3(pt, 5) - simplex3(pt + d, 5)).abs() < 1e-4);
+			assert!((perlin3(pt, 5) - perlin3(pt + d, 5)).abs() < 1e-4);
+			assert!((simplex2(pt.xy(), 5) - simplex2(pt.xy() + d.xy(), 5)).abs() < 1e-4);
+			assert!((value2(pt.xy(), 5) - value2(pt.xy() + d.xy(), 5)).abs() < 1e-4);
+		}
+	}
+}
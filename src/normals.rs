@@ -0,0 +1,109 @@
+/*!
+Smooth vertex normals.
+
+The face normals of the triangles sharing a vertex are summed and normalized, triangles are counter-clockwise when viewed from the front.
+These functions don't allocate, the normals are accumulated directly in the output.
+
+### Examples
+
+```
+use cvmath::prelude::Vec3;
+use cvmath::normals;
+
+// Two triangles folded along the X axis
+let positions = [Vec3(0.0, 0.0, 0.0), Vec3(1.0, 0.0, 0.0), Vec3(0.0, 1.0, 0.0), Vec3(0.0, 0.0, 1.0)];
+let indices = [0, 1, 2, 0, 3, 1];
+
+let mut out = [Vec3::default(); 4];
+normals::accumulate(&positions, &indices, &mut out);
+let diagonal = Vec3(0.0, 1.0, 1.0).norm();
+assert_eq!([diagonal, diagonal, Vec3(0.0, 0.0, 1.0), Vec3(0.0, 1.0, 0.0)], out);
+```
+*/
+
+use num::Float;
+use vec::Vec3;
+
+/// Computes area-weighted smooth normals.
+///
+/// Larger triangles contribute more to the vertex normal. This is the cheapest weighting.
+///
+/// Vertices not referenced by any triangle get a zero normal.
+/// Panics if the `positions` and `out` lengths don't match, or if an index is out of bounds.
+pub fn accumulate<T: Float>(positions: &[Vec3<T>], indices: &[u32], out: &mut [Vec3<T>]) {
+	assert_eq!(positions.len(), out.len());
+	clear(out);
+	for tri in indices.chunks_exact(3) {
+		let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+		// The length of the cross product is twice the area of the triangle
+		let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+		out[a] += normal;
+		out[b] += normal;
+		out[c] += normal;
+	}
+	normalize(out);
+}
+
+/// Computes angle-weighted smooth normals.
+///
+/// Each triangle contributes with the angle of its corner at the vertex.
+/// The result doesn't depend on how the surface is tessellated, which matters for meshes mixing large and thin triangles.
+///
+/// Vertices not referenced by any triangle get a zero normal.
+/// Panics if the `positions` and `out` lengths don't match, or if an index is out of bounds.
+pub fn accumulate_angle_weighted<T: Float>(positions: &[Vec3<T>], indices: &[u32], out: &mut [Vec3<T>]) {
+	assert_eq!(positions.len(), out.len());
+	clear(out);
+	for tri in indices.chunks_exact(3) {
+		let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+		let (pa, pb, pc) = (positions[a], positions[b], positions[c]);
+		let normal = (pb - pa).cross(pc - pa).norm();
+		if normal.len_sqr() > T::zero() {
+			out[a] += normal * (pb - pa).angle(pc - pa).0;
+			out[b] += normal * (pc - pb).angle(pa - pb).0;
+			out[c] += normal * (pa - pc).angle(pb - pc).0;
+		}
+	}
+	normalize(out);
+}
+
+fn clear<T: Float>(out: &mut [Vec3<T>]) {
+	for n in out.iter_mut() {
+		*n = Vec3::dup(T::zero());
+	}
+}
+
+fn normalize<T: Float>(out: &mut [Vec3<T>]) {
+	for n in out.iter_mut() {
+		if n.len_sqr() > T::zero() {
+			*n = n.norm();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn angle_weighted() {
+		// A corner of a cube split in one large and two small triangles on one side
+		let positions = [
+			Vec3(0.0, 0.0, 0.0),
+			Vec3(1.0, 0.0, 0.0), Vec3(0.0, 1.0, 0.0), Vec3(0.0, 0.0, 1.0),
+			Vec3(1.0, 1.0, 0.0),
+		];
+		let indices = [
+			0, 4, 2, 0, 1, 4, // XY plane as two triangles
+			0, 2, 3, // YZ plane
+			0, 3, 1, // ZX plane
+		];
+		let mut out = [Vec3::default(); 5];
+		accumulate_angle_weighted(&positions, &indices, &mut out);
+		assert!(out[0].dist(Vec3(1.0, 1.0, 1.0).norm()) < 1e-12);
+
+		// The two XY triangles cover an area of 1 against 1/2 for each of the other sides, so Z gets twice the weight
+		accumulate(&positions, &indices, &mut out);
+		assert!(out[0].dist(Vec3(1.0, 1.0, 2.0).norm()) < 1e-12);
+	}
+}
@@ -0,0 +1,31 @@
+use std::{fmt, hash};
+
+/// Canonical bit representation of floats.
+///
+/// Negative zero maps to positive zero and all NaN values map to a single quiet NaN.
+/// Values which compare equal (and any two NaN) have the same bits, suitable for hashing.
+pub trait CanonicalBits {
+	type Bits: Copy + Eq + Ord + hash::Hash + fmt::Debug;
+	fn canonical_bits(self) -> Self::Bits;
+}
+
+//----------------------------------------------------------------
+// Implementation
+
+macro_rules! impl_canonical_bits {
+	($ty:ident $bits:ident) => {
+
+impl CanonicalBits for $ty {
+	type Bits = $bits;
+	fn canonical_bits(self) -> $bits {
+		if self == 0.0 { 0 }
+		else if self.is_nan() { $ty::NAN.to_bits() }
+		else { self.to_bits() }
+	}
+}
+
+	}
+}
+
+impl_canonical_bits!(f32 u32);
+impl_canonical_bits!(f64 u64);
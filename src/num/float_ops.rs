@@ -1,9 +1,26 @@
+use super::Scalar;
 
+/// Floating point operations.
+///
+/// Methods added after the initial release have default implementations built on the original ones,
+/// implementors should override them with the native operations.
 pub trait FloatOps: Copy {
 	fn is_finite(self) -> bool;
 	fn is_infinite(self) -> bool;
 	fn sqrt(self) -> Self;
+	/// Returns the largest integer less than or equal to the number.
+	fn floor(self) -> Self where Self: Scalar {
+		floor(self)
+	}
+	/// Returns the smallest integer greater than or equal to the number.
+	fn ceil(self) -> Self where Self: Scalar {
+		-floor(-self)
+	}
 	fn remainder(self, Self) -> Self;
+	/// Raises the number to a floating point power.
+	fn powf(self, n: Self) -> Self where Self: Scalar {
+		powf(self, n)
+	}
 	fn sin(self) -> Self;
 	fn cos(self) -> Self;
 	fn tan(self) -> Self;
@@ -12,21 +29,81 @@ pub trait FloatOps: Copy {
 	fn acos(self) -> Self;
 	fn atan(self) -> Self;
 	fn atan2(self, Self) -> Self;
+	/// Approximates the reciprocal square root.
+	///
+	/// A fast estimate refined with one Newton-Raphson iteration, the relative error is below `0.2%`.
+	fn rsqrt_fast(self) -> Self where Self: Scalar {
+		Self::one() / self.sqrt()
+	}
+	/// Returns the machine epsilon, the difference between `1.0` and the next larger representable number.
+	fn epsilon() -> Self where Self: Scalar {
+		epsilon()
+	}
+}
+
+//----------------------------------------------------------------
+// Default implementations
+
+fn floor<T: Scalar + FloatOps>(x: T) -> T {
+	// The remainder rounds to the nearest integer, step down if it rounded up
+	let n = x - x.remainder(T::one());
+	if n > x { n - T::one() } else { n }
+}
+
+fn powf<T: Scalar + FloatOps>(x: T, n: T) -> T {
+	let (zero, one) = (T::zero(), T::one());
+	let two = one + one;
+	let exp = n.abs();
+	let int = floor(exp);
+	// Integer part by repeated squaring
+	let mut result = one;
+	let (mut base, mut rest) = (x, int);
+	while rest >= one {
+		if rest.remainder(two) != zero {
+			result *= base;
+		}
+		base *= base;
+		rest = floor(rest / two);
+	}
+	// Fractional part from its binary digits, each one a repeated square root
+	let (mut root, mut frac) = (x, exp - int);
+	while frac > zero && root != one {
+		root = root.sqrt();
+		frac *= two;
+		if frac >= one {
+			result *= root;
+			frac -= one;
+		}
+	}
+	if n < zero { one / result } else { result }
+}
+
+fn epsilon<T: Scalar>() -> T {
+	let (one, two) = (T::one(), T::one() + T::one());
+	let mut eps = one;
+	while one + eps / two != one {
+		eps /= two;
+	}
+	eps
 }
 
 //----------------------------------------------------------------
 // Implementation
 
+#[cfg(feature = "std")]
 macro_rules! impl_float_ops {
-	($ty:ty) => {
+	($ty:ty, $rsqrt_fast:ident, $($libm:ident)*) => {
 
 impl FloatOps for $ty {
 	fn is_finite(self) -> bool { self.is_finite() }
 	fn is_infinite(self) -> bool { self.is_infinite() }
 	fn sqrt(self) -> $ty { self.sqrt() }
+	fn floor(self) -> $ty { self.floor() }
+	fn ceil(self) -> $ty { self.ceil() }
 	fn remainder(self, y: $ty) -> $ty {
 		self - ((self / y).round() * y)
 	}
+	fn powf(self, n: $ty) -> $ty { self.powf(n) }
 	fn sin(self) -> $ty { self.sin() }
 	fn cos(self) -> $ty { self.cos() }
 	fn tan(self) -> $ty { self.tan() }
@@ -35,10 +112,87 @@ impl FloatOps for $ty {
 	fn acos(self) -> $ty { self.acos() }
 	fn atan(self) -> $ty { self.atan() }
 	fn atan2(self, x: $ty) -> $ty { self.atan2(x) }
+	fn rsqrt_fast(self) -> $ty { $rsqrt_fast(self) }
+	fn epsilon() -> $ty { <$ty>::EPSILON }
+}
+
+	}
 }
 
+// Without the standard library the float math is provided by libm.
+#[cfg(not(feature = "std"))]
+macro_rules! impl_float_ops {
+	($ty:ty, $rsqrt_fast:ident, $sqrt:ident $floor:ident $ceil:ident $round:ident $powf:ident $sin:ident $cos:ident $tan:ident $sin_cos:ident $asin:ident $acos:ident $atan:ident $atan2:ident) => {
+
+impl FloatOps for $ty {
+	fn is_finite(self) -> bool { self.is_finite() }
+	fn is_infinite(self) -> bool { self.is_infinite() }
+	fn sqrt(self) -> $ty { ::libm::$sqrt(self) }
+	fn floor(self) -> $ty { ::libm::$floor(self) }
+	fn ceil(self) -> $ty { ::libm::$ceil(self) }
+	fn remainder(self, y: $ty) -> $ty {
+		self - (::libm::$round(self / y) * y)
 	}
+	fn powf(self, n: $ty) -> $ty { ::libm::$powf(self, n) }
+	fn sin(self) -> $ty { ::libm::$sin(self) }
+	fn cos(self) -> $ty { ::libm::$cos(self) }
+	fn tan(self) -> $ty { ::libm::$tan(self) }
+	fn sin_cos(self) -> ($ty, $ty) { ::libm::$sin_cos(self) }
+	fn asin(self) -> $ty { ::libm::$asin(self) }
+	fn acos(self) -> $ty { ::libm::$acos(self) }
+	fn atan(self) -> $ty { ::libm::$atan(self) }
+	fn atan2(self, x: $ty) -> $ty { ::libm::$atan2(self, x) }
+	fn rsqrt_fast(self) -> $ty { $rsqrt_fast(self) }
+	fn epsilon() -> $ty { <$ty>::EPSILON }
 }
 
-impl_float_ops!(f32);
-impl_float_ops!(f64);
+	}
+}
+
+impl_float_ops!(f32, rsqrt_fast_f32, sqrtf floorf ceilf roundf powf sinf cosf tanf sincosf asinf acosf atanf atan2f);
+impl_float_ops!(f64, rsqrt_fast_f64, sqrt floor ceil round pow sin cos tan sincos asin acos atan atan2);
+
+#[cfg(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse")))]
+fn rsqrt_fast_f32(x: f32) -> f32 {
+	#[cfg(target_arch = "x86")]
+	use std::arch::x86::*;
+	#[cfg(target_arch = "x86_64")]
+	use std::arch::x86_64::*;
+	let y = unsafe { _mm_cvtss_f32(_mm_rsqrt_ss(_mm_set_ss(x))) };
+	y * (1.5 - 0.5 * x * y * y)
+}
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse"))))]
+fn rsqrt_fast_f32(x: f32) -> f32 {
+	let y = f32::from_bits(0x5f375a86 - (x.to_bits() >> 1));
+	y * (1.5 - 0.5 * x * y * y)
+}
+fn rsqrt_fast_f64(x: f64) -> f64 {
+	let y = f64::from_bits(0x5fe6eb50c7b537a9 - (x.to_bits() >> 1));
+	y * (1.5 - 0.5 * x * y * y)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn defaults() {
+		for &x in &[-2.5f64, -2.0, -0.5, -0.0, 0.0, 0.25, 0.5, 0.75, 1.0, 2.5, 3.0, 1e10 + 0.5] {
+			assert_eq!(x.floor(), floor(x));
+			assert_eq!(x.ceil(), -floor(-x));
+		}
+		for &(x, n) in &[(2.0f64, 10.0), (2.0, 0.5), (9.0, -1.5), (1.5, 3.25), (0.3, 0.0), (10.0, -2.0)] {
+			assert!((x.powf(n) - powf(x, n)).abs() <= 1e-12 * x.powf(n), "{}^{}", x, n);
+		}
+		assert_eq!(f32::EPSILON, epsilon::<f32>());
+		assert_eq!(f64::EPSILON, epsilon::<f64>());
+	}
+
+	#[test]
+	fn rsqrt_fast() {
+		for &x in &[1.0e-30f64, 0.001, 0.5, 1.0, 2.0, 3.0, 1000.0, 123456.0, 1.0e30] {
+			assert!((FloatOps::rsqrt_fast(x as f32) * (x as f32).sqrt() - 1.0).abs() < 0.002);
+			assert!((FloatOps::rsqrt_fast(x) * x.sqrt() - 1.0).abs() < 0.002);
+		}
+	}
+}
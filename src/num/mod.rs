@@ -11,6 +11,9 @@ mod extrema;
 mod abs;
 mod spatial_ord;
 mod float_ops;
+mod snap;
+mod canonical_bits;
+mod total_ord;
 
 pub use self::zero::Zero;
 pub use self::one::One;
@@ -19,6 +22,9 @@ pub use self::extrema::Extrema;
 pub use self::abs::Abs;
 pub use self::spatial_ord::SpatialOrd;
 pub use self::float_ops::FloatOps;
+pub use self::snap::{snap, snap_floor, snap_ceil};
+pub use self::canonical_bits::CanonicalBits;
+pub use self::total_ord::TotalOrd;
 
 pub trait Scalar where Self
 	: Copy + Default + Zero + One
@@ -28,7 +34,17 @@ pub trait Scalar where Self
 	+ ops::Neg<Output = Self> + ops::Rem<Output = Self>
 	+ ops::AddAssign + ops::SubAssign + ops::MulAssign + ops::DivAssign
 	+ Extrema + Abs<Output = Self>
-	+ cmp::PartialEq + cmp::PartialOrd {}
+	+ cmp::PartialEq + cmp::PartialOrd
+{
+	/// Calculates `self * a + b`.
+	///
+	/// With the `fma` feature floating point types compute this with a single rounding,
+	/// this is only fast when the target supports FMA instructions (eg. `-C target-feature=+fma`).
+	#[inline]
+	fn mul_add(self, a: Self, b: Self) -> Self {
+		self * a + b
+	}
+}
 
 pub trait Int where Self
 	: Scalar + cmp::Eq + cmp::Ord {}
@@ -44,8 +60,18 @@ impl Scalar for i16 {}
 impl Scalar for i32 {}
 impl Scalar for i64 {}
 
-impl Scalar for f32 {}
-impl Scalar for f64 {}
+macro_rules! impl_scalar_float {
+	($ty:ty) => {
+		impl Scalar for $ty {
+			#[cfg(feature = "fma")]
+			#[inline]
+			fn mul_add(self, a: $ty, b: $ty) -> $ty { <$ty>::mul_add(self, a, b) }
+		}
+	};
+}
+
+impl_scalar_float!(f32);
+impl_scalar_float!(f64);
 
 impl Int for i8 {}
 impl Int for i16 {}
@@ -54,3 +80,16 @@ impl Int for i64 {}
 
 impl Float for f32 {}
 impl Float for f64 {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mul_add_rounding() {
+		let e = f32::EPSILON;
+		let expected = if cfg!(feature = "fma") { -e * e } else { 0.0 };
+		assert_eq!(expected, Scalar::mul_add(1.0 + e, 1.0 - e, -1.0f32));
+		assert_eq!(7, Scalar::mul_add(2, 3, 1i32));
+	}
+}
@@ -0,0 +1,42 @@
+use super::Float;
+
+/// Rounds the value to the nearest multiple of the step.
+///
+/// Ties round towards positive infinity so snapping is consistent on both sides of zero.
+///
+/// ```
+/// use cvmath::num::snap;
+///
+/// assert_eq!(10.0, snap(11.0, 5.0));
+/// assert_eq!(15.0, snap(12.5, 5.0));
+/// assert_eq!(-10.0, snap(-12.5, 5.0));
+/// assert_eq!(-0.75, snap(-0.8, 0.25));
+/// ```
+pub fn snap<T: Float>(value: T, step: T) -> T {
+	let half = T::one() / (T::one() + T::one());
+	(value / step + half).floor() * step
+}
+
+/// Rounds the value down to the multiple of the step towards negative infinity.
+///
+/// ```
+/// use cvmath::num::snap_floor;
+///
+/// assert_eq!(10.0, snap_floor(14.9, 5.0));
+/// assert_eq!(-15.0, snap_floor(-10.1, 5.0));
+/// ```
+pub fn snap_floor<T: Float>(value: T, step: T) -> T {
+	(value / step).floor() * step
+}
+
+/// Rounds the value up to the multiple of the step towards positive infinity.
+///
+/// ```
+/// use cvmath::num::snap_ceil;
+///
+/// assert_eq!(15.0, snap_ceil(10.1, 5.0));
+/// assert_eq!(-10.0, snap_ceil(-14.9, 5.0));
+/// ```
+pub fn snap_ceil<T: Float>(value: T, step: T) -> T {
+	(value / step).ceil() * step
+}
@@ -0,0 +1,36 @@
+use std::cmp::Ordering;
+
+/// Total ordering of scalars.
+///
+/// Floats are ordered with `total_cmp`: negative NaN < -inf < ... < -0.0 < +0.0 < ... < +inf < positive NaN.
+pub trait TotalOrd {
+	fn total_cmp(&self, rhs: &Self) -> Ordering;
+}
+
+//----------------------------------------------------------------
+// Implementation
+
+macro_rules! impl_total_ord {
+	(float $ty:ty) => {
+		impl TotalOrd for $ty {
+			fn total_cmp(&self, rhs: &$ty) -> Ordering {
+				<$ty>::total_cmp(self, rhs)
+			}
+		}
+	};
+	(int $ty:ty) => {
+		impl TotalOrd for $ty {
+			fn total_cmp(&self, rhs: &$ty) -> Ordering {
+				Ord::cmp(self, rhs)
+			}
+		}
+	};
+}
+
+impl_total_ord!(int i8);
+impl_total_ord!(int i16);
+impl_total_ord!(int i32);
+impl_total_ord!(int i64);
+
+impl_total_ord!(float f32);
+impl_total_ord!(float f64);
@@ -0,0 +1,108 @@
+/*!
+num-traits support.
+
+Vectors implement `Zero`, `One` and `Bounded` component-wise, angles implement `Zero` and
+the transformation matrices implement `One` as their identity.
+
+```
+# extern crate cvmath;
+# extern crate num_traits;
+# use cvmath::prelude::{Vec3, Mat2, Deg};
+use num_traits::{Zero, One};
+
+# fn main() {
+assert!(Vec3::<f32>::zero().is_zero());
+assert_eq!(Vec3(1, 1, 1), Vec3::one());
+assert_eq!(Mat2::<f64>::identity(), Mat2::one());
+assert!(Deg::<f64>::zero().is_zero());
+# }
+```
+*/
+
+use num_traits::{Zero, One, Bounded};
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+use angle::{Deg, Rad};
+
+macro_rules! impl_vec {
+	($vec:ident { $($field:ident),+ }) => {
+		impl<T: Zero> Zero for $vec<T> {
+			fn zero() -> $vec<T> {
+				$vec { $($field: T::zero()),+ }
+			}
+			fn is_zero(&self) -> bool {
+				$(self.$field.is_zero())&&+
+			}
+		}
+		impl<T: One> One for $vec<T> {
+			fn one() -> $vec<T> {
+				$vec { $($field: T::one()),+ }
+			}
+		}
+		impl<T: Bounded> Bounded for $vec<T> {
+			fn min_value() -> $vec<T> {
+				$vec { $($field: T::min_value()),+ }
+			}
+			fn max_value() -> $vec<T> {
+				$vec { $($field: T::max_value()),+ }
+			}
+		}
+	};
+}
+
+impl_vec!(Vec1 { x });
+impl_vec!(Vec2 { x, y });
+impl_vec!(Vec3 { x, y, z });
+impl_vec!(Vec4 { x, y, z, w });
+
+macro_rules! impl_angle {
+	($ty:ident) => {
+		impl<T: Zero> Zero for $ty<T> {
+			fn zero() -> $ty<T> {
+				$ty(T::zero())
+			}
+			fn is_zero(&self) -> bool {
+				self.0.is_zero()
+			}
+		}
+	};
+}
+
+impl_angle!(Deg);
+impl_angle!(Rad);
+
+macro_rules! impl_mat {
+	($mat:ident { $($field:ident: $e:ident),+ }) => {
+		impl<T: Copy + Zero + One> One for $mat<T> {
+			fn one() -> $mat<T> {
+				$mat { $($field: T::$e()),+ }
+			}
+		}
+	};
+}
+
+impl_mat!(Mat2 {
+	a11: one, a12: zero,
+	a21: zero, a22: one
+});
+impl_mat!(Affine2 {
+	a11: one, a12: zero, a13: zero,
+	a21: zero, a22: one, a23: zero
+});
+impl_mat!(Mat3 {
+	a11: one, a12: zero, a13: zero,
+	a21: zero, a22: one, a23: zero,
+	a31: zero, a32: zero, a33: one
+});
+impl_mat!(Affine3 {
+	a11: one, a12: zero, a13: zero, a14: zero,
+	a21: zero, a22: one, a23: zero, a24: zero,
+	a31: zero, a32: zero, a33: one, a34: zero
+});
+impl_mat!(Mat4 {
+	a11: one, a12: zero, a13: zero, a14: zero,
+	a21: zero, a22: one, a23: zero, a24: zero,
+	a31: zero, a32: zero, a33: one, a34: zero,
+	a41: zero, a42: zero, a43: zero, a44: one
+});
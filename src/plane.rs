@@ -0,0 +1,185 @@
+/*!
+Plane in 3D space.
+*/
+
+use num::{Scalar, Float};
+use vec::{Vec3, Vec4};
+use point::Point3;
+use mat::Mat4;
+use error::MathError;
+
+/// Plane in 3D space.
+///
+/// The points on the plane satisfy `normal.dot(pt) + distance == 0`.
+/// The normal points towards the positive half-space.
+///
+/// ```
+/// use cvmath::prelude::{Plane, Point3, Vec3};
+///
+/// let plane = Plane::from_point_normal(Point3(0.0, 2.0, 0.0), Vec3(0.0, 1.0, 0.0));
+/// assert_eq!(-2.0, plane.distance);
+/// assert_eq!(3.0, plane.signed_distance(Point3(5.0, 5.0, 1.0)));
+/// assert_eq!(Point3(5.0, 2.0, 1.0), plane.project(Point3(5.0, 5.0, 1.0)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Plane<T> {
+	pub normal: Vec3<T>,
+	pub distance: T,
+}
+
+//----------------------------------------------------------------
+// Constructors
+
+impl<T> Plane<T> {
+	/// Constructs a new plane.
+	pub fn new(normal: Vec3<T>, distance: T) -> Plane<T> {
+		Plane { normal, distance }
+	}
+}
+
+impl<T: Scalar> Plane<T> {
+	/// Constructs a plane through the point with the given normal.
+	pub fn from_point_normal(pt: Point3<T>, normal: Vec3<T>) -> Plane<T> {
+		Plane { normal, distance: -normal.dot(pt) }
+	}
+	/// Constructs a plane through three points.
+	///
+	/// The normal is not normalized and points towards the side from which the points appear counter-clockwise.
+	pub fn from_points(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Plane<T> {
+		let normal = (b - a).cross(c - a);
+		Plane::from_point_normal(a, normal)
+	}
+	/// Constructs a plane through three points.
+	///
+	/// Returns `Err(MathError::Degenerate)` if the points are collinear or coincide.
+	///
+	/// ```
+	/// use cvmath::prelude::{Plane, Point3};
+	/// use cvmath::error::MathError;
+	///
+	/// let plane = Plane::try_from_points(Point3(0.0, 0.0, 1.0), Point3(1.0, 0.0, 1.0), Point3(0.0, 1.0, 1.0));
+	/// assert_eq!(Ok(1.0), plane.map(|p| p.normal.z));
+	///
+	/// let line = Plane::try_from_points(Point3(0.0, 0.0, 0.0), Point3(1.0, 1.0, 1.0), Point3(2.0, 2.0, 2.0));
+	/// assert_eq!(Err(MathError::Degenerate), line);
+	/// ```
+	pub fn try_from_points(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Result<Plane<T>, MathError> {
+		let normal = (b - a).cross(c - a);
+		if normal.len_sqr() == T::zero() {
+			return Err(MathError::Degenerate);
+		}
+		Ok(Plane::from_point_normal(a, normal))
+	}
+	/// Constructs a plane from its coefficients `(a, b, c, d)` where `ax + by + cz + d = 0`.
+	pub fn from_vec4(v: Vec4<T>) -> Plane<T> {
+		Plane { normal: v.xyz(), distance: v.w }
+	}
+	/// Returns the plane coefficients `(a, b, c, d)` where `ax + by + cz + d = 0`.
+	pub fn vec4(self) -> Vec4<T> {
+		self.normal.vec4(self.distance)
+	}
+}
+
+//----------------------------------------------------------------
+// Operations
+
+impl<T: Scalar> Plane<T> {
+	/// Returns the signed distance from the plane to the point.
+	///
+	/// The distance is scaled by the length of the normal unless the plane is normalized.
+	pub fn signed_distance(&self, pt: Point3<T>) -> T {
+		self.normal.dot(pt) + self.distance
+	}
+	/// Returns the plane with the opposite orientation.
+	pub fn flip(self) -> Plane<T> {
+		Plane { normal: -self.normal, distance: -self.distance }
+	}
+	/// Normalizes the plane so its normal has unit length.
+	pub fn norm(self) -> Plane<T> where T: Float {
+		let len = self.normal.len();
+		if len > T::zero() {
+			Plane { normal: self.normal / len, distance: self.distance / len }
+		}
+		else { self }
+	}
+	/// Normalizes the plane so its normal has unit length.
+	///
+	/// Returns `Err(MathError::ZeroLength)` if the normal has zero length.
+	pub fn try_norm(self) -> Result<Plane<T>, MathError> where T: Float {
+		let len = self.normal.len();
+		if len > T::zero() && len.is_finite() {
+			Ok(Plane { normal: self.normal / len, distance: self.distance / len })
+		}
+		else { Err(MathError::ZeroLength) }
+	}
+	/// Projects the point onto the plane.
+	pub fn project(&self, pt: Point3<T>) -> Point3<T> {
+		pt - self.normal * (self.signed_distance(pt) / self.normal.len_sqr())
+	}
+}
+
+//----------------------------------------------------------------
+// Transformations
+
+impl<T: Float> Plane<T> {
+	/// Transforms the plane by the matrix.
+	///
+	/// Planes transform by the inverse-transpose of the matrix, multiplying the coefficients like a point or direction is incorrect
+	/// for any transform which isn't rigid. The resulting plane is not normalized.
+	///
+	/// ```
+	/// use cvmath::prelude::{Mat4, Plane, Point3, Vec3};
+	///
+	/// let plane = Plane::from_point_normal(Point3(1.0f64, 1.0, 0.0), Vec3(1.0, 1.0, 0.0));
+	/// // Non-uniform scale and translation
+	/// let m = Mat4::new(
+	///     2.0, 0.0, 0.0, 1.0,
+	///     0.0, 1.0, 0.0, 0.0,
+	///     0.0, 0.0, 1.0, 0.0,
+	///     0.0, 0.0, 0.0, 1.0,
+	/// );
+	/// let transformed = plane.transform(&m).norm();
+	/// for &pt in &[Point3(1.0, 1.0, 0.0), Point3(2.0, 0.0, 0.0), Point3(0.0, 2.0, 5.0)] {
+	///     assert!(transformed.signed_distance(m.transform_point(pt)).abs() < 1e-12);
+	/// }
+	/// assert!(transformed.normal.dist(Vec3(1.0, 2.0, 0.0).norm()) < 1e-12);
+	/// ```
+	pub fn transform(&self, m: &Mat4<T>) -> Plane<T> {
+		Plane::from_vec4(m.inverse().transpose() * self.vec4())
+	}
+	/// Transforms the plane by a rigid transformation.
+	///
+	/// Faster than [`transform`](#method.transform) when the matrix only rotates and translates,
+	/// the result is wrong for matrices with scale, shear or projection.
+	pub fn transform_rigid(&self, m: &Mat4<T>) -> Plane<T> {
+		let normal = Vec3 {
+			x: m.a11 * self.normal.x + m.a12 * self.normal.y + m.a13 * self.normal.z,
+			y: m.a21 * self.normal.x + m.a22 * self.normal.y + m.a23 * self.normal.z,
+			z: m.a31 * self.normal.x + m.a32 * self.normal.y + m.a33 * self.normal.z,
+		};
+		let translation = Vec3 { x: m.a14, y: m.a24, z: m.a34 };
+		Plane { normal, distance: self.distance - normal.dot(translation) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use angle::Deg;
+	use mat::Mat3;
+
+	#[test]
+	fn transform_rigid() {
+		let plane = Plane::from_points(Point3(1.0f64, 0.0, 2.0), Point3(0.0, 3.0, 1.0), Point3(-1.0, 1.0, 0.0)).norm();
+		let mut m = Mat4::from(Mat3::rotate_x(Deg(30.0)) * Mat3::rotate_y(Deg(-70.0)));
+		m.a14 = 3.0;
+		m.a24 = -1.0;
+		m.a34 = 0.5;
+		let general = plane.transform(&m);
+		let rigid = plane.transform_rigid(&m);
+		assert!(general.normal.dist(rigid.normal) < 1e-12);
+		assert!((general.distance - rigid.distance).abs() < 1e-12);
+	}
+}
@@ -0,0 +1,49 @@
+/*!
+Simple polygons given as a list of points.
+
+The polygon is implicitly closed, the last point connects back to the first.
+See the [triangle module](../triangle/index.html#winding-convention) for the winding convention.
+
+### Examples
+
+```
+use cvmath::prelude::Point2;
+use cvmath::polygon;
+use cvmath::triangle::Winding;
+
+let square = [Point2(0.0, 0.0), Point2(2.0, 0.0), Point2(2.0, 2.0), Point2(0.0, 2.0)];
+assert_eq!(4.0, polygon::signed_area(&square));
+assert_eq!(Winding::CounterClockwise, polygon::winding(&square));
+
+let mut reversed = square;
+reversed.reverse();
+assert_eq!(Winding::Clockwise, polygon::winding(&reversed));
+```
+*/
+
+use num::Scalar;
+use point::Point2;
+use triangle::Winding;
+
+/// Calculates the signed area with the shoelace formula, positive if the polygon winds counter-clockwise.
+///
+/// Polygons with fewer than three points have zero area.
+pub fn signed_area<T: Scalar>(points: &[Point2<T>]) -> T {
+	let Some(&last) = points.last() else {
+		return T::zero();
+	};
+	let mut prev = last;
+	let mut sum = T::zero();
+	for &pt in points {
+		sum += prev.cross(pt);
+		prev = pt;
+	}
+	sum / (T::one() + T::one())
+}
+
+/// Returns the winding of the polygon.
+///
+/// For self-intersecting polygons this is the winding of the net signed area.
+pub fn winding<T: Scalar>(points: &[Point2<T>]) -> Winding {
+	Winding::from_signed_area(signed_area(points))
+}
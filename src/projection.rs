@@ -0,0 +1,378 @@
+/*!
+Projection helpers.
+
+Normalized device coordinates range from `-1` to `1` with the Y axis pointing up and depth ranging from `0` to `1`.
+This matches the projections constructed by [`Mat4`](../mat/struct.Mat4.html).
+The [`Viewport`](struct.Viewport.html) maps them to screen coordinates in pixels.
+
+### Examples
+
+Mouse picking:
+
+```
+use cvmath::prelude::{Mat4, Vec2, Vec3, Point2, Deg};
+use cvmath::projection::{Viewport, unproject, screen_ray};
+
+let viewport = Viewport::new(Point2(0.0, 0.0), Vec2(800.0, 600.0));
+let proj = Mat4::perspective(Deg(90.0), 800.0 / 600.0, 1.0, 100.0);
+let inv_view_proj = proj.inverse();
+
+// The center of the screen on the near plane
+let pt = unproject(Vec2(400.0, 300.0), 0.0, &viewport, &inv_view_proj);
+assert!(pt.dist(Vec3(0.0, 0.0, -1.0)) < 1e-12);
+
+// The ray through the top left corner
+let ray = screen_ray(Vec2(0.0, 0.0), &viewport, &inv_view_proj);
+assert!(ray.origin.dist(Vec3(-4.0 / 3.0, 1.0, -1.0)) < 1e-12);
+assert!(ray.direction.norm().dist(Vec3(-4.0 / 3.0, 1.0, -1.0).norm()) < 1e-12);
+```
+*/
+
+use num::{Scalar, Float};
+use vec::{Vec2, Vec3, Vec4};
+use point::Point2;
+use mat::Mat4;
+use bounds::{Bounds, Rect, Cuboid};
+use ray::Ray;
+use sphere::Sphere;
+
+/// Clip space depth range.
+///
+/// Selects the depth range of the projections constructed by [`Mat4`](../mat/struct.Mat4.html) with explicit conventions.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum DepthRange {
+	/// Depth ranges from `0` to `1` (Direct3D, Vulkan, Metal and WebGPU conventions).
+	#[default]
+	ZeroToOne,
+	/// Depth ranges from `-1` to `1` (OpenGL convention).
+	NegOneToOne,
+}
+
+/// Viewport transform.
+///
+/// Maps normalized device coordinates to screen coordinates in pixels and depth to the depth range.
+///
+/// By default the Y axis of the screen points down with the origin in the top left corner, as is the case for most windowing systems.
+/// Use [`flip_y`](#method.flip_y) for screen coordinates with the Y axis pointing up.
+///
+/// ```
+/// use cvmath::prelude::{Vec2, Vec3, Point2};
+/// use cvmath::projection::Viewport;
+///
+/// let viewport = Viewport::new(Point2(10.0, 20.0), Vec2(800.0, 600.0));
+/// assert_eq!(Vec3(10.0, 20.0, 0.0), viewport.ndc_to_screen(Vec3(-1.0, 1.0, 0.0)));
+/// assert_eq!(Vec3(810.0, 620.0, 1.0), viewport.ndc_to_screen(Vec3(1.0, -1.0, 1.0)));
+/// assert_eq!(Vec3(0.0, 0.0, 0.5), viewport.screen_to_ndc(Vec3(410.0, 320.0, 0.5)));
+///
+/// let viewport = viewport.flip_y().with_depth_range(0.5, 1.0);
+/// assert_eq!(Vec3(10.0, 620.0, 0.75), viewport.ndc_to_screen(Vec3(-1.0, 1.0, 0.5)));
+/// assert_eq!(viewport.ndc_to_screen(Vec3(0.5, 0.25, 0.5)), viewport.matrix().transform_point(Vec3(0.5, 0.25, 0.5)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Viewport<T> {
+	/// Top left corner in pixels, bottom left if the Y axis points up.
+	pub origin: Point2<T>,
+	/// Size in pixels.
+	pub size: Vec2<T>,
+	/// Screen depth for the NDC depth `0`.
+	pub min_depth: T,
+	/// Screen depth for the NDC depth `1`.
+	pub max_depth: T,
+	/// Whether the Y axis of the screen points down.
+	pub y_down: bool,
+}
+
+//----------------------------------------------------------------
+// Constructors
+
+impl<T: Scalar> Viewport<T> {
+	/// Constructs a new viewport with the Y axis pointing down and depth range `0` to `1`.
+	pub fn new(origin: Point2<T>, size: Vec2<T>) -> Viewport<T> {
+		Viewport { origin, size, min_depth: T::zero(), max_depth: T::one(), y_down: true }
+	}
+	/// Constructs a new viewport covering the rectangle.
+	pub fn from_rect(rect: Rect<T>) -> Viewport<T> {
+		Viewport::new(rect.mins, rect.size())
+	}
+	/// Returns the viewport with the given depth range.
+	pub fn with_depth_range(self, min_depth: T, max_depth: T) -> Viewport<T> {
+		Viewport { min_depth, max_depth, ..self }
+	}
+	/// Returns the viewport with the opposite Y axis convention.
+	pub fn flip_y(self) -> Viewport<T> {
+		Viewport { y_down: !self.y_down, ..self }
+	}
+}
+
+//----------------------------------------------------------------
+// Operations
+
+impl<T: Float> Viewport<T> {
+	/// Transforms normalized device coordinates to screen coordinates.
+	pub fn ndc_to_screen(&self, ndc: Vec3<T>) -> Vec3<T> {
+		let half = T::one() / (T::one() + T::one());
+		let y = if self.y_down { -ndc.y } else { ndc.y };
+		Vec3 {
+			x: self.origin.x + (ndc.x + T::one()) * half * self.size.x,
+			y: self.origin.y + (y + T::one()) * half * self.size.y,
+			z: self.min_depth + ndc.z * (self.max_depth - self.min_depth),
+		}
+	}
+	/// Transforms screen coordinates to normalized device coordinates.
+	pub fn screen_to_ndc(&self, screen: Vec3<T>) -> Vec3<T> {
+		let two = T::one() + T::one();
+		let y = two * (screen.y - self.origin.y) / self.size.y - T::one();
+		Vec3 {
+			x: two * (screen.x - self.origin.x) / self.size.x - T::one(),
+			y: if self.y_down { -y } else { y },
+			z: (screen.z - self.min_depth) / (self.max_depth - self.min_depth),
+		}
+	}
+	/// Returns the matrix transforming normalized device coordinates to screen coordinates.
+	pub fn matrix(&self) -> Mat4<T> {
+		let (zero, one) = (T::zero(), T::one());
+		let half = one / (one + one);
+		let (sx, sy) = (self.size.x * half, self.size.y * half);
+		let sy_signed = if self.y_down { -sy } else { sy };
+		Mat4::new(
+			sx, zero, zero, self.origin.x + sx,
+			zero, sy_signed, zero, self.origin.y + sy,
+			zero, zero, self.max_depth - self.min_depth, self.min_depth,
+			zero, zero, zero, one,
+		)
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Transforms the screen position and depth back to world space.
+///
+/// The `depth` is in the depth range of the viewport.
+/// The `inv_view_proj` is the inverse of the combined view and projection matrix.
+pub fn unproject<T: Float>(screen_pos: Vec2<T>, depth: T, viewport: &Viewport<T>, inv_view_proj: &Mat4<T>) -> Vec3<T> {
+	let ndc = viewport.screen_to_ndc(screen_pos.vec3(depth));
+	inv_view_proj.transform_point(ndc)
+}
+
+/// Constructs the world space ray through the screen position.
+///
+/// The ray starts on the near plane, its direction points away from the camera and is not normalized.
+/// For finite projections the direction reaches the far plane,
+/// when the far plane is at infinity it reaches the point halfway in the depth range instead.
+///
+/// Works for standard and reversed-Z perspective projections, the near plane is the one closer to the camera.
+/// Orthographic projections are assumed to map the near plane to the viewport's `min_depth`.
+pub fn screen_ray<T: Float>(screen_pos: Vec2<T>, viewport: &Viewport<T>, inv_view_proj: &Mat4<T>) -> Ray<T> {
+	let clip = |depth: T| *inv_view_proj * viewport.screen_to_ndc(screen_pos.vec3(depth)).vec4(T::one());
+	let (min, max) = (clip(viewport.min_depth), clip(viewport.max_depth));
+	// The homogeneous coordinate is inversely proportional to the view depth for perspective projections
+	let (near, far) = if max.w.abs() > min.w.abs() { (max, min) } else { (min, max) };
+	let origin = near.hdiv();
+	let end = if far.w != T::zero() {
+		far.hdiv()
+	}
+	else {
+		let half = T::one() / (T::one() + T::one());
+		unproject(screen_pos, (viewport.min_depth + viewport.max_depth) * half, viewport, inv_view_proj)
+	};
+	Ray { origin, direction: end - origin }
+}
+
+//----------------------------------------------------------------
+// Screen bounds
+
+/// Calculates the screen rectangle covered by the sphere.
+///
+/// The sphere is given in view space and `proj` must be a perspective projection as constructed by [`Mat4`](../mat/struct.Mat4.html),
+/// optionally jittered or narrowed by a pick matrix.
+/// The bounds are exact: the tangent planes of the sphere through the camera are projected instead of the corners of its bounding box.
+///
+/// Returns `None` unless the sphere lies entirely in front of the camera. The rectangle is not clipped to the viewport.
+///
+/// ```
+/// use cvmath::prelude::{Mat4, Sphere, Vec2, Point2, Point3, Deg};
+/// use cvmath::projection::{Viewport, sphere_screen_rect};
+///
+/// let viewport = Viewport::new(Point2(0.0, 0.0), Vec2(800.0, 800.0));
+/// let proj = Mat4::perspective(Deg(90.0), 1.0, 0.1, 100.0);
+///
+/// // A sphere straight ahead with a tangent cone of 30° half-angle
+/// let sphere = Sphere::new(Point3(0.0, 0.0, -2.0), 1.0);
+/// let rect = sphere_screen_rect(&sphere, &proj, &viewport).unwrap();
+/// let half = 400.0 / 3f64.sqrt();
+/// assert!(rect.mins.dist(Point2(400.0 - half, 400.0 - half)) < 1e-9);
+/// assert!(rect.maxs.dist(Point2(400.0 + half, 400.0 + half)) < 1e-9);
+///
+/// // Spheres touching the camera plane have no bounds
+/// assert_eq!(None, sphere_screen_rect(&Sphere::new(Point3(0.0, 0.0, -1.0), 1.0), &proj, &viewport));
+/// ```
+pub fn sphere_screen_rect<T: Float>(sphere: &Sphere<T>, proj: &Mat4<T>, viewport: &Viewport<T>) -> Option<Rect<T>> {
+	let Sphere { center, radius } = *sphere;
+	if center.z + radius >= T::zero() {
+		return None;
+	}
+	// Rotates the center by the half-angle of the tangent cone in the plane containing the axis and the view direction
+	let tangents = |u: T, z: T| {
+		let len_sqr = u * u + z * z;
+		let len = len_sqr.sqrt();
+		let t = (len_sqr - radius * radius).sqrt();
+		let (cos, sin) = (t / len, radius / len);
+		let scale = t / len;
+		[
+			((cos * u - sin * z) * scale, (sin * u + cos * z) * scale),
+			((cos * u + sin * z) * scale, (cos * z - sin * u) * scale),
+		]
+	};
+	let screen = |pt: Vec3<T>| viewport.ndc_to_screen(proj.transform_point(pt));
+	let [(x0, z0), (x1, z1)] = tangents(center.x, center.z);
+	let [(y0, w0), (y1, w1)] = tangents(center.y, center.z);
+	let (left, right) = (screen(Vec3 { x: x0, y: T::zero(), z: z0 }).x, screen(Vec3 { x: x1, y: T::zero(), z: z1 }).x);
+	let (top, bottom) = (screen(Vec3 { x: T::zero(), y: y0, z: w0 }).y, screen(Vec3 { x: T::zero(), y: y1, z: w1 }).y);
+	Some(Rect(Point2 { x: left.min(right), y: top.min(bottom) }, Point2 { x: left.max(right), y: top.max(bottom) }))
+}
+
+/// Calculates the screen rectangle covered by the bounding box.
+///
+/// The bounding box is given in view space and projected by its corners, this works for any projection.
+///
+/// Returns `None` unless the bounding box lies entirely in front of the camera. The rectangle is not clipped to the viewport.
+pub fn cuboid_screen_rect<T: Float>(bounds: &Cuboid<T>, proj: &Mat4<T>, viewport: &Viewport<T>) -> Option<Rect<T>> {
+	let (mins, maxs) = (bounds.mins, bounds.maxs);
+	let mut rect: Option<Rect<T>> = None;
+	for i in 0..8 {
+		let corner = Vec4 {
+			x: if i & 1 == 0 { mins.x } else { maxs.x },
+			y: if i & 2 == 0 { mins.y } else { maxs.y },
+			z: if i & 4 == 0 { mins.z } else { maxs.z },
+			w: T::one(),
+		};
+		let clip = *proj * corner;
+		if clip.w <= T::zero() {
+			return None;
+		}
+		let pt = Bounds::point(viewport.ndc_to_screen(clip.hdiv()).xy());
+		rect = Some(rect.map_or(pt, |rect| rect.union(pt)));
+	}
+	rect
+}
+
+//----------------------------------------------------------------
+// Depth
+
+/// Converts the NDC depth of [`Mat4::perspective`](../mat/struct.Mat4.html#method.perspective) to the distance from the camera.
+///
+/// ```
+/// use cvmath::projection::linearize_depth;
+///
+/// assert_eq!(1.0, linearize_depth(0.0, 1.0, 100.0));
+/// assert_eq!(100.0, linearize_depth(1.0, 1.0, 100.0));
+/// ```
+pub fn linearize_depth<T: Float>(ndc_depth: T, near: T, far: T) -> T {
+	near * far / (far - ndc_depth * (far - near))
+}
+
+/// Converts the NDC depth of [`Mat4::perspective_reverse`](../mat/struct.Mat4.html#method.perspective_reverse) to the distance from the camera.
+pub fn linearize_depth_reverse<T: Float>(ndc_depth: T, near: T, far: T) -> T {
+	near * far / (near + ndc_depth * (far - near))
+}
+
+/// Converts the NDC depth of [`Mat4::perspective_infinite`](../mat/struct.Mat4.html#method.perspective_infinite) to the distance from the camera.
+///
+/// Returns infinity for the NDC depth `1`.
+pub fn linearize_depth_infinite<T: Float>(ndc_depth: T, near: T) -> T {
+	near / (T::one() - ndc_depth)
+}
+
+/// Converts the NDC depth of [`Mat4::perspective_infinite_reverse`](../mat/struct.Mat4.html#method.perspective_infinite_reverse) to the distance from the camera.
+///
+/// Returns infinity for the NDC depth `0`.
+pub fn linearize_depth_infinite_reverse<T: Float>(ndc_depth: T, near: T) -> T {
+	near / ndc_depth
+}
+
+/// Reconstructs the view space position from normalized device coordinates.
+///
+/// The `inv_proj` is the inverse of the projection matrix, this works for any of the projections.
+pub fn reconstruct_view_position<T: Float>(ndc: Vec3<T>, inv_proj: &Mat4<T>) -> Vec3<T> {
+	inv_proj.transform_point(ndc)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use angle::{Deg, Rad};
+
+	#[test]
+	fn screen_ray_reversed_z() {
+		let viewport = Viewport::new(Point2(0.0, 0.0), Vec2(800.0, 800.0));
+		let center = Vec2(400.0, 400.0);
+		let corner = Vec2(0.0, 0.0);
+		let deg = ::angle::Deg(90.0f64);
+		for &proj in &[Mat4::perspective(deg, 1.0, 0.1, 100.0), Mat4::perspective_reverse(deg, 1.0, 0.1, 100.0)] {
+			let ray = screen_ray(center, &viewport, &proj.inverse());
+			assert!(ray.origin.dist(Vec3(0.0, 0.0, -0.1)) < 1e-9);
+			assert!(ray.direction.dist(Vec3(0.0, 0.0, -99.9)) < 1e-9);
+			let ray = screen_ray(corner, &viewport, &proj.inverse());
+			assert!(ray.origin.dist(Vec3(-0.1, 0.1, -0.1)) < 1e-9);
+			assert!(ray.direction.dist(Vec3(-99.9, 99.9, -99.9)) < 1e-9);
+		}
+		for &proj in &[Mat4::perspective_infinite(deg, 1.0, 0.1), Mat4::perspective_infinite_reverse(deg, 1.0, 0.1)] {
+			let ray = screen_ray(center, &viewport, &proj.inverse());
+			assert!(ray.origin.dist(Vec3(0.0, 0.0, -0.1)) < 1e-9);
+			assert!(ray.direction.z < 0.0 && ray.direction.z.is_finite());
+			assert!(ray.direction.xy().len() < 1e-9);
+			let ray = screen_ray(corner, &viewport, &proj.inverse());
+			assert!(ray.direction.norm().dist(Vec3(-1.0, 1.0, -1.0).norm()) < 1e-9);
+		}
+	}
+
+	#[test]
+	fn linearize_roundtrip() {
+		let (near, far) = (0.5, 200.0);
+		let standard = Mat4::perspective(Deg(60.0), 1.5, near, far);
+		let reverse = Mat4::perspective_reverse(Deg(60.0), 1.5, near, far);
+		let infinite = Mat4::perspective_infinite(Deg(60.0), 1.5, near);
+		let infinite_reverse = Mat4::perspective_infinite_reverse(Deg(60.0), 1.5, near);
+		for &dist in &[0.5, 1.0, 3.0, 42.0, 200.0] {
+			let view = Vec3(1.0, -2.0, -dist);
+			let check = |proj: &Mat4<f64>, linearize: &dyn Fn(f64) -> f64| {
+				let ndc = proj.transform_point(view);
+				assert!((linearize(ndc.z) - dist).abs() < 1e-9 * dist);
+				assert!(reconstruct_view_position(ndc, &proj.inverse()).dist(view) < 1e-9 * dist);
+			};
+			check(&standard, &|z| linearize_depth(z, near, far));
+			check(&reverse, &|z| linearize_depth_reverse(z, near, far));
+			check(&infinite, &|z| linearize_depth_infinite(z, near));
+			check(&infinite_reverse, &|z| linearize_depth_infinite_reverse(z, near));
+		}
+	}
+
+	#[test]
+	fn sphere_rect_is_tight() {
+		let viewport = Viewport::new(Point2(0.0, 0.0), Vec2(1280.0, 720.0));
+		let proj = Mat4::perspective(Deg(70.0), 1280.0 / 720.0, 0.1, 100.0);
+		let sphere = Sphere::new(Vec3(3.0, -1.5, -6.0), 2.0);
+		let rect = sphere_screen_rect(&sphere, &proj, &viewport).unwrap();
+
+		// Sample the sphere's surface and compare with the bounds
+		let mut samples: Option<Rect<f64>> = None;
+		for i in 0..=200 {
+			for j in 0..400 {
+				let (sin_t, cos_t) = Rad(i as f64 * (Rad::<f64>::half().0 / 200.0)).sin_cos();
+				let (sin_p, cos_p) = Rad(j as f64 * (Rad::<f64>::turn().0 / 400.0)).sin_cos();
+				let pt = sphere.center + Vec3(sin_t * cos_p, sin_t * sin_p, cos_t) * sphere.radius;
+				let pt = Bounds::point(viewport.ndc_to_screen(proj.transform_point(pt)).xy());
+				samples = Some(samples.map_or(pt, |rect| rect.union(pt)));
+			}
+		}
+		let samples = samples.unwrap();
+		assert!(rect.mins.x <= samples.mins.x && rect.mins.y <= samples.mins.y);
+		assert!(rect.maxs.x >= samples.maxs.x && rect.maxs.y >= samples.maxs.y);
+		assert!(rect.mins.dist(samples.mins) < 0.5 && rect.maxs.dist(samples.maxs) < 0.5);
+
+		// Tighter than the bounding box corners
+		let corners = cuboid_screen_rect(&sphere.bounds(), &proj, &viewport).unwrap();
+		assert!(corners.mins.x < rect.mins.x && corners.maxs.y > rect.maxs.y);
+	}
+}
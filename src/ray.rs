@@ -0,0 +1,46 @@
+/*!
+Ray in 3D space.
+*/
+
+use num::{Scalar, Float};
+use vec::Vec3;
+use point::Point3;
+
+/// Ray in 3D space.
+///
+/// The points on the ray are `origin + direction * t` for `t >= 0`.
+///
+/// ```
+/// use cvmath::prelude::{Ray, Point3, Vec3};
+///
+/// let ray = Ray::new(Point3(1.0, 2.0, 3.0), Vec3(0.0, 0.0, -2.0));
+/// assert_eq!(Point3(1.0, 2.0, -1.0), ray.at(2.0));
+/// assert_eq!(Vec3(0.0, 0.0, -1.0), ray.norm().direction);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Ray<T> {
+	pub origin: Point3<T>,
+	pub direction: Vec3<T>,
+}
+
+impl<T> Ray<T> {
+	/// Constructs a new ray.
+	pub fn new(origin: Point3<T>, direction: Vec3<T>) -> Ray<T> {
+		Ray { origin, direction }
+	}
+}
+
+impl<T: Scalar> Ray<T> {
+	/// Returns the point at distance `t` along the ray.
+	///
+	/// The distance is measured in multiples of the direction.
+	pub fn at(&self, t: T) -> Point3<T> {
+		self.origin + self.direction * t
+	}
+	/// Normalizes the direction of the ray.
+	pub fn norm(self) -> Ray<T> where T: Float {
+		Ray { origin: self.origin, direction: self.direction.norm() }
+	}
+}
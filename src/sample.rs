@@ -0,0 +1,280 @@
+/*!
+Sample point sets.
+
+Poisson-disk sampling generates blue-noise point sets, stratified and multi-jittered patterns generate well distributed samples in the unit square.
+
+### Examples
+
+```
+# extern crate cvmath;
+# extern crate rand;
+# use cvmath::prelude::{Rect, Point2};
+use cvmath::sample;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+# fn main() {
+let mut rng = SmallRng::seed_from_u64(42);
+let bounds = Rect(Point2(0.0, 0.0), Point2(100.0, 50.0));
+let points = sample::poisson_disk(bounds, 5.0, &mut rng);
+
+// The points are at least the radius apart from each other
+for (i, a) in points.iter().enumerate() {
+    assert!(bounds.contains(a));
+    for b in &points[i + 1..] {
+        assert!(a.dist(*b) >= 5.0);
+    }
+}
+# }
+```
+*/
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+
+use num::{Float, CastFrom};
+use vec::Vec2;
+use point::{Point2, Point3};
+use bounds::{Rect, Cuboid};
+use distributions::{UnitCircle, UnitSphere};
+
+/// Generates points in the rectangle which are at least `radius` apart, using Bridson's algorithm.
+///
+/// Samples are added around existing points until a number of candidates in a row fail, which fills the bounds evenly.
+///
+/// Panics if the radius is not positive and finite, or so small relative to the bounds that the acceleration grid would exceed `2^24` cells.
+pub fn poisson_disk<T: Float, R: Rng + ?Sized>(bounds: Rect<T>, radius: T, rng: &mut R) -> Vec<Point2<T>>
+	where Standard: Distribution<T>, i32: CastFrom<T>
+{
+	bridson(bounds.mins.into(), bounds.maxs.into(), radius, rng, |rng| UnitCircle.sample(rng).into())
+		.into_iter().map(Point2::from).collect()
+}
+
+/// Generates points in the bounding box which are at least `radius` apart, using Bridson's algorithm.
+///
+/// Samples are added around existing points until a number of candidates in a row fail, which fills the bounds evenly.
+///
+/// Panics if the radius is not positive and finite, or so small relative to the bounds that the acceleration grid would exceed `2^24` cells.
+pub fn poisson_disk3<T: Float, R: Rng + ?Sized>(bounds: Cuboid<T>, radius: T, rng: &mut R) -> Vec<Point3<T>>
+	where Standard: Distribution<T>, i32: CastFrom<T>
+{
+	bridson(bounds.mins.into(), bounds.maxs.into(), radius, rng, |rng| UnitSphere.sample(rng).into())
+		.into_iter().map(Point3::from).collect()
+}
+
+/// Generates a jittered sample in each cell of an `nx` by `ny` grid over the unit square.
+///
+/// The samples are ordered row by row.
+///
+/// ```
+/// # extern crate cvmath;
+/// # extern crate rand;
+/// use cvmath::sample;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// # fn main() {
+/// let mut rng = SmallRng::seed_from_u64(7);
+/// let samples = sample::stratified::<f64, _>(4, 2, &mut rng);
+/// assert_eq!(samples.len(), 8);
+/// assert!(samples[5].x >= 0.25 && samples[5].x < 0.5 && samples[5].y >= 0.5);
+/// # }
+/// ```
+pub fn stratified<T: Float, R: Rng + ?Sized>(nx: usize, ny: usize, rng: &mut R) -> Vec<Vec2<T>> where Standard: Distribution<T> {
+	let (sx, sy) = (T::cast_from(nx as f64), T::cast_from(ny as f64));
+	let mut samples = Vec::with_capacity(nx * ny);
+	for j in 0..ny {
+		for i in 0..nx {
+			let x = (T::cast_from(i as f64) + rng.gen::<T>()) / sx;
+			let y = (T::cast_from(j as f64) + rng.gen::<T>()) / sy;
+			samples.push(Vec2 { x, y });
+		}
+	}
+	samples
+}
+
+/// Generates `n` multi-jittered samples in the unit square.
+///
+/// The samples are stratified over a grid of `m` by `k` cells and also in `n` strips along either axis.
+/// This guarantee holds when `n` is a product of two nearly equal factors such as `16` or `12`, otherwise the excess samples of the pattern are dropped.
+/// The samples are returned in random order.
+///
+/// ```
+/// # extern crate cvmath;
+/// # extern crate rand;
+/// use cvmath::sample;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// # fn main() {
+/// let mut rng = SmallRng::seed_from_u64(7);
+/// let samples = sample::multi_jittered::<f64, _>(16, &mut rng);
+/// // Exactly one sample in every vertical strip
+/// for strip in 0..16 {
+///     let (lo, hi) = (strip as f64 / 16.0, (strip + 1) as f64 / 16.0);
+///     assert_eq!(1, samples.iter().filter(|s| s.x >= lo && s.x < hi).count());
+/// }
+/// # }
+/// ```
+pub fn multi_jittered<T: Float, R: Rng + ?Sized>(n: usize, rng: &mut R) -> Vec<Vec2<T>> where Standard: Distribution<T> {
+	if n == 0 {
+		return Vec::new();
+	}
+	let m = (n as f64).sqrt().ceil() as usize;
+	let k = n.div_ceil(m);
+	let (fm, fk) = (T::cast_from(m as f64), T::cast_from(k as f64));
+
+	// Canonical arrangement, sub-strata of each cell index the other axis
+	let mut samples = Vec::with_capacity(m * k);
+	for j in 0..k {
+		for i in 0..m {
+			let (fi, fj) = (T::cast_from(i as f64), T::cast_from(j as f64));
+			let x = (fi + (fj + rng.gen::<T>()) / fk) / fm;
+			let y = (fj + (fi + rng.gen::<T>()) / fm) / fk;
+			samples.push(Vec2 { x, y });
+		}
+	}
+
+	// Shuffle the x sub-strata within each column and the y sub-strata within each row
+	for i in 0..m {
+		for j in (1..k).rev() {
+			let other = rng.gen_range(0..=j);
+			let (a, b) = (j * m + i, other * m + i);
+			let (xa, xb) = (samples[a].x, samples[b].x);
+			samples[a].x = xb;
+			samples[b].x = xa;
+		}
+	}
+	for j in 0..k {
+		for i in (1..m).rev() {
+			let other = rng.gen_range(0..=i);
+			let (a, b) = (j * m + i, j * m + other);
+			let (ya, yb) = (samples[a].y, samples[b].y);
+			samples[a].y = yb;
+			samples[b].y = ya;
+		}
+	}
+
+	// Random order so truncating doesn't drop whole rows
+	for i in (1..samples.len()).rev() {
+		let other = rng.gen_range(0..=i);
+		samples.swap(i, other);
+	}
+	samples.truncate(n);
+	samples
+}
+
+/// Number of candidates tried around each active point before it is retired.
+const CANDIDATES: usize = 30;
+
+/// Upper limit on the number of cells of the acceleration grid.
+const MAX_CELLS: f64 = (1 << 24) as f64;
+
+fn bridson<T: Float, R: Rng + ?Sized, F, const D: usize>(mins: [T; D], maxs: [T; D], radius: T, rng: &mut R, direction: F) -> Vec<[T; D]>
+	where Standard: Distribution<T>, i32: CastFrom<T>, F: Fn(&mut R) -> [T; D]
+{
+	assert!(radius > T::zero() && radius.is_finite(), "poisson disk radius must be positive and finite");
+	// Cells are small enough to hold at most one point
+	let cell_size = radius / T::cast_from(D as f64).sqrt();
+	let cells = (0..D).fold(T::one(), |acc, i| acc * ((maxs[i] - mins[i]) / cell_size).ceil().max(T::one()));
+	assert!(cells <= T::cast_from(MAX_CELLS), "poisson disk radius is too small for the bounds");
+	let dims: [usize; D] = ::std::array::from_fn(|i| (i32::cast_from(((maxs[i] - mins[i]) / cell_size).ceil()).max(1)) as usize);
+	let mut grid: Vec<Option<usize>> = vec![None; dims.iter().product()];
+	let cell_of = |pt: &[T; D]| -> [usize; D] {
+		::std::array::from_fn(|i| (i32::cast_from(((pt[i] - mins[i]) / cell_size).floor()).max(0) as usize).min(dims[i] - 1))
+	};
+	let index_of = |cell: &[usize; D]| cell.iter().zip(&dims).rev().fold(0, |acc, (&c, &d)| acc * d + c);
+
+	let mut points = Vec::new();
+	let mut active = Vec::new();
+
+	let first: [T; D] = ::std::array::from_fn(|i| mins[i] + (maxs[i] - mins[i]) * rng.gen::<T>());
+	grid[index_of(&cell_of(&first))] = Some(0);
+	points.push(first);
+	active.push(0);
+
+	let radius_sqr = radius * radius;
+	while !active.is_empty() {
+		let i = rng.gen_range(0..active.len());
+		let center = points[active[i]];
+		let mut found = false;
+		for _ in 0..CANDIDATES {
+			// Candidates in the annulus between the radius and twice the radius
+			let dir = direction(rng);
+			let dist = radius * (T::one() + rng.gen::<T>());
+			let candidate: [T; D] = ::std::array::from_fn(|i| center[i] + dir[i] * dist);
+			if (0..D).any(|i| candidate[i] < mins[i] || candidate[i] > maxs[i]) {
+				continue;
+			}
+			// Check the neighboring cells within two cells in every direction
+			let cell = cell_of(&candidate);
+			let mut too_close = false;
+			for offset in 0..5usize.pow(D as u32) {
+				let mut digits = offset;
+				let mut neighbor = [0; D];
+				let mut valid = true;
+				for i in 0..D {
+					let c = (cell[i] + digits % 5).wrapping_sub(2);
+					digits /= 5;
+					valid &= c < dims[i];
+					neighbor[i] = c;
+				}
+				if valid {
+					if let Some(j) = grid[index_of(&neighbor)] {
+						let other = &points[j];
+						if (0..D).fold(T::zero(), |acc, i| acc + (candidate[i] - other[i]) * (candidate[i] - other[i])) < radius_sqr {
+							too_close = true;
+							break;
+						}
+					}
+				}
+			}
+			if !too_close {
+				grid[index_of(&cell)] = Some(points.len());
+				active.push(points.len());
+				points.push(candidate);
+				found = true;
+				break;
+			}
+		}
+		if !found {
+			active.swap_remove(i);
+		}
+	}
+	points
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::SeedableRng;
+	use rand::rngs::SmallRng;
+
+	#[test]
+	fn poisson_disk3_spacing() {
+		let mut rng = SmallRng::seed_from_u64(1);
+		let bounds = Cuboid(Point3(-5.0, 0.0, 0.0), Point3(5.0, 10.0, 4.0));
+		let points = poisson_disk3(bounds, 1.5, &mut rng);
+		assert!(points.len() > 50);
+		for (i, a) in points.iter().enumerate() {
+			assert!(bounds.contains(a));
+			for b in &points[i + 1..] {
+				assert!(a.dist(*b) >= 1.5);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "positive")]
+	fn poisson_disk_zero_radius() {
+		let mut rng = SmallRng::seed_from_u64(1);
+		poisson_disk(Rect(Point2(0.0, 0.0), Point2(1.0, 1.0)), 0.0, &mut rng);
+	}
+
+	#[test]
+	#[should_panic(expected = "too small")]
+	fn poisson_disk_tiny_radius() {
+		let mut rng = SmallRng::seed_from_u64(1);
+		poisson_disk(Rect(Point2(0.0, 0.0), Point2(1e6, 1e6)), 1e-3, &mut rng);
+	}
+}
@@ -0,0 +1,190 @@
+/*!
+Serde support.
+
+Vectors and matrices serialize as fixed-size sequences, matrices in row-major order regardless of their memory layout.
+Angles serialize as their underlying value.
+
+Use the `named` helpers with `#[serde(with = "cvmath::serde_named")]` to serialize with struct-style field names instead.
+*/
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor, SeqAccess};
+use serde::ser::SerializeTuple;
+
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use mat::{Mat2, Affine2, Mat3, Affine3, Mat4};
+use angle::{Deg, Rad};
+
+macro_rules! serde_seq {
+	($ty:ident $N:tt { $($field:ident),+ }) => {
+		impl<T: Serialize> Serialize for $ty<T> {
+			fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				let mut tuple = serializer.serialize_tuple($N)?;
+				$(tuple.serialize_element(&self.$field)?;)+
+				tuple.end()
+			}
+		}
+		impl<'de, T: Deserialize<'de>> Deserialize<'de> for $ty<T> {
+			fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$ty<T>, D::Error> {
+				struct SeqVisitor<T>(PhantomData<T>);
+				impl<'de, T: Deserialize<'de>> Visitor<'de> for SeqVisitor<T> {
+					type Value = $ty<T>;
+					fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+						write!(f, "a sequence of {} elements", $N)
+					}
+					#[allow(unused_assignments)]
+					fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$ty<T>, A::Error> {
+						let mut len = 0;
+						$(
+							let $field = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(len, &self))?;
+							len += 1;
+						)+
+						Ok($ty { $($field),+ })
+					}
+				}
+				deserializer.deserialize_tuple($N, SeqVisitor(PhantomData))
+			}
+		}
+	};
+}
+
+serde_seq!(Vec1 1 { x });
+serde_seq!(Vec2 2 { x, y });
+serde_seq!(Vec3 3 { x, y, z });
+serde_seq!(Vec4 4 { x, y, z, w });
+
+serde_seq!(Mat2 4 { a11, a12, a21, a22 });
+serde_seq!(Affine2 6 { a11, a12, a13, a21, a22, a23 });
+serde_seq!(Mat3 9 { a11, a12, a13, a21, a22, a23, a31, a32, a33 });
+serde_seq!(Affine3 12 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34 });
+serde_seq!(Mat4 16 { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34, a41, a42, a43, a44 });
+
+macro_rules! serde_angle {
+	($ty:ident) => {
+		impl<T: Serialize> Serialize for $ty<T> {
+			fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				self.0.serialize(serializer)
+			}
+		}
+		impl<'de, T: Deserialize<'de>> Deserialize<'de> for $ty<T> {
+			fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$ty<T>, D::Error> {
+				T::deserialize(deserializer).map($ty)
+			}
+		}
+	};
+}
+
+serde_angle!(Deg);
+serde_angle!(Rad);
+
+//----------------------------------------------------------------
+
+/// Serializes with struct-style field names.
+///
+/// ```
+/// # extern crate cvmath;
+/// # extern crate serde;
+/// # extern crate serde_json;
+/// # use serde::{Serialize, Deserialize};
+/// use cvmath::prelude::Vec3;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Particle {
+///     #[serde(with = "cvmath::serde_named")]
+///     pos: Vec3<f32>,
+///     vel: Vec3<f32>,
+/// }
+///
+/// # fn main() {
+/// let p = Particle { pos: Vec3(1.0, 2.0, 3.0), vel: Vec3(0.0, 0.5, 0.0) };
+/// let json = serde_json::to_string(&p).unwrap();
+/// assert_eq!(json, r#"{"pos":{"x":1.0,"y":2.0,"z":3.0},"vel":[0.0,0.5,0.0]}"#);
+/// # }
+/// ```
+pub mod named {
+	use super::*;
+
+	/// Types which can be serialized with their field names.
+	pub trait SerializeNamed {
+		fn serialize_named<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+	}
+	/// Types which can be deserialized from their field names.
+	pub trait DeserializeNamed<'de>: Sized {
+		fn deserialize_named<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+	}
+
+	/// Serializes with struct-style field names.
+	pub fn serialize<V: SerializeNamed, S: Serializer>(value: &V, serializer: S) -> Result<S::Ok, S::Error> {
+		value.serialize_named(serializer)
+	}
+	/// Deserializes from struct-style field names.
+	pub fn deserialize<'de, V: DeserializeNamed<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<V, D::Error> {
+		V::deserialize_named(deserializer)
+	}
+
+	macro_rules! serde_named {
+		($ty:ident $name:tt { $($field:ident),+ }) => {
+			impl<T: Serialize> SerializeNamed for $ty<T> {
+				fn serialize_named<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+					#[derive(Serialize)]
+					#[serde(rename = $name)]
+					struct Named<'a, T: 'a> { $($field: &'a T),+ }
+					Named { $($field: &self.$field),+ }.serialize(serializer)
+				}
+			}
+			impl<'de, T: Deserialize<'de>> DeserializeNamed<'de> for $ty<T> {
+				fn deserialize_named<D: Deserializer<'de>>(deserializer: D) -> Result<$ty<T>, D::Error> {
+					#[derive(Deserialize)]
+					#[serde(rename = $name)]
+					struct Named<T> { $($field: T),+ }
+					let Named { $($field),+ } = Named::deserialize(deserializer)?;
+					Ok($ty { $($field),+ })
+				}
+			}
+		};
+	}
+
+	serde_named!(Vec1 "Vec1" { x });
+	serde_named!(Vec2 "Vec2" { x, y });
+	serde_named!(Vec3 "Vec3" { x, y, z });
+	serde_named!(Vec4 "Vec4" { x, y, z, w });
+
+	serde_named!(Mat2 "Mat2" { a11, a12, a21, a22 });
+	serde_named!(Affine2 "Affine2" { a11, a12, a13, a21, a22, a23 });
+	serde_named!(Mat3 "Mat3" { a11, a12, a13, a21, a22, a23, a31, a32, a33 });
+	serde_named!(Affine3 "Affine3" { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34 });
+	serde_named!(Mat4 "Mat4" { a11, a12, a13, a14, a21, a22, a23, a24, a31, a32, a33, a34, a41, a42, a43, a44 });
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate serde_json;
+	use super::*;
+	use bools::Bool3;
+
+	#[test]
+	fn roundtrip() {
+		let v = Vec3(1, -2, 3);
+		assert_eq!("[1,-2,3]", serde_json::to_string(&v).unwrap());
+		assert_eq!(v, serde_json::from_str("[1,-2,3]").unwrap());
+
+		let mask = Bool3(true, false, true);
+		assert_eq!(mask, serde_json::from_str(&serde_json::to_string(&mask).unwrap()).unwrap());
+
+		let m = Mat2::new(1, 2, 3, 4);
+		assert_eq!("[1,2,3,4]", serde_json::to_string(&m).unwrap());
+		assert_eq!(m, serde_json::from_str("[1,2,3,4]").unwrap());
+
+		assert_eq!("90.0", serde_json::to_string(&Deg(90.0)).unwrap());
+		assert_eq!(Rad(1.5), serde_json::from_str("1.5").unwrap());
+	}
+
+	#[test]
+	fn length_mismatch() {
+		assert!(serde_json::from_str::<Vec3<i32>>("[1,2]").is_err());
+		assert!(serde_json::from_str::<Vec2<i32>>("[1,2,3]").is_err());
+	}
+}
@@ -0,0 +1,433 @@
+/*!
+SIMD accelerated operations on `Vec4<f32>` and `Mat4<f32>`.
+
+These match their scalar counterparts on `Vec4<f32>` (up to rounding of the horizontal sums) but use SSE2 on `x86` and `x86_64`,
+and NEON on `aarch64`. Other targets fall back to the scalar implementation.
+
+The matrix products match `Mat4<f32>` exactly, the inverse uses the same cofactor expansion as `Mat4::inverse`
+and agrees with it up to rounding.
+
+### Examples
+
+```
+# #[macro_use] extern crate cvmath;
+# use cvmath::prelude::{Vec3, Vec4, Mat4, Bool4};
+use cvmath::simd;
+
+# fn main() {
+
+let a = Vec4(1.0, 2.0, 3.0, 4.0);
+let b = Vec4(4.0, -3.0, 2.0, 1.0);
+
+assert_eq!(a.dot(b), simd::dot(a, b));
+assert_eq!(a.len(), simd::len(a));
+assert_eq!(Vec4(1.0, -3.0, 2.0, 1.0), simd::min(a, b));
+assert_eq!(Vec4(4.0, 2.0, 3.0, 4.0), simd::max(a, b));
+assert_eq!(Vec4(1.0, -3.0, 3.0, 1.0), simd::select(Bool4(true, false, true, false), a, b));
+
+let m = Mat4::translate(Vec3(1.0, 2.0, 3.0)) * Mat4::scale(Vec3(2.0, 4.0, 8.0));
+assert_eq!(m * m, simd::mat4_mul(m, m));
+assert_eq!(m * a, simd::mat4_transform(m, a));
+assert_approx_eq!(m.inverse(), simd::mat4_inverse(m));
+# }
+```
+*/
+
+use vec::Vec4;
+use bools::Bool4;
+use mat::Mat4;
+
+/// Calculates the dot product.
+#[inline]
+pub fn dot(lhs: Vec4<f32>, rhs: Vec4<f32>) -> f32 {
+	imp::dot(lhs, rhs)
+}
+
+/// Calculates the squared length.
+#[inline]
+pub fn len_sqr(v: Vec4<f32>) -> f32 {
+	imp::dot(v, v)
+}
+
+/// Calculates the length.
+#[inline]
+pub fn len(v: Vec4<f32>) -> f32 {
+	imp::len(v)
+}
+
+/// Component wise minimum value.
+#[inline]
+pub fn min(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+	imp::min(lhs, rhs)
+}
+
+/// Component wise maximum value.
+#[inline]
+pub fn max(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+	imp::max(lhs, rhs)
+}
+
+/// Combines two vectors based on the mask, selecting components from the left-hand side if `true` and right-hand side if `false`.
+#[inline]
+pub fn select(mask: Bool4, lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+	imp::select(mask, lhs, rhs)
+}
+
+/// Multiplies the matrices.
+#[inline]
+pub fn mat4_mul(lhs: Mat4<f32>, rhs: Mat4<f32>) -> Mat4<f32> {
+	let rows = [imp::load(rhs.row(0)), imp::load(rhs.row(1)), imp::load(rhs.row(2)), imp::load(rhs.row(3))];
+	let row = |v: Vec4<f32>| {
+		let mut acc = imp::mul(imp::splat(v.x), rows[0]);
+		acc = imp::add(acc, imp::mul(imp::splat(v.y), rows[1]));
+		acc = imp::add(acc, imp::mul(imp::splat(v.z), rows[2]));
+		acc = imp::add(acc, imp::mul(imp::splat(v.w), rows[3]));
+		imp::store(acc)
+	};
+	Mat4::from_rows(row(lhs.row(0)), row(lhs.row(1)), row(lhs.row(2)), row(lhs.row(3)))
+}
+
+/// Transforms the vector by the matrix.
+#[inline]
+pub fn mat4_transform(m: Mat4<f32>, v: Vec4<f32>) -> Vec4<f32> {
+	// Accumulate the columns scaled by the components
+	let mut acc = imp::mul(imp::load(m.col(0)), imp::splat(v.x));
+	acc = imp::add(acc, imp::mul(imp::load(m.col(1)), imp::splat(v.y)));
+	acc = imp::add(acc, imp::mul(imp::load(m.col(2)), imp::splat(v.z)));
+	acc = imp::add(acc, imp::mul(imp::load(m.col(3)), imp::splat(v.w)));
+	imp::store(acc)
+}
+
+/// Calculates the inverse matrix.
+///
+/// Uses the cofactor expansion with the 2x2 sub-determinants of the columns computed four at a time.
+/// If the determinant is zero the matrix is returned unchanged, same as `Mat4::inverse`.
+pub fn mat4_inverse(m: Mat4<f32>) -> Mat4<f32> {
+	use self::imp::{shuffle, add, sub, mul};
+	let c = [imp::load(m.col(0)), imp::load(m.col(1)), imp::load(m.col(2)), imp::load(m.col(3))];
+
+	// Sub-determinants of the last two columns, see `Mat4::adjugate`
+	macro_rules! fac {
+		($p:literal, $q:literal) => {{
+			let p = shuffle::<{ $p * 0b01_01_01_01 }>(c[3], c[2]);
+			let q = shuffle::<{ $q * 0b01_01_01_01 }>(c[3], c[2]);
+			let lhs = mul(shuffle::<{ $q * 0b01_01_01_01 }>(c[2], c[1]), shuffle::<0b10_00_00_00>(p, p));
+			let rhs = mul(shuffle::<0b10_00_00_00>(q, q), shuffle::<{ $p * 0b01_01_01_01 }>(c[2], c[1]));
+			sub(lhs, rhs)
+		}};
+	}
+	let fac0 = fac!(3, 2);
+	let fac1 = fac!(3, 1);
+	let fac2 = fac!(2, 1);
+	let fac3 = fac!(3, 0);
+	let fac4 = fac!(2, 0);
+	let fac5 = fac!(1, 0);
+
+	// Elements of the first two columns broadcast in the order the cofactors need them
+	macro_rules! lanes {
+		($i:literal) => {{
+			let t = shuffle::<{ $i * 0b01_01_01_01 }>(c[1], c[0]);
+			shuffle::<0b10_10_10_00>(t, t)
+		}};
+	}
+	let (vec0, vec1, vec2, vec3) = (lanes!(0), lanes!(1), lanes!(2), lanes!(3));
+
+	let sign_a = imp::load(Vec4 { x: 1.0, y: -1.0, z: 1.0, w: -1.0 });
+	let sign_b = imp::load(Vec4 { x: -1.0, y: 1.0, z: -1.0, w: 1.0 });
+	let inv0 = mul(sign_b, add(sub(mul(vec1, fac0), mul(vec2, fac1)), mul(vec3, fac2)));
+	let inv1 = mul(sign_a, add(sub(mul(vec0, fac0), mul(vec2, fac3)), mul(vec3, fac4)));
+	let inv2 = mul(sign_b, add(sub(mul(vec0, fac1), mul(vec1, fac3)), mul(vec3, fac5)));
+	let inv3 = mul(sign_a, add(sub(mul(vec0, fac2), mul(vec1, fac4)), mul(vec2, fac5)));
+
+	// Expand the determinant along the first column
+	let row0 = shuffle::<0b00_00_00_00>(inv0, inv1);
+	let row1 = shuffle::<0b00_00_00_00>(inv2, inv3);
+	let det = imp::dot(m.col(0), imp::store(shuffle::<0b10_00_10_00>(row0, row1)));
+	if det == 0.0 {
+		return m;
+	}
+	let rcp = imp::splat(1.0 / det);
+	Mat4::from_cols(
+		imp::store(mul(inv0, rcp)),
+		imp::store(mul(inv1, rcp)),
+		imp::store(mul(inv2, rcp)),
+		imp::store(mul(inv3, rcp)),
+	)
+}
+
+// Portable equivalent of `_mm_shuffle_ps`
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse2"))))]
+#[inline]
+fn shuffle_lanes(mask: i32, lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+	let lane = |v: Vec4<f32>, i: i32| [v.x, v.y, v.z, v.w][((mask >> (2 * i)) & 3) as usize];
+	Vec4 { x: lane(lhs, 0), y: lane(lhs, 1), z: lane(rhs, 2), w: lane(rhs, 3) }
+}
+
+//----------------------------------------------------------------
+// SSE2
+
+#[cfg(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse2")))]
+mod imp {
+	#[cfg(target_arch = "x86")]
+	use std::arch::x86::*;
+	#[cfg(target_arch = "x86_64")]
+	use std::arch::x86_64::*;
+
+	use vec::Vec4;
+	use bools::Bool4;
+
+	#[inline]
+	pub fn load(v: Vec4<f32>) -> __m128 {
+		unsafe { _mm_loadu_ps(&v as *const Vec4<f32> as *const f32) }
+	}
+	#[inline]
+	pub fn store(v: __m128) -> Vec4<f32> {
+		let mut result = Vec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+		unsafe { _mm_storeu_ps(&mut result as *mut Vec4<f32> as *mut f32, v) };
+		result
+	}
+	#[inline]
+	pub fn splat(v: f32) -> __m128 {
+		unsafe { _mm_set1_ps(v) }
+	}
+	#[inline]
+	pub fn add(lhs: __m128, rhs: __m128) -> __m128 {
+		unsafe { _mm_add_ps(lhs, rhs) }
+	}
+	#[inline]
+	pub fn sub(lhs: __m128, rhs: __m128) -> __m128 {
+		unsafe { _mm_sub_ps(lhs, rhs) }
+	}
+	#[inline]
+	pub fn mul(lhs: __m128, rhs: __m128) -> __m128 {
+		unsafe { _mm_mul_ps(lhs, rhs) }
+	}
+	// Lanes `x` and `y` from `lhs` and `z` and `w` from `rhs` selected by the 2-bit fields of `MASK`, low bits first
+	#[inline]
+	pub fn shuffle<const MASK: i32>(lhs: __m128, rhs: __m128) -> __m128 {
+		unsafe { _mm_shuffle_ps::<MASK>(lhs, rhs) }
+	}
+	#[inline]
+	fn hadd(v: __m128) -> __m128 {
+		unsafe {
+			// Computes (x + y) + (z + w) in the lowest lane
+			let shuf = _mm_shuffle_ps(v, v, 0b10_11_00_01);
+			let sums = _mm_add_ps(v, shuf);
+			let high = _mm_movehl_ps(shuf, sums);
+			_mm_add_ss(sums, high)
+		}
+	}
+
+	#[inline]
+	pub fn dot(lhs: Vec4<f32>, rhs: Vec4<f32>) -> f32 {
+		unsafe { _mm_cvtss_f32(hadd(_mm_mul_ps(load(lhs), load(rhs)))) }
+	}
+	#[inline]
+	pub fn len(v: Vec4<f32>) -> f32 {
+		let v = load(v);
+		unsafe { _mm_cvtss_f32(_mm_sqrt_ss(hadd(_mm_mul_ps(v, v)))) }
+	}
+	#[inline]
+	pub fn min(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		store(unsafe { _mm_min_ps(load(lhs), load(rhs)) })
+	}
+	#[inline]
+	pub fn max(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		store(unsafe { _mm_max_ps(load(lhs), load(rhs)) })
+	}
+	#[inline]
+	pub fn select(mask: Bool4, lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		unsafe {
+			let mask = _mm_castsi128_ps(_mm_set_epi32(-(mask.w as i32), -(mask.z as i32), -(mask.y as i32), -(mask.x as i32)));
+			store(_mm_or_ps(_mm_and_ps(mask, load(lhs)), _mm_andnot_ps(mask, load(rhs))))
+		}
+	}
+}
+
+//----------------------------------------------------------------
+// NEON
+
+#[cfg(target_arch = "aarch64")]
+mod imp {
+	use std::arch::aarch64::*;
+
+	use vec::Vec4;
+	use bools::Bool4;
+
+	#[inline]
+	pub fn load(v: Vec4<f32>) -> float32x4_t {
+		unsafe { vld1q_f32(&v as *const Vec4<f32> as *const f32) }
+	}
+	#[inline]
+	pub fn store(v: float32x4_t) -> Vec4<f32> {
+		let mut result = Vec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+		unsafe { vst1q_f32(&mut result as *mut Vec4<f32> as *mut f32, v) };
+		result
+	}
+	#[inline]
+	pub fn splat(v: f32) -> float32x4_t {
+		unsafe { vdupq_n_f32(v) }
+	}
+	// Separate multiply and add instead of vfmaq_f32 to round the same as the scalar implementation
+	#[inline]
+	pub fn add(lhs: float32x4_t, rhs: float32x4_t) -> float32x4_t {
+		unsafe { vaddq_f32(lhs, rhs) }
+	}
+	#[inline]
+	pub fn sub(lhs: float32x4_t, rhs: float32x4_t) -> float32x4_t {
+		unsafe { vsubq_f32(lhs, rhs) }
+	}
+	#[inline]
+	pub fn mul(lhs: float32x4_t, rhs: float32x4_t) -> float32x4_t {
+		unsafe { vmulq_f32(lhs, rhs) }
+	}
+	#[inline]
+	pub fn shuffle<const MASK: i32>(lhs: float32x4_t, rhs: float32x4_t) -> float32x4_t {
+		load(super::shuffle_lanes(MASK, store(lhs), store(rhs)))
+	}
+
+	#[inline]
+	pub fn dot(lhs: Vec4<f32>, rhs: Vec4<f32>) -> f32 {
+		unsafe { vaddvq_f32(vmulq_f32(load(lhs), load(rhs))) }
+	}
+	#[inline]
+	pub fn len(v: Vec4<f32>) -> f32 {
+		dot(v, v).sqrt()
+	}
+	// vminq_f32 and vmaxq_f32 propagate NaN, compare and select instead to match the scalar semantics
+	#[inline]
+	pub fn min(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		unsafe {
+			let (lhs, rhs) = (load(lhs), load(rhs));
+			store(vbslq_f32(vcltq_f32(lhs, rhs), lhs, rhs))
+		}
+	}
+	#[inline]
+	pub fn max(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		unsafe {
+			let (lhs, rhs) = (load(lhs), load(rhs));
+			store(vbslq_f32(vcgtq_f32(lhs, rhs), lhs, rhs))
+		}
+	}
+	#[inline]
+	pub fn select(mask: Bool4, lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		unsafe {
+			let bits = [
+				(mask.x as u32).wrapping_neg(),
+				(mask.y as u32).wrapping_neg(),
+				(mask.z as u32).wrapping_neg(),
+				(mask.w as u32).wrapping_neg(),
+			];
+			store(vbslq_f32(vld1q_u32(bits.as_ptr()), load(lhs), load(rhs)))
+		}
+	}
+}
+
+//----------------------------------------------------------------
+// Scalar fallback
+
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "x86", target_feature = "sse2"), target_arch = "aarch64")))]
+mod imp {
+	use vec::Vec4;
+	use bools::Bool4;
+
+	#[inline]
+	pub fn load(v: Vec4<f32>) -> Vec4<f32> {
+		v
+	}
+	#[inline]
+	pub fn store(v: Vec4<f32>) -> Vec4<f32> {
+		v
+	}
+	#[inline]
+	pub fn splat(v: f32) -> Vec4<f32> {
+		Vec4::dup(v)
+	}
+	#[inline]
+	pub fn add(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		lhs + rhs
+	}
+	#[inline]
+	pub fn sub(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		lhs - rhs
+	}
+	#[inline]
+	pub fn mul(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		lhs * rhs
+	}
+	#[inline]
+	pub fn shuffle<const MASK: i32>(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		super::shuffle_lanes(MASK, lhs, rhs)
+	}
+
+	#[inline]
+	pub fn dot(lhs: Vec4<f32>, rhs: Vec4<f32>) -> f32 {
+		lhs.dot(rhs)
+	}
+	#[inline]
+	pub fn len(v: Vec4<f32>) -> f32 {
+		v.len()
+	}
+	#[inline]
+	pub fn min(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		lhs.min(rhs)
+	}
+	#[inline]
+	pub fn max(lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		lhs.max(rhs)
+	}
+	#[inline]
+	pub fn select(mask: Bool4, lhs: Vec4<f32>, rhs: Vec4<f32>) -> Vec4<f32> {
+		mask.select(lhs, rhs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_scalar() {
+		let a = Vec4(1.5f32, -2.0, 3.25, 0.5);
+		let b = Vec4(-0.5f32, 4.0, 3.25, -8.0);
+		assert_eq!(a.dot(b), dot(a, b));
+		assert_eq!(a.len_sqr(), len_sqr(a));
+		assert_eq!(a.len(), len(a));
+		assert_eq!(a.min(b), min(a, b));
+		assert_eq!(a.max(b), max(a, b));
+		let mask = Bool4(false, true, true, false);
+		assert_eq!(mask.select(a, b), select(mask, a, b));
+	}
+
+	#[test]
+	fn mat4_matches_scalar() {
+		let a = Mat4::new(
+			2.0f32, 0.0, 1.0, 3.0,
+			1.0, 1.0, 0.0, -1.0,
+			0.0, 4.0, 1.0, 2.0,
+			1.0, 0.0, 2.0, 1.0,
+		);
+		let b = Mat4::new(
+			0.5f32, -1.0, 2.0, 0.0,
+			3.0, 0.25, -2.0, 1.0,
+			1.0, 1.0, 1.0, 1.0,
+			-4.0, 0.0, 0.5, 2.0,
+		);
+		let v = Vec4(1.5f32, -2.0, 0.5, 1.0);
+		assert_eq!(a * b, mat4_mul(a, b));
+		assert_eq!(a * v, mat4_transform(a, v));
+
+		let inv = mat4_inverse(a);
+		for (x, y) in inv.to_rows_array().iter().zip(a.inverse().to_rows_array().iter()) {
+			assert!((x - y).abs() < 1e-5, "{} != {}", x, y);
+		}
+		for (x, y) in (a * inv).to_rows_array().iter().zip(Mat4::<f32>::identity().to_rows_array().iter()) {
+			assert!((x - y).abs() < 1e-5);
+		}
+
+		// Singular matrices are returned unchanged
+		let singular = Mat4::from_rows(a.row(0), a.row(1), a.row(0), a.row(3));
+		assert_eq!(singular, mat4_inverse(singular));
+		assert_eq!(singular.inverse(), mat4_inverse(singular));
+	}
+}
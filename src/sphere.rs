@@ -0,0 +1,45 @@
+/*!
+Sphere in 3D space.
+*/
+
+use num::Scalar;
+use vec::Vec3;
+use point::Point3;
+use bounds::Cuboid;
+
+/// Sphere in 3D space.
+///
+/// ```
+/// use cvmath::prelude::{Sphere, Point3, Cuboid};
+///
+/// let sphere = Sphere::new(Point3(1.0, 2.0, 3.0), 2.0);
+/// assert!(sphere.contains(Point3(1.0, 3.0, 4.0)));
+/// assert!(!sphere.contains(Point3(3.0, 3.0, 3.0)));
+/// assert_eq!(Cuboid(Point3(-1.0, 0.0, 1.0), Point3(3.0, 4.0, 5.0)), sphere.bounds());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Sphere<T> {
+	pub center: Point3<T>,
+	pub radius: T,
+}
+
+impl<T> Sphere<T> {
+	/// Constructs a new sphere.
+	pub fn new(center: Point3<T>, radius: T) -> Sphere<T> {
+		Sphere { center, radius }
+	}
+}
+
+impl<T: Scalar> Sphere<T> {
+	/// Returns whether the point lies inside or on the surface of the sphere.
+	pub fn contains(&self, pt: Point3<T>) -> bool {
+		self.center.dist_sqr(pt) <= self.radius * self.radius
+	}
+	/// Returns the bounds enclosing the sphere.
+	pub fn bounds(&self) -> Cuboid<T> {
+		let extent = Vec3::dup(self.radius);
+		Cuboid { mins: self.center - extent, maxs: self.center + extent }
+	}
+}
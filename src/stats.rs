@@ -0,0 +1,339 @@
+/*!
+Statistics of point sets.
+
+### Examples
+
+```
+use cvmath::prelude::Vec3;
+use cvmath::stats;
+
+// Points spread along the X axis and a little along the Y axis
+let points = [Vec3(-2.0f64, 0.0, 0.0), Vec3(2.0, 0.0, 0.0), Vec3(0.0, 1.0, 0.0), Vec3(0.0, -1.0, 0.0)];
+
+let (centroid, axes, variances) = stats::pca(&points);
+assert_eq!(Vec3(0.0, 0.0, 0.0), centroid);
+assert_eq!(Vec3(2.0, 0.5, 0.0), variances);
+assert_eq!(1.0, axes.x().x.abs());
+assert_eq!(1.0, axes.y().y.abs());
+```
+*/
+
+use std::ops;
+
+use num::Float;
+use vec::{Vec2, Vec3};
+use mat::{Mat2, Mat3};
+use bounds::Bounds;
+use batch;
+
+fn covariance_about<T: Float>(points: &[Vec3<T>], mean: Vec3<T>) -> Mat3<T> {
+	let zero = T::zero();
+	let (mut xx, mut xy, mut xz, mut yy, mut yz, mut zz) = (zero, zero, zero, zero, zero, zero);
+	for &pt in points {
+		let d = pt - mean;
+		xx += d.x * d.x;
+		xy += d.x * d.y;
+		xz += d.x * d.z;
+		yy += d.y * d.y;
+		yz += d.y * d.z;
+		zz += d.z * d.z;
+	}
+	let inv_n = T::one() / T::cast_from(points.len() as f64);
+	Mat3::new(
+		xx, xy, xz,
+		xy, yy, yz,
+		xz, yz, zz,
+	) * inv_n
+}
+
+/// Calculates the covariance matrix of the points.
+///
+/// This is the population covariance, divided by the number of points.
+/// Returns the null matrix if there are no points.
+pub fn covariance<T: Float>(points: &[Vec3<T>]) -> Mat3<T> {
+	match batch::centroid(points) {
+		Some(centroid) => covariance_about(points, centroid),
+		None => Mat3::null(),
+	}
+}
+
+/// Principal component analysis of the points.
+///
+/// Returns the centroid, the principal axes as the columns of an orthogonal matrix and the variance along each axis.
+/// The axes are sorted from largest to smallest variance, their signs are arbitrary.
+///
+/// Returns the origin, the identity matrix and zero variances if there are no points.
+pub fn pca<T: Float>(points: &[Vec3<T>]) -> (Vec3<T>, Mat3<T>, Vec3<T>) {
+	let centroid = match batch::centroid(points) {
+		Some(centroid) => centroid,
+		None => return (Vec3::origin(), Mat3::identity(), Vec3::origin()),
+	};
+	let (variances, axes) = covariance_about(points, centroid).eigen_symmetric();
+	(centroid, axes, variances)
+}
+
+fn covariance2_about<T: Float>(points: &[Vec2<T>], mean: Vec2<T>) -> Mat2<T> {
+	let zero = T::zero();
+	let (mut xx, mut xy, mut yy) = (zero, zero, zero);
+	for &pt in points {
+		let d = pt - mean;
+		xx += d.x * d.x;
+		xy += d.x * d.y;
+		yy += d.y * d.y;
+	}
+	let inv_n = T::one() / T::cast_from(points.len() as f64);
+	Mat2::new(
+		xx, xy,
+		xy, yy,
+	) * inv_n
+}
+
+fn centroid2<T: Float>(points: &[Vec2<T>]) -> Option<Vec2<T>> {
+	if points.is_empty() {
+		return None;
+	}
+	let sum = points.iter().fold(Vec2::<T>::origin(), |acc, &pt| acc + pt);
+	Some(sum / T::cast_from(points.len() as f64))
+}
+
+/// Calculates the covariance matrix of the 2D points.
+///
+/// This is the population covariance, divided by the number of points.
+/// Returns the null matrix if there are no points.
+pub fn covariance2<T: Float>(points: &[Vec2<T>]) -> Mat2<T> {
+	match centroid2(points) {
+		Some(centroid) => covariance2_about(points, centroid),
+		None => Mat2::null(),
+	}
+}
+
+/// Principal component analysis of the 2D points.
+///
+/// Returns the centroid, the principal axes as the columns of a rotation matrix and the variance along each axis.
+/// The axes are sorted from largest to smallest variance, their signs are arbitrary.
+///
+/// Returns the origin, the identity matrix and zero variances if there are no points.
+///
+/// ```
+/// use cvmath::prelude::Vec2;
+/// use cvmath::stats;
+///
+/// let points = [Vec2(-2.0f64, 0.0), Vec2(2.0, 0.0), Vec2(0.0, 1.0), Vec2(0.0, -1.0)];
+/// let (centroid, axes, variances) = stats::pca2(&points);
+/// assert_eq!(Vec2(0.0, 0.0), centroid);
+/// assert_eq!(Vec2(2.0, 0.5), variances);
+/// assert_eq!(1.0, axes.x().x.abs());
+/// ```
+pub fn pca2<T: Float>(points: &[Vec2<T>]) -> (Vec2<T>, Mat2<T>, Vec2<T>) {
+	let centroid = match centroid2(points) {
+		Some(centroid) => centroid,
+		None => return (Vec2::origin(), Mat2::identity(), Vec2::origin()),
+	};
+	let (variances, axes) = covariance2_about(points, centroid).eigen_symmetric();
+	(centroid, axes, variances)
+}
+
+//----------------------------------------------------------------
+// Weighted
+
+/// Calculates the weighted average of the points.
+///
+/// Returns `None` if the total weight is zero.
+///
+/// ```
+/// use cvmath::prelude::Vec2;
+/// use cvmath::stats;
+///
+/// let points = vec![(Vec2(0.0, 0.0), 1.0), (Vec2(4.0, 2.0), 3.0)];
+/// assert_eq!(Some(Vec2(3.0, 1.5)), stats::centroid_weighted(points));
+/// assert_eq!(None, stats::centroid_weighted(Vec::<(Vec2<f64>, f64)>::new()));
+/// ```
+pub fn centroid_weighted<T, V, I>(points: I) -> Option<V>
+	where T: Float, V: Default + ops::Add<Output = V> + ops::Mul<T, Output = V> + ops::Div<T, Output = V>, I: IntoIterator<Item = (V, T)>
+{
+	let (sum, weight) = points.into_iter().fold((V::default(), T::zero()), |(sum, weight), (pt, w)| (sum + pt * w, weight + w));
+	if weight != T::zero() { Some(sum / weight) } else { None }
+}
+
+/// Mass properties of a set of point masses.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MassProperties<T> {
+	/// Total mass.
+	pub mass: T,
+	/// Center of mass.
+	pub center: Vec3<T>,
+	/// Inertia tensor about the center of mass.
+	pub inertia: Mat3<T>,
+}
+
+/// Calculates the mass properties of the point masses.
+///
+/// The center of mass is the origin if the total mass is zero.
+///
+/// ```
+/// use cvmath::prelude::Vec3;
+/// use cvmath::stats;
+///
+/// // Dumbbell along the X axis
+/// let props = stats::mass_properties(&[(Vec3(-1.0, 0.0, 0.0), 2.0), (Vec3(3.0, 0.0, 0.0), 2.0)]);
+/// assert_eq!(4.0, props.mass);
+/// assert_eq!(Vec3(1.0, 0.0, 0.0), props.center);
+/// assert_eq!([[0.0, 0.0, 0.0], [0.0, 16.0, 0.0], [0.0, 0.0, 16.0]], props.inertia.into_row_major());
+/// ```
+pub fn mass_properties<T: Float>(points: &[(Vec3<T>, T)]) -> MassProperties<T> {
+	let mass = points.iter().fold(T::zero(), |acc, &(_, m)| acc + m);
+	let center = centroid_weighted(points.iter().cloned()).unwrap_or_else(Vec3::origin);
+	let zero = T::zero();
+	let (mut xx, mut xy, mut xz, mut yy, mut yz, mut zz) = (zero, zero, zero, zero, zero, zero);
+	for &(pt, m) in points {
+		let r = pt - center;
+		xx += m * (r.y * r.y + r.z * r.z);
+		yy += m * (r.x * r.x + r.z * r.z);
+		zz += m * (r.x * r.x + r.y * r.y);
+		xy -= m * r.x * r.y;
+		xz -= m * r.x * r.z;
+		yz -= m * r.y * r.z;
+	}
+	let inertia = Mat3::new(
+		xx, xy, xz,
+		xy, yy, yz,
+		xz, yz, zz,
+	);
+	MassProperties { mass, center, inertia }
+}
+
+//----------------------------------------------------------------
+// Streaming
+
+macro_rules! accumulator {
+	(
+		$(#[$meta:meta])*
+		$name:ident $vec:ident $mat:ident { $($sum:ident: $a:ident $b:ident),+ }
+	) => {
+		$(#[$meta])*
+		#[derive(Copy, Clone, Debug, Default, PartialEq)]
+		pub struct $name<T> {
+			count: usize,
+			shift: $vec<T>,
+			mean: $vec<T>,
+			bounds: Bounds<$vec<T>>,
+			$($sum: T,)+
+		}
+
+		impl<T: Float> $name<T> {
+			/// Constructs an empty accumulator.
+			pub fn new() -> $name<T> {
+				$name {
+					count: 0,
+					shift: $vec::origin(),
+					mean: $vec::origin(),
+					bounds: Bounds::point($vec::origin()),
+					$($sum: T::zero(),)+
+				}
+			}
+			/// Adds a point.
+			pub fn push(&mut self, pt: $vec<T>) {
+				self.count += 1;
+				if self.count == 1 {
+					// Accumulate relative to the first point to avoid precision loss with large offsets
+					self.shift = pt;
+					self.bounds = Bounds::point(pt);
+				}
+				else {
+					self.bounds = self.bounds.union(Bounds::point(pt));
+				}
+				// Welford's update of the mean and the sum of squared deviations
+				let pt = pt - self.shift;
+				let delta = pt - self.mean;
+				self.mean += delta / T::cast_from(self.count as f64);
+				let delta2 = pt - self.mean;
+				$(self.$sum += delta.$a * delta2.$b;)+
+			}
+			/// Returns the number of points.
+			pub fn count(&self) -> usize {
+				self.count
+			}
+			/// Returns the mean of the points, the origin if there are no points.
+			pub fn mean(&self) -> $vec<T> {
+				self.shift + self.mean
+			}
+			/// Returns the bounds of the points, `None` if there are no points.
+			pub fn bounds(&self) -> Option<Bounds<$vec<T>>> {
+				if self.count > 0 { Some(self.bounds) } else { None }
+			}
+			/// Returns the population covariance matrix of the points, the null matrix if there are no points.
+			pub fn covariance(&self) -> $mat<T> {
+				if self.count == 0 {
+					return $mat::null();
+				}
+				let inv_n = T::one() / T::cast_from(self.count as f64);
+				accumulator!(@cov self inv_n $mat)
+			}
+		}
+
+		impl<T: Float> Extend<$vec<T>> for $name<T> {
+			fn extend<I: IntoIterator<Item = $vec<T>>>(&mut self, iter: I) {
+				for pt in iter {
+					self.push(pt);
+				}
+			}
+		}
+	};
+	(@cov $self:ident $inv_n:ident Mat2) => {
+		Mat2::new(
+			$self.xx * $inv_n, $self.xy * $inv_n,
+			$self.xy * $inv_n, $self.yy * $inv_n,
+		)
+	};
+	(@cov $self:ident $inv_n:ident Mat3) => {
+		Mat3::new(
+			$self.xx * $inv_n, $self.xy * $inv_n, $self.xz * $inv_n,
+			$self.xy * $inv_n, $self.yy * $inv_n, $self.yz * $inv_n,
+			$self.xz * $inv_n, $self.yz * $inv_n, $self.zz * $inv_n,
+		)
+	};
+}
+
+accumulator! {
+	/// Streaming statistics of 2D points.
+	///
+	/// Ingests points one at a time and tracks the count, mean, bounds and covariance in a numerically stable way
+	/// without storing the points.
+	///
+	/// ```
+	/// use cvmath::prelude::{Vec2, Bounds};
+	/// use cvmath::stats::PointAccumulator2;
+	///
+	/// let mut acc = PointAccumulator2::new();
+	/// acc.extend(vec![Vec2(1.0, 2.0), Vec2(3.0, 2.0), Vec2(2.0, 5.0)]);
+	/// assert_eq!(3, acc.count());
+	/// assert_eq!(Vec2(2.0, 3.0), acc.mean());
+	/// assert_eq!(Some(Bounds(Vec2(1.0, 2.0), Vec2(3.0, 5.0))), acc.bounds());
+	/// assert_eq!([[2.0 / 3.0, 0.0], [0.0, 2.0]], acc.covariance().into_row_major());
+	/// ```
+	PointAccumulator2 Vec2 Mat2 { xx: x x, xy: x y, yy: y y }
+}
+
+accumulator! {
+	/// Streaming statistics of 3D points.
+	///
+	/// Ingests points one at a time and tracks the count, mean, bounds and covariance in a numerically stable way
+	/// without storing the points. The covariance matches [`covariance`](fn.covariance.html) of the collected points.
+	///
+	/// ```
+	/// use cvmath::prelude::Vec3;
+	/// use cvmath::stats::{self, PointAccumulator3};
+	///
+	/// // Large offsets would lose precision with the naive sum of squares
+	/// let points: Vec<Vec3<f64>> = (0..100).map(|i| Vec3(1e9 + i as f64, 1e9 - (i % 7) as f64, (i * i) as f64)).collect();
+	/// let mut acc = PointAccumulator3::new();
+	/// for &pt in &points {
+	///     acc.push(pt);
+	/// }
+	/// let expected = stats::covariance(&points).into_row_major();
+	/// for (a, b) in acc.covariance().into_row_major().iter().flatten().zip(expected.iter().flatten()) {
+	///     assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0));
+	/// }
+	/// ```
+	PointAccumulator3 Vec3 Mat3 { xx: x x, xy: x y, xz: x z, yy: y y, yz: y z, zz: z z }
+}
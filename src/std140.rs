@@ -0,0 +1,260 @@
+/*!
+GPU buffer layouts.
+
+GLSL uniform blocks use the `std140` layout rules and shader storage blocks typically use `std430`.
+Under both rules a `vec3` is aligned to 16 bytes and the columns of a `mat3` are padded to 16 bytes,
+which doesn't match the tightly packed `repr(C)` layout of this crate's types.
+
+The types in this module have the padding made explicit (and zeroed) so they can be uploaded with `as_bytes`.
+They assume 32-bit scalars (`float`, `int` and `uint`), GLSL matrices are column-major.
+Other scalar types would leave implicit padding bytes, so `as_bytes` is only available for `f32`, `i32` and `u32`.
+
+### Examples
+
+```
+# use cvmath::prelude::{Vec3, Mat3};
+use cvmath::std140::{AsStd140, Std140Vec3, Std140Mat3, Std140Mat4};
+use std::mem;
+
+assert_eq!(16, mem::size_of::<Std140Vec3<f32>>());
+assert_eq!(16, mem::align_of::<Std140Vec3<f32>>());
+assert_eq!(48, mem::size_of::<Std140Mat3<f32>>());
+assert_eq!(64, mem::size_of::<Std140Mat4<f32>>());
+
+let color = Vec3(1.0f32, 0.5, 0.25).as_std140();
+assert_eq!(16, color.as_bytes().len());
+
+let normal = Mat3::<f32>::identity().as_std140();
+assert_eq!(48, normal.as_bytes().len());
+```
+*/
+
+use std::{mem, slice};
+
+use vec::{Vec2, Vec3, Vec4};
+use mat::{Mat2, Mat3, Mat4};
+
+/// `vec2` aligned to 8 bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(8))]
+pub struct Std140Vec2<T> {
+	pub x: T,
+	pub y: T,
+}
+
+/// `vec3` padded and aligned to 16 bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Vec3<T> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	_pad: T,
+}
+
+/// `vec4` aligned to 16 bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Vec4<T> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	pub w: T,
+}
+
+/// `mat2` in the `std140` layout.
+///
+/// Every column is padded to 16 bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Mat2<T> {
+	pub x: Std140Vec2<T>,
+	_pad0: Std140Vec2<T>,
+	pub y: Std140Vec2<T>,
+	_pad1: Std140Vec2<T>,
+}
+
+/// `mat2` in the `std430` layout.
+///
+/// The columns are tightly packed.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(8))]
+pub struct Std430Mat2<T> {
+	pub x: Std140Vec2<T>,
+	pub y: Std140Vec2<T>,
+}
+
+/// `mat3` in the `std140` and `std430` layout.
+///
+/// Every column is padded to 16 bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Mat3<T> {
+	pub x: Std140Vec3<T>,
+	pub y: Std140Vec3<T>,
+	pub z: Std140Vec3<T>,
+}
+
+/// `mat4` in the `std140` layout.
+///
+/// The columns are `vec4` and need no padding.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C, align(16))]
+pub struct Std140Mat4<T> {
+	pub x: Std140Vec4<T>,
+	pub y: Std140Vec4<T>,
+	pub z: Std140Vec4<T>,
+	pub w: Std140Vec4<T>,
+}
+
+/// `mat4` in the `std430` layout, identical to the `std140` layout.
+pub type Std430Mat4<T> = Std140Mat4<T>;
+
+/// Converts to the `std140` layout.
+pub trait AsStd140 {
+	type Std140: Copy;
+	fn as_std140(&self) -> Self::Std140;
+}
+
+/// Converts to the `std430` layout.
+pub trait AsStd430 {
+	type Std430: Copy;
+	fn as_std430(&self) -> Self::Std430;
+}
+
+//----------------------------------------------------------------
+// Implementation
+
+macro_rules! as_bytes {
+	($ty:ident $($scalar:ty)*) => {
+		$(impl $ty<$scalar> {
+			/// Views the padded value as bytes for upload.
+			pub fn as_bytes(&self) -> &[u8] {
+				// With 32-bit scalars all padding is explicit and initialized
+				unsafe { slice::from_raw_parts(self as *const _ as *const u8, mem::size_of_val(self)) }
+			}
+		})*
+	};
+}
+
+as_bytes!(Std140Vec2 f32 i32 u32);
+as_bytes!(Std140Vec3 f32 i32 u32);
+as_bytes!(Std140Vec4 f32 i32 u32);
+as_bytes!(Std140Mat2 f32 i32 u32);
+as_bytes!(Std430Mat2 f32 i32 u32);
+as_bytes!(Std140Mat3 f32 i32 u32);
+as_bytes!(Std140Mat4 f32 i32 u32);
+
+impl<T: Copy> From<Vec2<T>> for Std140Vec2<T> {
+	fn from(v: Vec2<T>) -> Std140Vec2<T> {
+		Std140Vec2 { x: v.x, y: v.y }
+	}
+}
+impl<T: Copy + Default> From<Vec3<T>> for Std140Vec3<T> {
+	fn from(v: Vec3<T>) -> Std140Vec3<T> {
+		Std140Vec3 { x: v.x, y: v.y, z: v.z, _pad: T::default() }
+	}
+}
+impl<T: Copy> From<Vec4<T>> for Std140Vec4<T> {
+	fn from(v: Vec4<T>) -> Std140Vec4<T> {
+		Std140Vec4 { x: v.x, y: v.y, z: v.z, w: v.w }
+	}
+}
+impl<T: Copy + Default> From<Mat2<T>> for Std140Mat2<T> {
+	fn from(m: Mat2<T>) -> Std140Mat2<T> {
+		Std140Mat2 {
+			x: m.x().into(),
+			_pad0: Std140Vec2::default(),
+			y: m.y().into(),
+			_pad1: Std140Vec2::default(),
+		}
+	}
+}
+impl<T: Copy> From<Mat2<T>> for Std430Mat2<T> {
+	fn from(m: Mat2<T>) -> Std430Mat2<T> {
+		Std430Mat2 {
+			x: m.x().into(),
+			y: m.y().into(),
+		}
+	}
+}
+impl<T: Copy + Default> From<Mat3<T>> for Std140Mat3<T> {
+	fn from(m: Mat3<T>) -> Std140Mat3<T> {
+		Std140Mat3 {
+			x: m.x().into(),
+			y: m.y().into(),
+			z: m.z().into(),
+		}
+	}
+}
+
+impl<T: Copy> From<Mat4<T>> for Std140Mat4<T> {
+	fn from(m: Mat4<T>) -> Std140Mat4<T> {
+		Std140Mat4 {
+			x: m.x().into(),
+			y: m.y().into(),
+			z: m.z().into(),
+			w: m.w().into(),
+		}
+	}
+}
+
+macro_rules! impl_std140 {
+	($src:ident $dst:ident $dst430:ident $ty:ty) => {
+		impl AsStd140 for $src<$ty> {
+			type Std140 = $dst<$ty>;
+			fn as_std140(&self) -> $dst<$ty> { (*self).into() }
+		}
+		impl AsStd430 for $src<$ty> {
+			type Std430 = $dst430<$ty>;
+			fn as_std430(&self) -> $dst430<$ty> { (*self).into() }
+		}
+	};
+}
+
+impl_std140!(Vec2 Std140Vec2 Std140Vec2 f32);
+impl_std140!(Vec2 Std140Vec2 Std140Vec2 i32);
+impl_std140!(Vec2 Std140Vec2 Std140Vec2 u32);
+impl_std140!(Vec3 Std140Vec3 Std140Vec3 f32);
+impl_std140!(Vec3 Std140Vec3 Std140Vec3 i32);
+impl_std140!(Vec3 Std140Vec3 Std140Vec3 u32);
+impl_std140!(Vec4 Std140Vec4 Std140Vec4 f32);
+impl_std140!(Vec4 Std140Vec4 Std140Vec4 i32);
+impl_std140!(Vec4 Std140Vec4 Std140Vec4 u32);
+impl_std140!(Mat2 Std140Mat2 Std430Mat2 f32);
+impl_std140!(Mat3 Std140Mat3 Std140Mat3 f32);
+impl_std140!(Mat4 Std140Mat4 Std430Mat4 f32);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn layout() {
+		assert_eq!((8, 8), (mem::size_of::<Std140Vec2<f32>>(), mem::align_of::<Std140Vec2<f32>>()));
+		assert_eq!((16, 16), (mem::size_of::<Std140Vec3<f32>>(), mem::align_of::<Std140Vec3<f32>>()));
+		assert_eq!((16, 16), (mem::size_of::<Std140Vec4<f32>>(), mem::align_of::<Std140Vec4<f32>>()));
+		assert_eq!((32, 16), (mem::size_of::<Std140Mat2<f32>>(), mem::align_of::<Std140Mat2<f32>>()));
+		assert_eq!((16, 8), (mem::size_of::<Std430Mat2<f32>>(), mem::align_of::<Std430Mat2<f32>>()));
+		assert_eq!((48, 16), (mem::size_of::<Std140Mat3<f32>>(), mem::align_of::<Std140Mat3<f32>>()));
+		assert_eq!((64, 16), (mem::size_of::<Std140Mat4<f32>>(), mem::align_of::<Std140Mat4<f32>>()));
+	}
+
+	#[test]
+	fn columns() {
+		let m = Mat2::new(1.0f32, 2.0, 3.0, 4.0).as_std140();
+		assert_eq!(Std140Vec2 { x: 1.0, y: 3.0 }, m.x);
+		assert_eq!(Std140Vec2 { x: 2.0, y: 4.0 }, m.y);
+		let bytes = m.as_bytes();
+		assert_eq!(&bytes[8..16], &[0u8; 8]);
+	}
+
+	#[test]
+	fn mat4_columns() {
+		let m = Mat4::translate(Vec3(1.0f32, 2.0, 3.0));
+		let std140 = m.as_std140();
+		assert_eq!(Std140Vec4 { x: 1.0, y: 2.0, z: 3.0, w: 1.0 }, std140.w);
+		assert_eq!(std140, m.as_std430());
+		assert_eq!(64, std140.as_bytes().len());
+	}
+}
@@ -0,0 +1,107 @@
+/*!
+Proptest strategies.
+
+All vectors and angles implement proptest's `Arbitrary` when their components do, use `any::<Vec3<f32>>()` for unconstrained values.
+The functions in this module build more useful strategies for testing geometry code.
+
+```
+# #[macro_use] extern crate proptest;
+# extern crate cvmath;
+# use cvmath::prelude::Vec3;
+use cvmath::strategy;
+
+proptest! {
+    fn cross_is_orthogonal(a in strategy::unit_vec3::<f64>(), b in strategy::unit_vec3::<f64>()) {
+        let c = a.cross(b);
+        prop_assert!(c.dot(a).abs() < 1e-12);
+        prop_assert!(c.dot(b).abs() < 1e-12);
+    }
+}
+# fn main() { cross_is_orthogonal(); }
+```
+*/
+
+use std::ops::Range;
+
+use proptest::prelude::*;
+use proptest::arbitrary::{Arbitrary, any_with};
+use proptest::strategy::Map;
+
+use num::Float;
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use angle::{Deg, Rad};
+
+macro_rules! arbitrary_vec {
+	($vec:ident ($($T:ident),+)) => {
+		impl<T: Arbitrary> Arbitrary for $vec<T> {
+			type Parameters = <($($T,)+) as Arbitrary>::Parameters;
+			type Strategy = Map<<($($T,)+) as Arbitrary>::Strategy, fn(($($T,)+)) -> $vec<T>>;
+			fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+				any_with::<($($T,)+)>(args).prop_map($vec::from)
+			}
+		}
+	};
+}
+
+arbitrary_vec!(Vec1 (T));
+arbitrary_vec!(Vec2 (T, T));
+arbitrary_vec!(Vec3 (T, T, T));
+arbitrary_vec!(Vec4 (T, T, T, T));
+
+macro_rules! arbitrary_angle {
+	($ty:ident) => {
+		impl<T: Arbitrary> Arbitrary for $ty<T> {
+			type Parameters = T::Parameters;
+			type Strategy = Map<T::Strategy, fn(T) -> $ty<T>>;
+			fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+				any_with::<T>(args).prop_map($ty)
+			}
+		}
+	};
+}
+
+arbitrary_angle!(Deg);
+arbitrary_angle!(Rad);
+
+/// Vectors with components from the given strategy.
+pub fn vec2<S: Strategy + Clone>(s: S) -> impl Strategy<Value = Vec2<S::Value>> {
+	(s.clone(), s).prop_map(Vec2::from)
+}
+/// Vectors with components from the given strategy.
+pub fn vec3<S: Strategy + Clone>(s: S) -> impl Strategy<Value = Vec3<S::Value>> {
+	(s.clone(), s.clone(), s).prop_map(Vec3::from)
+}
+/// Vectors with components from the given strategy.
+pub fn vec4<S: Strategy + Clone>(s: S) -> impl Strategy<Value = Vec4<S::Value>> {
+	(s.clone(), s.clone(), s.clone(), s).prop_map(Vec4::from)
+}
+
+/// Finite floats, excludes infinities and NaN.
+pub fn finite_f32() -> impl Strategy<Value = f32> + Clone {
+	prop::num::f32::NORMAL | prop::num::f32::SUBNORMAL | prop::num::f32::ZERO
+}
+/// Finite floats, excludes infinities and NaN.
+pub fn finite_f64() -> impl Strategy<Value = f64> + Clone {
+	prop::num::f64::NORMAL | prop::num::f64::SUBNORMAL | prop::num::f64::ZERO
+}
+
+/// Uniformly distributed directions on the unit circle.
+pub fn unit_vec2<T: Float>() -> impl Strategy<Value = Vec2<T>> where Range<T>: Strategy<Value = T> {
+	(T::zero()..T::one()).prop_map(|t| {
+		let (y, x) = (Rad::turn() * t).sin_cos();
+		Vec2 { x, y }
+	})
+}
+/// Uniformly distributed directions on the unit sphere.
+pub fn unit_vec3<T: Float>() -> impl Strategy<Value = Vec3<T>> where Range<T>: Strategy<Value = T> {
+	(-T::one()..T::one(), T::zero()..T::one()).prop_map(|(z, t)| {
+		let r = (T::one() - z * z).sqrt();
+		let (sin, cos) = (Rad::turn() * t).sin_cos();
+		Vec3 { x: r * cos, y: r * sin, z }
+	})
+}
+
+/// Angles in the normalized range `[-π rad, π rad)`.
+pub fn norm_rad<T: Float>() -> impl Strategy<Value = Rad<T>> where Range<T>: Strategy<Value = T> {
+	(-T::one()..T::one()).prop_map(|t| Rad::half() * t)
+}
@@ -0,0 +1,116 @@
+/*!
+Continuous collision tests against moving boxes.
+
+The tests return the time of impact and the surface normal of the box at the point of impact.
+Time is measured in units of the ray direction and the velocities, at time `t` the ray is at `ray.at(t)` and the box has moved by `velocity * t`.
+
+### Examples
+
+```
+use cvmath::prelude::{Ray, Cuboid, Point3, Vec3};
+use cvmath::sweep;
+
+// A platform moving up towards a falling ray
+let platform = Cuboid(Point3(-1.0, -1.0, -1.0), Point3(1.0, 0.0, 1.0));
+let ray = Ray::new(Point3(0.0, 10.0, 0.0), Vec3(0.0, -1.0, 0.0));
+let hit = sweep::ray_moving_aabb(&ray, &platform, Vec3(0.0, 1.0, 0.0));
+assert_eq!(Some((5.0, Vec3(0.0, 1.0, 0.0))), hit);
+
+// The ray is moving away faster than the platform
+let hit = sweep::ray_moving_aabb(&ray, &platform, Vec3(0.0, -2.0, 0.0));
+assert_eq!(None, hit);
+```
+*/
+
+use num::Float;
+use vec::Vec3;
+use bounds::Cuboid;
+use ray::Ray;
+
+/// Sweeps a ray against a moving box.
+///
+/// Returns the smallest `t >= 0` where the ray touches the box and the outward normal of the face that was hit.
+/// If the ray starts inside the box the time is zero and the normal is zero.
+pub fn ray_moving_aabb<T: Float>(ray: &Ray<T>, aabb: &Cuboid<T>, aabb_velocity: Vec3<T>) -> Option<(T, Vec3<T>)> {
+	// In the frame of the box the ray moves with the relative velocity
+	let origin: [T; 3] = ray.origin.into();
+	let dir: [T; 3] = (ray.direction - aabb_velocity).into();
+	let mins: [T; 3] = aabb.mins.into();
+	let maxs: [T; 3] = aabb.maxs.into();
+
+	let mut t_enter = T::zero();
+	let mut t_exit = T::one() / T::zero();
+	let mut normal = [T::zero(); 3];
+	for i in 0..3 {
+		if dir[i] == T::zero() {
+			if origin[i] < mins[i] || origin[i] > maxs[i] {
+				return None;
+			}
+			continue;
+		}
+		let inv = T::one() / dir[i];
+		let (near, far, sign) = if dir[i] > T::zero() {
+			((mins[i] - origin[i]) * inv, (maxs[i] - origin[i]) * inv, -T::one())
+		}
+		else {
+			((maxs[i] - origin[i]) * inv, (mins[i] - origin[i]) * inv, T::one())
+		};
+		if near > t_enter {
+			t_enter = near;
+			normal = [T::zero(); 3];
+			normal[i] = sign;
+		}
+		t_exit = t_exit.min(far);
+		if t_enter > t_exit {
+			return None;
+		}
+	}
+	Some((t_enter, normal.into()))
+}
+
+/// Sweeps a moving box against another moving box.
+///
+/// The other box is expanded by the half extents of the box (their Minkowski sum) and the center of the box is swept against it.
+/// Returns the smallest `t >= 0` where the boxes touch and the outward normal of the other box's face that was hit.
+/// If the boxes overlap at the start the time is zero and the normal is zero.
+///
+/// ```
+/// use cvmath::prelude::{Cuboid, Point3, Vec3};
+/// use cvmath::sweep;
+///
+/// let player = Cuboid(Point3(0.0, 2.0, 0.0), Point3(1.0, 4.0, 1.0));
+/// let platform = Cuboid(Point3(-5.0, -1.0, -5.0), Point3(5.0, 0.0, 5.0));
+/// let hit = sweep::aabb_moving_aabb(&player, Vec3(0.0, -1.0, 0.0), &platform, Vec3(0.0, 1.0, 0.0));
+/// assert_eq!(Some((1.0, Vec3(0.0, 1.0, 0.0))), hit);
+/// ```
+pub fn aabb_moving_aabb<T: Float>(aabb: &Cuboid<T>, velocity: Vec3<T>, other: &Cuboid<T>, other_velocity: Vec3<T>) -> Option<(T, Vec3<T>)> {
+	let half = (aabb.maxs - aabb.mins) * T::cast_from(0.5);
+	let center = aabb.mins + half;
+	let expanded = Cuboid(other.mins - half, other.maxs + half);
+	ray_moving_aabb(&Ray::new(center, velocity), &expanded, other_velocity)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use point::Point3;
+
+	#[test]
+	fn ray_moving_aabb_cases() {
+		let aabb = Cuboid(Point3(1.0, 1.0, 1.0), Point3(2.0, 2.0, 2.0));
+		// Starts inside
+		let ray = Ray::new(Point3(1.5, 1.5, 1.5), Vec3(1.0, 0.0, 0.0));
+		assert_eq!(Some((0.0, Vec3(0.0, 0.0, 0.0))), ray_moving_aabb(&ray, &aabb, Vec3(5.0, 0.0, 0.0)));
+		// Static box, diagonal ray enters through the x face
+		let ray = Ray::new(Point3(0.0, 0.75, 1.5), Vec3(1.0, 0.5, 0.0));
+		assert_eq!(Some((1.0, Vec3(-1.0, 0.0, 0.0))), ray_moving_aabb(&ray, &aabb, Vec3(0.0, 0.0, 0.0)));
+		// Box moves out of the ray's path before it arrives
+		let ray = Ray::new(Point3(0.0, 1.5, 1.5), Vec3(1.0, 0.0, 0.0));
+		assert_eq!(None, ray_moving_aabb(&ray, &aabb, Vec3(0.0, 1.0, 0.0)));
+		// Box behind the ray
+		let ray = Ray::new(Point3(3.0, 1.5, 1.5), Vec3(1.0, 0.0, 0.0));
+		assert_eq!(None, ray_moving_aabb(&ray, &aabb, Vec3(0.0, 0.0, 0.0)));
+		// The box catches up with the ray from behind
+		assert_eq!(Some((1.0, Vec3(1.0, 0.0, 0.0))), ray_moving_aabb(&ray, &aabb, Vec3(2.0, 0.0, 0.0)));
+	}
+}
@@ -0,0 +1,107 @@
+/*!
+Tangent space for normal mapping.
+
+### Examples
+
+```
+use cvmath::prelude::{Vec2, Vec3, Vec4};
+use cvmath::tangent;
+
+// A quad in the XY plane with the texture mapped upright
+let positions = [Vec3(0.0, 0.0, 0.0), Vec3(2.0, 0.0, 0.0), Vec3(2.0, 2.0, 0.0), Vec3(0.0, 2.0, 0.0)];
+let uvs = [Vec2(0.0, 0.0), Vec2(1.0, 0.0), Vec2(1.0, 1.0), Vec2(0.0, 1.0)];
+let normals = [Vec3(0.0, 0.0, 1.0); 4];
+let indices = [0, 1, 2, 0, 2, 3];
+
+let (t, b) = tangent::compute([positions[0], positions[1], positions[2]], [uvs[0], uvs[1], uvs[2]]);
+assert_eq!(Vec3(2.0, 0.0, 0.0), t);
+assert_eq!(Vec3(0.0, 2.0, 0.0), b);
+
+let mut tangents = [Vec4::default(); 4];
+tangent::accumulate(&positions, &uvs, &normals, &indices, &mut tangents);
+assert_eq!([Vec4(1.0, 0.0, 0.0, 1.0); 4], tangents);
+```
+*/
+
+use num::Float;
+use vec::{Vec2, Vec3, Vec4};
+
+/// Computes the tangent and bitangent of the triangle.
+///
+/// The tangent points along the increasing U texture coordinate and the bitangent along the increasing V texture coordinate.
+/// They are not normalized, their lengths are the ratios of the triangle's size in model space to texture space.
+///
+/// Returns zero vectors if the texture coordinates are degenerate.
+pub fn compute<T: Float>(positions: [Vec3<T>; 3], uvs: [Vec2<T>; 3]) -> (Vec3<T>, Vec3<T>) {
+	let e1 = positions[1] - positions[0];
+	let e2 = positions[2] - positions[0];
+	let d1 = uvs[1] - uvs[0];
+	let d2 = uvs[2] - uvs[0];
+	let det = d1.x * d2.y - d2.x * d1.y;
+	if det == T::zero() {
+		return (Vec3::dup(T::zero()), Vec3::dup(T::zero()));
+	}
+	let r = T::one() / det;
+	let tangent = (e1 * d2.y - e2 * d1.y) * r;
+	let bitangent = (e2 * d1.x - e1 * d2.x) * r;
+	(tangent, bitangent)
+}
+
+/// Computes the per-vertex tangents of an indexed triangle mesh.
+///
+/// The tangents of the triangles sharing a vertex are averaged and orthogonalized against the vertex normal (Gram-Schmidt).
+/// The `w` component holds the handedness, the bitangent is `normal.cross(tangent.xyz()) * tangent.w`.
+///
+/// Vertices without a valid tangent get an arbitrary tangent perpendicular to the normal.
+///
+/// Panics if the `positions`, `uvs`, `normals` and `out` lengths don't match, or if an index is out of bounds.
+pub fn accumulate<T: Float>(positions: &[Vec3<T>], uvs: &[Vec2<T>], normals: &[Vec3<T>], indices: &[u32], out: &mut [Vec4<T>]) {
+	assert_eq!(positions.len(), uvs.len());
+	assert_eq!(positions.len(), normals.len());
+	assert_eq!(positions.len(), out.len());
+
+	let zero = Vec3::dup(T::zero());
+	let mut tangents = vec![zero; positions.len()];
+	let mut bitangents = vec![zero; positions.len()];
+	for tri in indices.chunks_exact(3) {
+		let (a, b, c) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+		let (t, bt) = compute([positions[a], positions[b], positions[c]], [uvs[a], uvs[b], uvs[c]]);
+		for &i in &[a, b, c] {
+			tangents[i] += t;
+			bitangents[i] += bt;
+		}
+	}
+
+	for i in 0..out.len() {
+		let n = normals[i];
+		let mut t = tangents[i] - n * n.dot(tangents[i]);
+		if t.len_sqr() == T::zero() {
+			t = n.cross(if n.x.abs() < n.z.abs() { Vec3::unit_x() } else { Vec3::unit_z() });
+		}
+		let t = t.norm();
+		let w = if n.cross(t).dot(bitangents[i]) < T::zero() { -T::one() } else { T::one() };
+		out[i] = t.vec4(w);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mirrored_handedness() {
+		let positions = [Vec3(0.0, 0.0, 0.0), Vec3(1.0, 0.0, 0.0), Vec3(0.0, 1.0, 0.0)];
+		let uvs = [Vec2(1.0, 0.0), Vec2(0.0, 0.0), Vec2(1.0, 1.0)];
+		let normals = [Vec3(0.0, 0.0, 1.0); 3];
+		let mut out = [Vec4::default(); 3];
+		accumulate(&positions, &uvs, &normals, &[0, 1, 2], &mut out);
+		assert_eq!([Vec4(-1.0, 0.0, 0.0, -1.0); 3], out);
+
+		// Degenerate texture coordinates still produce a tangent frame
+		accumulate(&positions, &[Vec2(0.0, 0.0); 3], &normals, &[0, 1, 2], &mut out);
+		for t in &out {
+			assert_eq!(0.0, t.xyz().dot(normals[0]));
+			assert_eq!(1.0, t.xyz().len());
+		}
+	}
+}
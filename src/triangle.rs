@@ -0,0 +1,142 @@
+/*!
+Triangles in 2D and 3D space.
+
+## Winding convention
+
+Winding is defined in a y-up coordinate system: counter-clockwise triangles have a positive signed area, `(b - a).cross(c - a) > 0`.
+In a y-down coordinate system, such as screen space and `Rect`, counter-clockwise triangles appear clockwise on screen.
+
+In 3D the counter-clockwise side is the front face, the normal `(b - a).cross(c - a)` points out of it (the OpenGL default).
+*/
+
+use num::{Scalar, Float};
+use point::{Point2, Point3};
+use vec::Vec3;
+
+/// Orientation of a triangle or polygon.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Winding {
+	/// Positive signed area.
+	CounterClockwise,
+	/// Negative signed area.
+	Clockwise,
+	/// Zero area, the points are collinear or coincide.
+	Degenerate,
+}
+
+impl Winding {
+	/// Returns the winding of the signed area.
+	pub fn from_signed_area<T: Scalar>(area: T) -> Winding {
+		if area > T::zero() { Winding::CounterClockwise }
+		else if area < T::zero() { Winding::Clockwise }
+		else { Winding::Degenerate }
+	}
+	/// Returns the opposite winding.
+	pub fn reverse(self) -> Winding {
+		match self {
+			Winding::CounterClockwise => Winding::Clockwise,
+			Winding::Clockwise => Winding::CounterClockwise,
+			Winding::Degenerate => Winding::Degenerate,
+		}
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Triangle in 2D space.
+///
+/// ```
+/// use cvmath::prelude::{Triangle2, Point2};
+/// use cvmath::triangle::Winding;
+///
+/// let tri = Triangle2::new(Point2(0.0, 0.0), Point2(2.0, 0.0), Point2(0.0, 1.0));
+/// assert_eq!(1.0, tri.signed_area());
+/// assert_eq!(Winding::CounterClockwise, tri.winding());
+/// assert_eq!(Winding::Clockwise, tri.flip().winding());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Triangle2<T> {
+	pub a: Point2<T>,
+	pub b: Point2<T>,
+	pub c: Point2<T>,
+}
+
+impl<T> Triangle2<T> {
+	/// Constructs a new triangle.
+	pub fn new(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> Triangle2<T> {
+		Triangle2 { a, b, c }
+	}
+	/// Returns the triangle with the opposite winding.
+	pub fn flip(self) -> Triangle2<T> {
+		Triangle2 { a: self.a, b: self.c, c: self.b }
+	}
+}
+
+impl<T: Scalar> Triangle2<T> {
+	/// Returns the signed area, positive if the triangle winds counter-clockwise.
+	pub fn signed_area(&self) -> T {
+		(self.b - self.a).cross(self.c - self.a) / (T::one() + T::one())
+	}
+	/// Returns the area.
+	pub fn area(&self) -> T {
+		self.signed_area().abs()
+	}
+	/// Returns the winding of the triangle.
+	pub fn winding(&self) -> Winding {
+		Winding::from_signed_area(self.signed_area())
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Triangle in 3D space.
+///
+/// ```
+/// use cvmath::prelude::{Triangle3, Point3, Vec3};
+///
+/// // Counter-clockwise when seen from +z looking down the -z axis
+/// let tri = Triangle3::new(Point3(0.0, 0.0, 0.0), Point3(1.0, 0.0, 0.0), Point3(0.0, 1.0, 0.0));
+/// assert_eq!(Vec3(0.0, 0.0, 1.0), tri.normal());
+/// assert!(tri.is_front_facing(Vec3(0.0, 0.0, -1.0)));
+/// assert!(!tri.is_front_facing(Vec3(0.0, 0.0, 1.0)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Triangle3<T> {
+	pub a: Point3<T>,
+	pub b: Point3<T>,
+	pub c: Point3<T>,
+}
+
+impl<T> Triangle3<T> {
+	/// Constructs a new triangle.
+	pub fn new(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Triangle3<T> {
+		Triangle3 { a, b, c }
+	}
+	/// Returns the triangle with the opposite winding.
+	pub fn flip(self) -> Triangle3<T> {
+		Triangle3 { a: self.a, b: self.c, c: self.b }
+	}
+}
+
+impl<T: Scalar> Triangle3<T> {
+	/// Returns the normal of the front face.
+	///
+	/// The normal is not normalized, its length equals twice the area.
+	pub fn normal(&self) -> Vec3<T> {
+		(self.b - self.a).cross(self.c - self.a)
+	}
+	/// Returns the area.
+	pub fn area(&self) -> T where T: Float {
+		self.normal().len() / (T::one() + T::one())
+	}
+	/// Returns whether the front face is visible when looking along the view direction.
+	///
+	/// The view direction points from the eye into the scene, edge-on and degenerate triangles are not front facing.
+	pub fn is_front_facing(&self, view_dir: Vec3<T>) -> bool {
+		self.normal().dot(view_dir) < T::zero()
+	}
+}
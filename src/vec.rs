@@ -11,9 +11,9 @@ An overview of their implementations:
 
 `Eq`, `PartialEq` where T: `Eq`, `PartialEq`: Compares if _all_ the underlying components are equal.
 
-`Ord`, `PartialOrd` where T: `Ord`, `PartialOrd`
+`Ord`, `PartialOrd` where T: `Ord`, `PartialOrd`: Compares lexicographically, not component-wise, see [`partial_dominance`](../bools/index.html#ordering). Float vectors aren't `Ord`, use `total_cmp` or wrap them in [`OrderedVec`](../key/struct.OrderedVec.html) to sort them.
 
-`Hash` where T: `Hash`
+`Hash` where T: `Hash`: Float vectors aren't `Hash`, wrap them in [`HashableVec`](../key/struct.HashableVec.html) to use them as keys.
 
 ### Examples
 
@@ -70,10 +70,12 @@ assert_eq!(Vec3 { x: 5, y: -2, z: 5 }, Vec3(-2, 12, 5).shuffle(Z, X, Z));
 
 `xyz(self)`: Drops the `w` component from `Vec4`.
 
+`vec3a(self)`, `vec3(self)`: Converts between the packed `Vec3` and the 16-byte aligned `Vec3A`.
+
 ### Examples
 
 ```
-# use cvmath::prelude::{Vec2, Vec3, Vec4};
+# use cvmath::prelude::{Vec2, Vec3, Vec3A, Vec4};
 assert_eq!(Vec3 { x: 3, y: 4, z: 5 }, Vec2(3, 4).vec3(5));
 
 assert_eq!(Vec4 { x: -1, y: -2, z: -3, w: -4 }, Vec3(-1, -2, -3).vec4(-4));
@@ -82,6 +84,10 @@ assert_eq!(Vec2 { x: 2, y: 1 }, Vec3(2, 1, 0).xy());
 assert_eq!(Vec2 { x: 1, y: 2 }, Vec4(1, 2, 3, 4).xy());
 
 assert_eq!(Vec3 { x: 1, y: 2, z: 3 }, Vec4(1, 2, 3, 4).xyz());
+
+assert_eq!(Vec3A { x: 1.0, y: 2.0, z: 3.0 }, Vec3(1.0, 2.0, 3.0).vec3a());
+assert_eq!(Vec3(1.0, 2.0, 3.0), Vec3A(1.0, 2.0, 3.0).vec3());
+assert_eq!(16, std::mem::size_of::<Vec3A<f32>>());
 ```
 
 ## Transformations
@@ -164,6 +170,10 @@ assert_eq!(Vec2::<i32>::from((2, 3)), Vec2::from([2, 3]));
 
 `mul_add(self, vec, scale)`: Adds the scaled value.
 
+`wrap(self, bounds)` where T: `Float`: Wraps the position into a periodic domain.
+
+`wrap_delta(self, to, bounds)` where T: `Float`: Calculates the shortest difference vector to another position in a periodic domain.
+
 Exclusive to `Vec2`:
 
 `polar_angle(self)`: Calculates the polar angle.
@@ -176,10 +186,22 @@ Exclusive to `Vec2`:
 
 `hsub(self)`: Horizontal subtracts y from x.
 
+`scale_to_fit(self, container)`, `scale_to_cover(self, container)`: Calculates the uniform scale to fit a size inside or to cover a container.
+
 Exclusive to `Vec3`:
 
 `cross(self, rhs)`: Calculates the 3D cross product.
 
+Exclusive to `Vec4`:
+
+`hdiv(self)`: Homogeneous divide.
+
+`dot3(self, rhs)`: Calculates the inner product with a `Vec3`, ignoring `w`.
+
+`dot4h(self, point)`: Calculates the inner product with a `Vec3` point extended with `w = 1`.
+
+`normalize_plane(self)` where T: `Float`: Normalizes plane coefficients so the normal has unit length.
+
 ### Examples
 
 ```
@@ -235,21 +257,37 @@ assert_eq!(Vec3 { x: -12, y: 1, z: 39 }, Vec3::cross(Vec3(3, -3, 1), Vec3(4, 9,
 
 */
 
-use std::{fmt, mem, ops, slice};
+use std::{cmp, fmt, mem, ops, slice};
 
-use num::{Scalar, Zero, One, Float, CastTo, Extrema, SpatialOrd};
+use num::{Scalar, Zero, One, Float, CastTo, Extrema, SpatialOrd, CanonicalBits, TotalOrd};
 
 use angle::Rad;
+use error::MathError;
+use bounds::Bounds;
 
-// /// A 1-dimensional vector.
-// #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
-// #[repr(C)]
-// pub struct Vec1<T> {
-// 	pub x: T,
-// }
+/// A 1-dimensional vector.
+///
+/// Shares the API of the other vector types so dimension generic code, such as animation tracks, works with scalars too.
+///
+/// ```
+/// # use cvmath::prelude::{Vec1, Vec2, Bool1};
+/// let a = Vec1(2.0);
+/// let b = Vec1(6.0);
+/// assert_eq!(Vec1(3.0), a.lerp(b, 0.25));
+/// assert_eq!(4.0, a.dist(b));
+/// assert_eq!(Bool1 { x: true }, a.lt(b));
+/// assert_eq!(Vec2(2.0, 1.0), a.vec2(1.0));
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
+pub struct Vec1<T> {
+	pub x: T,
+}
 
 /// A 2-dimensional vector.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Vec2<T> {
 	pub x: T,
@@ -258,6 +296,7 @@ pub struct Vec2<T> {
 
 /// A 3-dimensional vector.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Vec3<T> {
 	pub x: T,
@@ -265,8 +304,21 @@ pub struct Vec3<T> {
 	pub z: T,
 }
 
+/// A 3-dimensional vector padded and aligned to 16 bytes.
+///
+/// Has the same API as `Vec3`, the padding allows aligned loads in hot loops and SIMD code.
+/// Convert to `Vec3` for tightly packed storage.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(C, align(16))]
+pub struct Vec3A<T> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+}
+
 /// A 4-dimensional vector.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 #[repr(C)]
 pub struct Vec4<T> {
 	pub x: T,
@@ -291,6 +343,10 @@ pub trait ComponentImpl<T, C> {
 	fn get(self) -> T;
 }
 
+impl<T> ComponentImpl<T, X> for Vec1<T> {
+	fn get(self) -> T { self.x }
+}
+
 impl<T> ComponentImpl<T, X> for Vec2<T> {
 	fn get(self) -> T { self.x }
 }
@@ -308,6 +364,16 @@ impl<T> ComponentImpl<T, Z> for Vec3<T> {
 	fn get(self) -> T { self.z }
 }
 
+impl<T> ComponentImpl<T, X> for Vec3A<T> {
+	fn get(self) -> T { self.x }
+}
+impl<T> ComponentImpl<T, Y> for Vec3A<T> {
+	fn get(self) -> T { self.y }
+}
+impl<T> ComponentImpl<T, Z> for Vec3A<T> {
+	fn get(self) -> T { self.z }
+}
+
 impl<T> ComponentImpl<T, X> for Vec4<T> {
 	fn get(self) -> T { self.x }
 }
@@ -340,6 +406,14 @@ macro_rules! unit {
 		/// Unit vector in the `z` direction.
 		pub fn unit_z() -> Vec3<T> where T: Zero + One { Vec3 { x: T::zero(), y: T::zero(), z: T::one() } }
 	};
+	(Vec3A) => {
+		/// Unit vector in the `x` direction.
+		pub fn unit_x() -> Vec3A<T> where T: Zero + One { Vec3A { x: T::one(), y: T::zero(), z: T::zero() } }
+		/// Unit vector in the `y` direction.
+		pub fn unit_y() -> Vec3A<T> where T: Zero + One { Vec3A { x: T::zero(), y: T::one(), z: T::zero() } }
+		/// Unit vector in the `z` direction.
+		pub fn unit_z() -> Vec3A<T> where T: Zero + One { Vec3A { x: T::zero(), y: T::zero(), z: T::one() } }
+	};
 	(Vec4) => {
 		/// Unit vector in the `x` direction.
 		pub fn unit_x() -> Vec4<T> where T: Zero + One { Vec4 { x: T::one(), y: T::zero(), z: T::zero(), w: T::zero() } }
@@ -355,7 +429,7 @@ macro_rules! unit {
 macro_rules! with {
 	(Vec1) => {
 		/// Sets the `x` component.
-		pub fn with_x(self, x: T) { Vec1 { x } }
+		pub fn with_x(self, x: T) -> Vec1<T> { Vec1 { x } }
 	};
 	(Vec2) => {
 		/// Sets the `x` component.
@@ -371,6 +445,14 @@ macro_rules! with {
 		/// Sets the `z` component.
 		pub fn with_z(self, z: T) -> Vec3<T> { Vec3 { x: self.x, y: self.y, z } }
 	};
+	(Vec3A) => {
+		/// Sets the `x` component.
+		pub fn with_x(self, x: T) -> Vec3A<T> { Vec3A { x, y: self.y, z: self.z } }
+		/// Sets the `y` component.
+		pub fn with_y(self, y: T) -> Vec3A<T> { Vec3A { x: self.x, y, z: self.z } }
+		/// Sets the `z` component.
+		pub fn with_z(self, z: T) -> Vec3A<T> { Vec3A { x: self.x, y: self.y, z } }
+	};
 	(Vec4) => {
 		/// Sets the `x` component.
 		pub fn with_x(self, x: T) -> Vec4<T> { Vec4 { x, y: self.y, z: self.z, w: self.w } }
@@ -399,12 +481,24 @@ macro_rules! cvt {
 		pub fn vec4(self, w: T) -> Vec4<T> { Vec4 { x: self.x, y: self.y, z: self.z, w } }
 		/// Drops the `z` component.
 		pub fn xy(self) -> Vec2<T> { Vec2 { x: self.x, y: self.y } }
+		/// Pads to 16 bytes.
+		pub fn vec3a(self) -> Vec3A<T> { Vec3A { x: self.x, y: self.y, z: self.z } }
+	};
+	(Vec3A) => {
+		/// Extends the 3D vector with a `w` component.
+		pub fn vec4(self, w: T) -> Vec4<T> { Vec4 { x: self.x, y: self.y, z: self.z, w } }
+		/// Drops the `z` component.
+		pub fn xy(self) -> Vec2<T> { Vec2 { x: self.x, y: self.y } }
+		/// Drops the padding.
+		pub fn vec3(self) -> Vec3<T> { Vec3 { x: self.x, y: self.y, z: self.z } }
 	};
 	(Vec4) => {
 		/// Drops the `z` and `w` coordinates.
 		pub fn xy(self) -> Vec2<T> { Vec2 { x: self.x, y: self.y } }
 		/// Drops the `w` component.
 		pub fn xyz(self) -> Vec3<T> { Vec3 { x: self.x, y: self.y, z: self.z } }
+		/// Drops the `w` component and pads to 16 bytes.
+		pub fn xyz_a(self) -> Vec3A<T> { Vec3A { x: self.x, y: self.y, z: self.z } }
 	};
 }
 
@@ -450,6 +544,15 @@ macro_rules! parse_vec_elems {
 	}};
 }
 
+// Accumulates with `mul_add`, fused with the `fma` feature.
+macro_rules! dot {
+	($lhs:ident, $rhs:ident; $field:ident $(, $tail:ident)*) => {{
+		let acc = $lhs.$field * $rhs.$field;
+		$(let acc = $lhs.$tail.mul_add($rhs.$tail, acc);)*
+		acc
+	}};
+}
+
 // This may or may not be horrible abuse of the `macro_rules!` system :)
 macro_rules! vec {
 	(
@@ -580,12 +683,6 @@ macro_rules! vec {
 				<Self as AsRef<[T; $N]>>::as_ref(self)
 			}
 		}
-		impl<T> $vec<T> {
-			pub fn as_bytes(&self) -> &[u8] {
-				unsafe { slice::from_raw_parts(self as *const _ as *const u8, mem::size_of_val(self)) }
-			}
-		}
-
 		impl<T> AsMut<($($T,)+)> for $vec<T> {
 			fn as_mut(&mut self) -> &mut ($($T,)+) {
 				unsafe { mem::transmute(self) }
@@ -631,7 +728,7 @@ macro_rules! vec {
 			/// assert_eq!(49, this.len_sqr());
 			/// ```
 			pub fn len_sqr(self) -> T {
-				infix!(+ $(self.$field * self.$field),+)
+				dot!(self, self; $($field),+)
 			}
 			/// Calculates the length of the vector.
 			///
@@ -646,6 +743,30 @@ macro_rules! vec {
 			pub fn len(self) -> T where T: Float {
 				self.len_sqr().sqrt()
 			}
+			/// Calculates the length of the vector without intermediate overflow or underflow.
+			///
+			/// Slower than `len` but scales the components by the largest magnitude first,
+			/// so very large or very small components don't overflow to infinity or underflow to zero.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2, Vec3};
+			/// let this = Vec2 { x: 3.0e30f32, y: 4.0e30 };
+			/// assert_eq!(f32::INFINITY, this.len());
+			/// assert_eq!(5.0e30, this.len_stable());
+			///
+			/// let this = Vec3 { x: 2.0e-30f32, y: -3.0e-30, z: 6.0e-30 };
+			/// assert_eq!(0.0, this.len());
+			/// assert!((this.len_stable() / 7.0e-30 - 1.0).abs() < 1e-6);
+			/// ```
+			pub fn len_stable(self) -> T where T: Float {
+				let scale = fold!(T::max, $(self.$field.abs()),+);
+				if scale > T::zero() && scale.is_finite() {
+					(self / scale).len() * scale
+				}
+				else {
+					scale
+				}
+			}
 			/// Calculates the manhattan length of the vector.
 			///
 			/// <!--LEN_HAT--><svg width="400" height="120" font-family="monospace" xmlns="http://www.w3.org/2000/svg"><path fill="none" d="M40 100 L360.5 20 M353.70688 25.818361 L360.5 20 L351.76944 18.056509" stroke="black" /><path fill="none" d="M40 100 L360.5 100 M352.5 104 L360.5 100 L352.5 96" stroke="grey" stroke-width="0.5" /><path fill="none" d="M360.5 100 L360.5 20 M364.5 28 L360.5 20 L356.5 28" stroke="grey" stroke-width="0.5" /><circle cx="40" cy="100" r="2" /><text x="365.5" y="20">this</text><text x="200.25" y="115" fill="grey">x</text><text x="365.5" y="60" fill="grey">y</text></svg>
@@ -736,6 +857,62 @@ macro_rules! vec {
 					(self, self_len)
 				}
 			}
+			/// Normalizes the vector.
+			///
+			/// Returns `Err(MathError::ZeroLength)` if the vector has zero length or isn't finite.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// # use cvmath::error::MathError;
+			/// let this = Vec2 { x: 3.0, y: -4.0 };
+			/// assert_eq!(Ok(Vec2(0.6, -0.8)), this.try_norm());
+			///
+			/// let this = Vec2 { x: 0.0, y: 0.0 };
+			/// assert_eq!(Err(MathError::ZeroLength), this.try_norm());
+			/// ```
+			pub fn try_norm(self) -> Result<$vec<T>, MathError> where T: Float {
+				let self_len = self.len();
+				if self_len > T::zero() && self_len.is_finite() {
+					Ok(self / self_len)
+				}
+				else {
+					Err(MathError::ZeroLength)
+				}
+			}
+			/// Approximates the reciprocal length of the vector.
+			///
+			/// Uses a fast reciprocal square root estimate refined with one Newton-Raphson iteration, the relative error is below `0.2%`.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// let this = Vec2 { x: 3.0f32, y: 4.0 };
+			/// assert!((this.len_recip_fast() - 0.2).abs() < 0.2 * 0.002);
+			/// ```
+			pub fn len_recip_fast(self) -> T where T: Float {
+				self.len_sqr().rsqrt_fast()
+			}
+			/// Approximately normalizes the vector.
+			///
+			/// Faster than `norm` but the resulting length is only accurate up to `0.2%`, see `len_recip_fast`.
+			/// The null vector remains null.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec3};
+			/// let this = Vec3 { x: 2.0f32, y: -3.0, z: 6.0 };
+			/// assert!((this.norm_fast().len() - 1.0).abs() < 0.002);
+			///
+			/// let this = Vec3 { x: 0.0f32, y: 0.0, z: 0.0 };
+			/// assert_eq!(this, this.norm_fast());
+			/// ```
+			pub fn norm_fast(self) -> $vec<T> where T: Float {
+				let len_sqr = self.len_sqr();
+				if len_sqr > T::zero() {
+					self * len_sqr.rsqrt_fast()
+				}
+				else {
+					self
+				}
+			}
 			/// Resizes the vector to the given length.
 			///
 			/// The null vector remains null.
@@ -842,7 +1019,7 @@ macro_rules! vec {
 			/// assert_eq!(12, Vec3::dot(lhs, rhs));
 			/// ```
 			pub fn dot(self, rhs: $vec<T>) -> T {
-				infix!(+ $(self.$field * rhs.$field),+)
+				dot!(self, rhs; $($field),+)
 			}
 			/// Calculates the cosine of the angle between two vectors.
 			///
@@ -919,9 +1096,81 @@ macro_rules! vec {
 			pub fn max(self, rhs: $vec<T>) -> $vec<T> {
 				$vec { $($field: T::max(self.$field, rhs.$field)),+ }
 			}
+			/// Rounds the components to the nearest multiple of the step, use [`dup`](#method.dup) for a uniform step.
+			///
+			/// Ties round towards positive infinity, see [`num::snap`](../num/fn.snap.html).
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// let this = Vec2 { x: -7.5, y: 13.0 };
+			/// assert_eq!(Vec2(-5.0, 15.0), this.snap(Vec2(5.0, 5.0)));
+			/// assert_eq!(Vec2(-8.0, 12.0), this.snap(Vec2(2.0, 3.0)));
+			/// ```
+			pub fn snap(self, step: $vec<T>) -> $vec<T> where T: Float {
+				$vec { $($field: ::num::snap(self.$field, step.$field)),+ }
+			}
+			/// Rounds the components down to a multiple of the step.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// let this = Vec2 { x: -7.5, y: 13.0 };
+			/// assert_eq!(Vec2(-10.0, 10.0), this.snap_floor(Vec2(5.0, 5.0)));
+			/// ```
+			pub fn snap_floor(self, step: $vec<T>) -> $vec<T> where T: Float {
+				$vec { $($field: ::num::snap_floor(self.$field, step.$field)),+ }
+			}
+			/// Rounds the components up to a multiple of the step.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// let this = Vec2 { x: -7.5, y: 13.0 };
+			/// assert_eq!(Vec2(-5.0, 15.0), this.snap_ceil(Vec2(5.0, 5.0)));
+			/// ```
+			pub fn snap_ceil(self, step: $vec<T>) -> $vec<T> where T: Float {
+				$vec { $($field: ::num::snap_ceil(self.$field, step.$field)),+ }
+			}
+			/// Wraps the position into the periodic domain spanned by the bounds.
+			///
+			/// The result lies within `[mins, maxs)` on every axis. The bounds must have positive size.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// # use cvmath::bounds::{Rect};
+			/// let bounds = Rect(Vec2(0.0, 0.0), Vec2(10.0, 5.0));
+			/// assert_eq!(Vec2(2.0, 4.0), Vec2(12.0, -1.0).wrap(bounds));
+			/// assert_eq!(Vec2(0.0, 3.0), Vec2(-10.0, 3.0).wrap(bounds));
+			/// // Tiny negative offsets round up to the size and wrap back to the minimum
+			/// assert_eq!(Vec2(0.0, 0.0), Vec2(-1e-20, -1e-20).wrap(bounds));
+			/// ```
+			pub fn wrap(self, bounds: Bounds<$vec<T>>) -> $vec<T> where T: Float {
+				let size = bounds.size();
+				$vec { $($field: {
+					let offset = self.$field - bounds.mins.$field;
+					let x = bounds.mins.$field + offset - ::num::snap_floor(offset, size.$field);
+					if x >= bounds.maxs.$field { bounds.mins.$field } else { x }
+				}),+ }
+			}
+			/// Calculates the shortest difference vector to another position in the periodic domain spanned by the bounds.
+			///
+			/// Each component lies within `[-size / 2, size / 2)`. The bounds must have positive size.
+			///
+			/// ```
+			/// # use cvmath::vec::{Vec2};
+			/// # use cvmath::bounds::{Rect};
+			/// let bounds = Rect(Vec2(0.0, 0.0), Vec2(10.0, 10.0));
+			/// assert_eq!(Vec2(-2.0, 3.0), Vec2(1.0, 8.0).wrap_delta(Vec2(9.0, 1.0), bounds));
+			/// assert_eq!(Vec2(-5.0, 0.0), Vec2(0.0, 0.0).wrap_delta(Vec2(5.0, 0.0), bounds));
+			/// ```
+			pub fn wrap_delta(self, to: $vec<T>, bounds: Bounds<$vec<T>>) -> $vec<T> where T: Float {
+				let size = bounds.size();
+				$vec { $($field: {
+					let delta = to.$field - self.$field;
+					delta - ::num::snap(delta, size.$field)
+				}),+ }
+			}
 			/// Adds the scaled vector.
 			pub fn mul_add(self, vec: $vec<T>, scale: T) -> $vec<T> {
-				$vec { $($field: self.$field + vec.$field * scale),+ }
+				$vec { $($field: vec.$field.mul_add(scale, self.$field)),+ }
 			}
 			/// Linear interpolation between the vectors.
 			///
@@ -975,6 +1224,39 @@ macro_rules! vec {
 				($vec { $($field: temp.$field.0),+ }, $vec { $($field: temp.$field.1),+ })
 			}
 		}
+		impl<T: TotalOrd> $vec<T> {
+			/// Compares the vectors lexicographically with a total order on the components.
+			///
+			/// Unlike `PartialOrd` this is defined for floats with NaN components, see [`TotalOrd`](../num/trait.TotalOrd.html).
+			///
+			/// ```
+			/// # use cvmath::prelude::Vec2;
+			/// use std::cmp::Ordering;
+			/// assert_eq!(Ordering::Less, Vec2(1.0, f64::NAN).total_cmp(&Vec2(2.0, 0.0)));
+			/// assert_eq!(Ordering::Greater, Vec2(1.0, f64::NAN).total_cmp(&Vec2(1.0, 0.0)));
+			/// assert_eq!(Ordering::Less, Vec2(-0.0, 0.0).total_cmp(&Vec2(0.0, 0.0)));
+			/// ```
+			pub fn total_cmp(&self, rhs: &$vec<T>) -> cmp::Ordering {
+				$(
+					match self.$field.total_cmp(&rhs.$field) {
+						cmp::Ordering::Equal => (),
+						ordering => return ordering,
+					}
+				)+
+				cmp::Ordering::Equal
+			}
+		}
+		impl<T: TotalOrd> TotalOrd for $vec<T> {
+			fn total_cmp(&self, rhs: &$vec<T>) -> cmp::Ordering {
+				$vec::total_cmp(self, rhs)
+			}
+		}
+		impl<T: CanonicalBits> CanonicalBits for $vec<T> {
+			type Bits = $vec<T::Bits>;
+			fn canonical_bits(self) -> $vec<T::Bits> {
+				$vec { $($field: self.$field.canonical_bits()),+ }
+			}
+		}
 		impl<T: PartialOrd> SpatialOrd<$vec<T>> for $vec<T> {
 			fn spatial_lt(&self, rhs: &$vec<T>) -> bool { $(self.$field < rhs.$field &&)+ true }
 			fn spatial_le(&self, rhs: &$vec<T>) -> bool { $(self.$field <= rhs.$field &&)+ true }
@@ -1100,6 +1382,8 @@ macro_rules! vec {
 				}
 				// Comma separated list of values
 				let mut iter = s.bytes().enumerate().filter_map(|(i, v)| if v == b',' { Some(i) } else { None });
+				// Only advanced between elements, not used mutably by `Vec1`
+				#[allow(unused_mut)]
 				let mut next = 1;
 				$(let $field;)+
 				parse_vec_elems!(s, iter, next; $($field),+);
@@ -1109,7 +1393,7 @@ macro_rules! vec {
 	}
 }
 
-// vec!(Vec1 1 { x 0 T X });
+vec!(Vec1 1 { x 0 T X } {});
 vec!(Vec2 2 { x 0 T X, y 1 T Y } {
 	/// Calculates the polar angle.
 	///
@@ -1178,6 +1462,31 @@ vec!(Vec2 2 { x 0 T X, y 1 T Y } {
 	pub fn hsub(self) -> T {
 		self.x - self.y
 	}
+	/// Calculates the uniform scale which fits this size inside the container.
+	///
+	/// The scaled size touches the container on one axis and fits within it on the other, eg. letterboxing.
+	///
+	/// ```
+	/// # use cvmath::vec::{Vec2};
+	/// let image = Vec2 { x: 400.0, y: 300.0 };
+	/// assert_eq!(0.5, image.scale_to_fit(Vec2(200.0, 200.0)));
+	/// assert_eq!(Vec2(200.0, 150.0), image * image.scale_to_fit(Vec2(200.0, 200.0)));
+	/// ```
+	pub fn scale_to_fit(self, container: Vec2<T>) -> T {
+		T::min(container.x / self.x, container.y / self.y)
+	}
+	/// Calculates the uniform scale which makes this size cover the container.
+	///
+	/// The scaled size touches the container on one axis and overflows it on the other, eg. cropped thumbnails.
+	///
+	/// ```
+	/// # use cvmath::vec::{Vec2};
+	/// let image = Vec2 { x: 400.0, y: 300.0 };
+	/// assert_eq!(200.0 / 300.0, image.scale_to_cover(Vec2(200.0, 200.0)));
+	/// ```
+	pub fn scale_to_cover(self, container: Vec2<T>) -> T {
+		T::max(container.x / self.x, container.y / self.y)
+	}
 	/// Intercepts the vector with `x = constant` returning the y.
 	pub fn y_intercept(self, x: T) -> Option<T> {
 		if self.x != T::zero() {
@@ -1228,6 +1537,16 @@ vec!(Vec3 3 { x 0 T X, y 1 T Y, z 2 T Z } {
 		else { self.xy() }
 	}
 });
+vec!(Vec3A 3 { x 0 T X, y 1 T Y, z 2 T Z } {
+	/// Calculates the 3D cross product.
+	pub fn cross(self, rhs: Vec3A<T>) -> Vec3A<T> {
+		Vec3A {
+			x: self.y * rhs.z - self.z * rhs.y,
+			y: self.z * rhs.x - self.x * rhs.z,
+			z: self.x * rhs.y - self.y * rhs.x,
+		}
+	}
+});
 vec!(Vec4 4 { x 0 T X, y 1 T Y, z 2 T Z, w 3 T W } {
 	/// Homogeneous divide.
 	pub fn hdiv(self) -> Vec3<T> {
@@ -1240,8 +1559,156 @@ vec!(Vec4 4 { x 0 T X, y 1 T Y, z 2 T Z, w 3 T W } {
 		}
 		else { self.xyz() }
 	}
+	/// Calculates the inner product of the `xyz` components, ignoring `w`.
+	///
+	/// ```
+	/// # use cvmath::vec::{Vec3, Vec4};
+	/// let this = Vec4 { x: 1, y: 2, z: 3, w: 4 };
+	/// assert_eq!(14, this.dot3(Vec3(1, 2, 3)));
+	/// ```
+	pub fn dot3(self, rhs: Vec3<T>) -> T {
+		self.xyz().dot(rhs)
+	}
+	/// Calculates the inner product with the point extended with `w = 1`.
+	///
+	/// For plane coefficients `(a, b, c, d)` this is the signed distance `ax + by + cz + d` from the plane to the point.
+	///
+	/// ```
+	/// # use cvmath::vec::{Vec3, Vec4};
+	/// let plane = Vec4 { x: 0.0, y: 0.0, z: 1.0, w: -2.0 };
+	/// assert_eq!(3.0, plane.dot4h(Vec3(7.0, -1.0, 5.0)));
+	/// ```
+	pub fn dot4h(self, point: Vec3<T>) -> T {
+		self.xyz().dot(point) + self.w
+	}
+	/// Normalizes the vector as plane coefficients `(a, b, c, d)` so the normal `(a, b, c)` has unit length.
+	///
+	/// The vector with a zero normal remains unchanged.
+	///
+	/// ```
+	/// # use cvmath::vec::{Vec4};
+	/// let plane = Vec4 { x: 0.0, y: 3.0, z: 4.0, w: 10.0 };
+	/// assert_eq!(Vec4(0.0, 0.6, 0.8, 2.0), plane.normalize_plane());
+	/// ```
+	pub fn normalize_plane(self) -> Vec4<T> where T: Float {
+		let len = self.xyz().len();
+		if len > T::zero() { self / len } else { self }
+	}
 });
 
+// Vec3A is excluded, its padding bytes are uninitialized.
+macro_rules! as_bytes {
+	($vec:ident) => {
+		impl<T> $vec<T> {
+			pub fn as_bytes(&self) -> &[u8] {
+				unsafe { slice::from_raw_parts(self as *const _ as *const u8, mem::size_of_val(self)) }
+			}
+		}
+	};
+}
+
+as_bytes!(Vec1);
+as_bytes!(Vec2);
+as_bytes!(Vec3);
+as_bytes!(Vec4);
+
+impl<T: Float> Vec2<T> {
+	/// Returns the point of the Halton sequence with bases 2 and 3 at the index.
+	///
+	/// The points are well distributed in `[0, 1)²`, subtract `0.5` for sub-pixel jitter offsets used by temporal anti-aliasing.
+	/// The sequence starts at index `1`, index `0` returns the origin.
+	///
+	/// ```
+	/// # use cvmath::vec::Vec2;
+	/// assert_eq!(Vec2(0.5, 1.0 / 3.0), Vec2::halton(1));
+	/// assert_eq!(Vec2(0.25, 2.0 / 3.0), Vec2::halton(2));
+	/// assert_eq!(Vec2(0.75, 1.0 / 9.0), Vec2::halton(3));
+	/// ```
+	pub fn halton(index: u32) -> Vec2<T> {
+		let radical_inverse = |mut i: u32, base: u32| {
+			let inv_base = 1.0 / base as f64;
+			let mut f = 1.0;
+			let mut r = 0.0;
+			while i > 0 {
+				f *= inv_base;
+				r += f * (i % base) as f64;
+				i /= base;
+			}
+			T::cast_from(r)
+		};
+		Vec2 { x: radical_inverse(index, 2), y: radical_inverse(index, 3) }
+	}
+}
+
+impl Vec2<i32> {
+	/// Offsets to the 4-connected neighbors sharing an edge, in row-major order.
+	pub const NEIGHBORS4: [Vec2<i32>; 4] = [Vec2 { x: 0, y: -1 }, Vec2 { x: -1, y: 0 }, Vec2 { x: 1, y: 0 }, Vec2 { x: 0, y: 1 }];
+	/// Offsets to the 8-connected neighbors sharing an edge or a corner, in row-major order.
+	pub const NEIGHBORS8: [Vec2<i32>; 8] = [
+		Vec2 { x: -1, y: -1 }, Vec2 { x: 0, y: -1 }, Vec2 { x: 1, y: -1 }, Vec2 { x: -1, y: 0 },
+		Vec2 { x: 1, y: 0 }, Vec2 { x: -1, y: 1 }, Vec2 { x: 0, y: 1 }, Vec2 { x: 1, y: 1 },
+	];
+	/// Iterates over the 4-connected neighbors.
+	///
+	/// ```
+	/// # use cvmath::vec::Vec2;
+	/// let neighbors: Vec<_> = Vec2(2, 3).neighbors4().collect();
+	/// assert_eq!(neighbors, [Vec2(2, 2), Vec2(1, 3), Vec2(3, 3), Vec2(2, 4)]);
+	/// ```
+	pub fn neighbors4(self) -> impl Iterator<Item = Vec2<i32>> {
+		Self::NEIGHBORS4.iter().map(move |&offset| self + offset)
+	}
+	/// Iterates over the 8-connected neighbors.
+	pub fn neighbors8(self) -> impl Iterator<Item = Vec2<i32>> {
+		Self::NEIGHBORS8.iter().map(move |&offset| self + offset)
+	}
+}
+
+impl Vec3<i32> {
+	/// Offsets to the 6-connected neighbors sharing a face, X varies fastest and Z slowest.
+	pub const NEIGHBORS6: [Vec3<i32>; 6] = [
+		Vec3 { x: 0, y: 0, z: -1 }, Vec3 { x: 0, y: -1, z: 0 }, Vec3 { x: -1, y: 0, z: 0 },
+		Vec3 { x: 1, y: 0, z: 0 }, Vec3 { x: 0, y: 1, z: 0 }, Vec3 { x: 0, y: 0, z: 1 },
+	];
+	/// Offsets to the 26-connected neighbors sharing a face, an edge or a corner, X varies fastest and Z slowest.
+	pub const NEIGHBORS26: [Vec3<i32>; 26] = [
+		Vec3 { x: -1, y: -1, z: -1 }, Vec3 { x: 0, y: -1, z: -1 }, Vec3 { x: 1, y: -1, z: -1 },
+		Vec3 { x: -1, y: 0, z: -1 }, Vec3 { x: 0, y: 0, z: -1 }, Vec3 { x: 1, y: 0, z: -1 },
+		Vec3 { x: -1, y: 1, z: -1 }, Vec3 { x: 0, y: 1, z: -1 }, Vec3 { x: 1, y: 1, z: -1 },
+		Vec3 { x: -1, y: -1, z: 0 }, Vec3 { x: 0, y: -1, z: 0 }, Vec3 { x: 1, y: -1, z: 0 },
+		Vec3 { x: -1, y: 0, z: 0 }, Vec3 { x: 1, y: 0, z: 0 }, Vec3 { x: -1, y: 1, z: 0 },
+		Vec3 { x: 0, y: 1, z: 0 }, Vec3 { x: 1, y: 1, z: 0 }, Vec3 { x: -1, y: -1, z: 1 },
+		Vec3 { x: 0, y: -1, z: 1 }, Vec3 { x: 1, y: -1, z: 1 }, Vec3 { x: -1, y: 0, z: 1 },
+		Vec3 { x: 0, y: 0, z: 1 }, Vec3 { x: 1, y: 0, z: 1 }, Vec3 { x: -1, y: 1, z: 1 },
+		Vec3 { x: 0, y: 1, z: 1 }, Vec3 { x: 1, y: 1, z: 1 },
+	];
+	/// Iterates over the 6-connected neighbors.
+	///
+	/// ```
+	/// # use cvmath::vec::Vec3;
+	/// assert_eq!(6, Vec3(0, 0, 0).neighbors6().count());
+	/// assert!(Vec3(5, 5, 5).neighbors26().all(|n| n != Vec3(5, 5, 5) && (n - Vec3(5, 5, 5)).map(i32::abs).reduce(i32::max) == 1));
+	/// ```
+	pub fn neighbors6(self) -> impl Iterator<Item = Vec3<i32>> {
+		Self::NEIGHBORS6.iter().map(move |&offset| self + offset)
+	}
+	/// Iterates over the 26-connected neighbors.
+	pub fn neighbors26(self) -> impl Iterator<Item = Vec3<i32>> {
+		Self::NEIGHBORS26.iter().map(move |&offset| self + offset)
+	}
+}
+
+impl<T> From<Vec3<T>> for Vec3A<T> {
+	fn from(v: Vec3<T>) -> Vec3A<T> {
+		v.vec3a()
+	}
+}
+impl<T> From<Vec3A<T>> for Vec3<T> {
+	fn from(v: Vec3A<T>) -> Vec3<T> {
+		v.vec3()
+	}
+}
+
 //----------------------------------------------------------------
 
 use std::str::FromStr;
@@ -0,0 +1,340 @@
+/*!
+Vector with a const generic number of dimensions.
+
+`VecN` is backed by an array and implements the same operators and most of the methods of the fixed size vector types.
+Dimension generic algorithms can be written once for `VecN<T, N>` and convert from and to `Vec2`, `Vec3` and `Vec4` at the edges.
+The conversions move the components and compile to nothing.
+
+### Examples
+
+```
+use cvmath::prelude::{Vec3, VecN};
+
+fn centroid<const N: usize>(points: &[VecN<f64, N>]) -> VecN<f64, N> {
+    let sum = points.iter().fold(VecN::<f64, N>::origin(), |acc, &pt| acc + pt);
+    sum / points.len() as f64
+}
+
+let points = [VecN::from(Vec3(1.0, 2.0, 3.0)), VecN::from(Vec3(3.0, 4.0, 5.0))];
+assert_eq!(Vec3(2.0, 3.0, 4.0), centroid(&points).into());
+
+let v = VecN([3.0, 4.0, 0.0, 0.0, 12.0]);
+assert_eq!(13.0, v.len());
+assert_eq!("(3,4,0,0,12)", format!("{}", v));
+```
+*/
+
+use std::{array, fmt, ops};
+
+use num::{Scalar, Zero, One, Float, CastTo};
+use vec::{Vec1, Vec2, Vec3, Vec4};
+use error::MathError;
+
+/// An N-dimensional vector.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
+pub struct VecN<T, const N: usize>(pub [T; N]);
+
+/// Zips two arrays with the function.
+fn zip_with<A, B, U, F: FnMut(A, B) -> U, const N: usize>(a: [A; N], b: [B; N], mut f: F) -> [U; N] {
+	let mut a = IntoIterator::into_iter(a);
+	let mut b = IntoIterator::into_iter(b);
+	array::from_fn(|_| match (a.next(), b.next()) {
+		(Some(a), Some(b)) => f(a, b),
+		_ => unreachable!(),
+	})
+}
+
+//----------------------------------------------------------------
+// Constructors
+
+impl<T, const N: usize> VecN<T, N> {
+	/// Constructs a new vector from components.
+	pub fn new(components: [T; N]) -> VecN<T, N> {
+		VecN(components)
+	}
+	/// Constructs a new vector by broadcasting to all its components.
+	pub fn dup(u: T) -> VecN<T, N> where T: Copy {
+		VecN([u; N])
+	}
+	/// Returns the origin for the vector space.
+	pub fn origin() -> VecN<T, N> where T: Zero {
+		VecN(array::from_fn(|_| T::zero()))
+	}
+	/// Unit vector in the direction of the axis.
+	///
+	/// Panics if the axis is out of bounds.
+	pub fn unit(axis: usize) -> VecN<T, N> where T: Zero + One {
+		assert!(axis < N, "axis out of bounds");
+		VecN(array::from_fn(|i| if i == axis { T::one() } else { T::zero() }))
+	}
+}
+
+impl<T: Default, const N: usize> Default for VecN<T, N> {
+	fn default() -> VecN<T, N> {
+		VecN(array::from_fn(|_| T::default()))
+	}
+}
+
+//----------------------------------------------------------------
+// Transformations
+
+impl<T, const N: usize> VecN<T, N> {
+	/// Casts to a vector of different type with the same dimensions.
+	pub fn cast<U>(self) -> VecN<U, N> where T: CastTo<U> {
+		VecN(self.0.map(T::cast_to))
+	}
+	/// Maps a callable over the components.
+	pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> VecN<U, N> {
+		VecN(self.0.map(f))
+	}
+	/// Zips two vectors together.
+	pub fn zip<U, F: FnMut(T, T) -> U>(self, rhs: VecN<T, N>, f: F) -> VecN<U, N> {
+		VecN(zip_with(self.0, rhs.0, f))
+	}
+	/// Reduces the vector.
+	///
+	/// Panics if the vector has no components.
+	pub fn reduce<F: Fn(T, T) -> T>(self, f: F) -> T {
+		IntoIterator::into_iter(self.0).reduce(f).expect("empty vector")
+	}
+	/// Folds the vector.
+	pub fn fold<A, F: Fn(A, T) -> A>(self, acc: A, f: F) -> A {
+		IntoIterator::into_iter(self.0).fold(acc, f)
+	}
+	/// Returns the components as a slice.
+	pub fn as_slice(&self) -> &[T] {
+		&self.0
+	}
+	/// Returns the components as a mutable slice.
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		&mut self.0
+	}
+}
+
+//----------------------------------------------------------------
+// Operations
+
+impl<T: Scalar, const N: usize> VecN<T, N> {
+	/// Squares the components.
+	pub fn sqr(self) -> VecN<T, N> {
+		self.map(|x| x * x)
+	}
+	/// Calculates the squared length of the vector.
+	pub fn len_sqr(self) -> T {
+		self.dot(self)
+	}
+	/// Calculates the length of the vector.
+	pub fn len(self) -> T where T: Float {
+		self.len_sqr().sqrt()
+	}
+	/// Squared distance between two points.
+	pub fn dist_sqr(self, to: VecN<T, N>) -> T {
+		(to - self).len_sqr()
+	}
+	/// Distance between two points.
+	pub fn dist(self, to: VecN<T, N>) -> T where T: Float {
+		(to - self).len()
+	}
+	/// Normalizes the vector.
+	///
+	/// After normalizing the vector has the length `1.0` except the zero vector remains zero.
+	pub fn norm(self) -> VecN<T, N> where T: Float {
+		let len = self.len();
+		if len == T::zero() { self } else { self * (T::one() / len) }
+	}
+	/// Normalizes the vector.
+	///
+	/// Returns `Err(MathError::ZeroLength)` if the vector has zero length or isn't finite.
+	pub fn try_norm(self) -> Result<VecN<T, N>, MathError> where T: Float {
+		let len = self.len();
+		if len > T::zero() && len.is_finite() { Ok(self * (T::one() / len)) } else { Err(MathError::ZeroLength) }
+	}
+	/// Calculates the inner product.
+	pub fn dot(self, rhs: VecN<T, N>) -> T {
+		let mut acc = T::zero();
+		for (&a, &b) in self.0.iter().zip(&rhs.0) {
+			acc = a.mul_add(b, acc);
+		}
+		acc
+	}
+	/// Component wise absolute value.
+	pub fn abs(self) -> VecN<T, N> {
+		self.map(T::abs)
+	}
+	/// Component wise minimum value.
+	pub fn min(self, rhs: VecN<T, N>) -> VecN<T, N> {
+		self.zip(rhs, T::min)
+	}
+	/// Component wise maximum value.
+	pub fn max(self, rhs: VecN<T, N>) -> VecN<T, N> {
+		self.zip(rhs, T::max)
+	}
+	/// Adds the scaled vector.
+	pub fn mul_add(self, vec: VecN<T, N>, scale: T) -> VecN<T, N> {
+		self.zip(vec, |a, v| v.mul_add(scale, a))
+	}
+	/// Linear interpolation between the vectors.
+	pub fn lerp(self, rhs: VecN<T, N>, t: T) -> VecN<T, N> {
+		self + (rhs - self) * t
+	}
+}
+
+//----------------------------------------------------------------
+// Conversions
+
+impl<T, const N: usize> From<[T; N]> for VecN<T, N> {
+	fn from(val: [T; N]) -> VecN<T, N> {
+		VecN(val)
+	}
+}
+impl<T, const N: usize> From<VecN<T, N>> for [T; N] {
+	fn from(val: VecN<T, N>) -> [T; N] {
+		val.0
+	}
+}
+impl<T, const N: usize> AsRef<[T; N]> for VecN<T, N> {
+	fn as_ref(&self) -> &[T; N] {
+		&self.0
+	}
+}
+impl<T, const N: usize> AsMut<[T; N]> for VecN<T, N> {
+	fn as_mut(&mut self) -> &mut [T; N] {
+		&mut self.0
+	}
+}
+impl<T, const N: usize> ops::Index<usize> for VecN<T, N> {
+	type Output = T;
+	fn index(&self, i: usize) -> &T {
+		&self.0[i]
+	}
+}
+impl<T, const N: usize> ops::IndexMut<usize> for VecN<T, N> {
+	fn index_mut(&mut self, i: usize) -> &mut T {
+		&mut self.0[i]
+	}
+}
+
+macro_rules! convert {
+	($vec:ident $N:tt { $($field:ident),+ }) => {
+		impl<T> From<$vec<T>> for VecN<T, $N> {
+			fn from(v: $vec<T>) -> VecN<T, $N> {
+				VecN([$(v.$field),+])
+			}
+		}
+		impl<T> From<VecN<T, $N>> for $vec<T> {
+			fn from(v: VecN<T, $N>) -> $vec<T> {
+				let [$($field),+] = v.0;
+				$vec { $($field),+ }
+			}
+		}
+	};
+}
+
+convert!(Vec1 1 { x });
+convert!(Vec2 2 { x, y });
+convert!(Vec3 3 { x, y, z });
+convert!(Vec4 4 { x, y, z, w });
+
+//----------------------------------------------------------------
+// Operators
+
+macro_rules! binary_ops {
+	($($op:ident $fn:ident $op_assign:ident $fn_assign:ident),+) => {$(
+		impl<U, T: ops::$op<U>, const N: usize> ops::$op<VecN<U, N>> for VecN<T, N> {
+			type Output = VecN<T::Output, N>;
+			fn $fn(self, rhs: VecN<U, N>) -> VecN<T::Output, N> {
+				VecN(zip_with(self.0, rhs.0, ops::$op::$fn))
+			}
+		}
+		impl<U, T: ops::$op_assign<U>, const N: usize> ops::$op_assign<VecN<U, N>> for VecN<T, N> {
+			fn $fn_assign(&mut self, rhs: VecN<U, N>) {
+				for (a, b) in self.0.iter_mut().zip(IntoIterator::into_iter(rhs.0)) {
+					ops::$op_assign::$fn_assign(a, b);
+				}
+			}
+		}
+	)+};
+}
+
+macro_rules! scalar_ops {
+	($($op:ident $fn:ident $op_assign:ident $fn_assign:ident),+) => {$(
+		impl<U: Scalar, T: ops::$op<U>, const N: usize> ops::$op<U> for VecN<T, N> {
+			type Output = VecN<T::Output, N>;
+			fn $fn(self, rhs: U) -> VecN<T::Output, N> {
+				VecN(self.0.map(|a| ops::$op::$fn(a, rhs)))
+			}
+		}
+		impl<U: Scalar, T: ops::$op_assign<U>, const N: usize> ops::$op_assign<U> for VecN<T, N> {
+			fn $fn_assign(&mut self, rhs: U) {
+				for a in self.0.iter_mut() {
+					ops::$op_assign::$fn_assign(a, rhs);
+				}
+			}
+		}
+	)+};
+}
+
+// Vector addition, subtraction, multiplication, division and remainder
+binary_ops!(Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign, Div div DivAssign div_assign, Rem rem RemAssign rem_assign);
+// Scalar multiplication, division and remainder
+scalar_ops!(Mul mul MulAssign mul_assign, Div div DivAssign div_assign, Rem rem RemAssign rem_assign);
+
+impl<T: ops::Neg, const N: usize> ops::Neg for VecN<T, N> {
+	type Output = VecN<T::Output, N>;
+	fn neg(self) -> VecN<T::Output, N> {
+		VecN(self.0.map(|a| -a))
+	}
+}
+
+//----------------------------------------------------------------
+// Formatting
+
+macro_rules! fmt {
+	($($fmt:path),+) => {$(
+		impl<T: $fmt, const N: usize> $fmt for VecN<T, N> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("(")?;
+				for (i, v) in self.0.iter().enumerate() {
+					if i > 0 {
+						f.write_str(",")?;
+					}
+					v.fmt(f)?;
+				}
+				f.write_str(")")
+			}
+		}
+	)+};
+}
+
+fmt!(fmt::Display, fmt::Debug, fmt::Binary, fmt::Octal, fmt::LowerHex, fmt::UpperHex, fmt::LowerExp, fmt::UpperExp);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_fixed_vectors() {
+		let a = Vec4(1.0, -2.0, 3.5, 0.25);
+		let b = Vec4(-4.0, 0.5, 2.0, 8.0);
+		let (na, nb) = (VecN::from(a), VecN::from(b));
+		assert_eq!(a + b, (na + nb).into());
+		assert_eq!(a - b, (na - nb).into());
+		assert_eq!(a * b, (na * nb).into());
+		assert_eq!(a * 3.0, (na * 3.0).into());
+		assert_eq!(-a, (-na).into());
+		assert_eq!(a.dot(b), na.dot(nb));
+		assert_eq!(a.len(), na.len());
+		assert_eq!(a.norm(), na.norm().into());
+		assert_eq!(a.min(b), na.min(nb).into());
+		assert_eq!(a.lerp(b, 0.25), na.lerp(nb, 0.25).into());
+		assert_eq!(a.reduce(f64::max), na.reduce(f64::max));
+		assert_eq!(format!("{:?}", a), format!("{:?}", na));
+
+		let mut c = na;
+		c += nb;
+		c *= 2.0;
+		assert_eq!((a + b) * 2.0, c.into());
+		assert_eq!(VecN([0, 0, 1]), VecN::unit(2));
+	}
+}
@@ -0,0 +1,274 @@
+/*!
+Structure-of-arrays vector types.
+
+`Vec3x4` and `Vec3x8` hold 4 and 8 vectors with every component stored in its own array.
+Operations work on all lanes at once which lets the compiler vectorize batch geometry kernels
+such as frustum culling and particle updates.
+
+Masks are plain arrays of `bool`, one per lane.
+
+`RayPacket4` traces four rays at once against bounding boxes, as used in BVH traversal.
+
+### Examples
+
+```
+# use cvmath::prelude::Vec3;
+use cvmath::wide::Vec3x4;
+
+let points = [Vec3(1.0, 0.0, 0.0), Vec3(-2.0, 1.0, 0.0), Vec3(0.0, 0.0, 3.0)];
+let normal = Vec3x4::splat(Vec3(1.0, 0.0, 0.0));
+
+// Missing lanes are filled with zero
+let wide = Vec3x4::gather(&points);
+let dist = wide.dot(normal);
+assert_eq!([1.0, -2.0, 0.0, 0.0], dist);
+
+// Flip the points behind the plane
+let mask = dist.map(|d| d < 0.0);
+let wide = Vec3x4::select(mask, -wide, wide);
+
+let mut result = [Vec3::default(); 3];
+wide.scatter(&mut result);
+assert_eq!([Vec3(1.0, 0.0, 0.0), Vec3(2.0, -1.0, -0.0), Vec3(0.0, 0.0, 3.0)], result);
+```
+*/
+
+use std::{array, ops};
+
+use num::{Scalar, Float};
+use vec::Vec3;
+use bounds::Cuboid;
+
+/// Four 3-dimensional vectors in structure-of-arrays layout.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec3x4<T> {
+	pub x: [T; 4],
+	pub y: [T; 4],
+	pub z: [T; 4],
+}
+
+/// Eight 3-dimensional vectors in structure-of-arrays layout.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Vec3x8<T> {
+	pub x: [T; 8],
+	pub y: [T; 8],
+	pub z: [T; 8],
+}
+
+macro_rules! wide {
+	($wide:ident $N:tt) => {
+
+		//----------------------------------------------------------------
+		// Constructors
+
+		impl<T: Copy> $wide<T> {
+			/// Broadcasts the vector to all lanes.
+			pub fn splat(v: Vec3<T>) -> $wide<T> {
+				$wide { x: [v.x; $N], y: [v.y; $N], z: [v.z; $N] }
+			}
+			/// Loads up to `N` vectors from the slice, missing lanes are filled with `T::default()`.
+			pub fn gather(src: &[Vec3<T>]) -> $wide<T> where T: Default {
+				let mut result = $wide { x: [T::default(); $N], y: [T::default(); $N], z: [T::default(); $N] };
+				for (i, v) in src.iter().take($N).enumerate() {
+					result.x[i] = v.x;
+					result.y[i] = v.y;
+					result.z[i] = v.z;
+				}
+				result
+			}
+			/// Stores up to `N` vectors into the slice.
+			pub fn scatter(self, dst: &mut [Vec3<T>]) {
+				for (i, v) in dst.iter_mut().take($N).enumerate() {
+					*v = self.lane(i);
+				}
+			}
+			/// Returns the vector in the given lane.
+			pub fn lane(self, i: usize) -> Vec3<T> {
+				Vec3 { x: self.x[i], y: self.y[i], z: self.z[i] }
+			}
+			/// Combines two wide vectors based on the mask, selecting lanes from the left-hand side if `true` and right-hand side if `false`.
+			pub fn select(mask: [bool; $N], lhs: $wide<T>, rhs: $wide<T>) -> $wide<T> {
+				let pick = |i: usize, l: &[T; $N], r: &[T; $N]| if mask[i] { l[i] } else { r[i] };
+				$wide {
+					x: array::from_fn(|i| pick(i, &lhs.x, &rhs.x)),
+					y: array::from_fn(|i| pick(i, &lhs.y, &rhs.y)),
+					z: array::from_fn(|i| pick(i, &lhs.z, &rhs.z)),
+				}
+			}
+		}
+
+		//----------------------------------------------------------------
+		// Operations
+
+		impl<T: Scalar> $wide<T> {
+			/// Calculates the dot product in every lane.
+			pub fn dot(self, rhs: $wide<T>) -> [T; $N] {
+				array::from_fn(|i| self.z[i].mul_add(rhs.z[i], self.y[i].mul_add(rhs.y[i], self.x[i] * rhs.x[i])))
+			}
+			/// Calculates the 3D cross product in every lane.
+			pub fn cross(self, rhs: $wide<T>) -> $wide<T> {
+				$wide {
+					x: array::from_fn(|i| self.y[i] * rhs.z[i] - self.z[i] * rhs.y[i]),
+					y: array::from_fn(|i| self.z[i] * rhs.x[i] - self.x[i] * rhs.z[i]),
+					z: array::from_fn(|i| self.x[i] * rhs.y[i] - self.y[i] * rhs.x[i]),
+				}
+			}
+			/// Calculates the squared length in every lane.
+			pub fn len_sqr(self) -> [T; $N] {
+				self.dot(self)
+			}
+			/// Calculates the length in every lane.
+			pub fn len(self) -> [T; $N] where T: Float {
+				self.len_sqr().map(|l| l.sqrt())
+			}
+			/// Normalizes the vector in every lane.
+			pub fn norm(self) -> $wide<T> where T: Float {
+				let len = self.len();
+				let inv = |i: usize, c: T| if len[i] > T::zero() { c / len[i] } else { c };
+				$wide {
+					x: array::from_fn(|i| inv(i, self.x[i])),
+					y: array::from_fn(|i| inv(i, self.y[i])),
+					z: array::from_fn(|i| inv(i, self.z[i])),
+				}
+			}
+			/// Adds the scaled vector in every lane.
+			pub fn mul_add(self, vec: $wide<T>, scale: T) -> $wide<T> {
+				self + vec * scale
+			}
+		}
+
+		//----------------------------------------------------------------
+		// Operators
+
+		impl<T: Scalar> ops::Add for $wide<T> {
+			type Output = $wide<T>;
+			fn add(self, rhs: $wide<T>) -> $wide<T> {
+				$wide {
+					x: array::from_fn(|i| self.x[i] + rhs.x[i]),
+					y: array::from_fn(|i| self.y[i] + rhs.y[i]),
+					z: array::from_fn(|i| self.z[i] + rhs.z[i]),
+				}
+			}
+		}
+		impl<T: Scalar> ops::Sub for $wide<T> {
+			type Output = $wide<T>;
+			fn sub(self, rhs: $wide<T>) -> $wide<T> {
+				$wide {
+					x: array::from_fn(|i| self.x[i] - rhs.x[i]),
+					y: array::from_fn(|i| self.y[i] - rhs.y[i]),
+					z: array::from_fn(|i| self.z[i] - rhs.z[i]),
+				}
+			}
+		}
+		impl<T: Scalar> ops::Neg for $wide<T> {
+			type Output = $wide<T>;
+			fn neg(self) -> $wide<T> {
+				$wide { x: self.x.map(|c| -c), y: self.y.map(|c| -c), z: self.z.map(|c| -c) }
+			}
+		}
+		impl<T: Scalar> ops::Mul<T> for $wide<T> {
+			type Output = $wide<T>;
+			fn mul(self, rhs: T) -> $wide<T> {
+				$wide { x: self.x.map(|c| c * rhs), y: self.y.map(|c| c * rhs), z: self.z.map(|c| c * rhs) }
+			}
+		}
+		impl<T: Scalar> ops::Mul<[T; $N]> for $wide<T> {
+			type Output = $wide<T>;
+			fn mul(self, rhs: [T; $N]) -> $wide<T> {
+				$wide {
+					x: array::from_fn(|i| self.x[i] * rhs[i]),
+					y: array::from_fn(|i| self.y[i] * rhs[i]),
+					z: array::from_fn(|i| self.z[i] * rhs[i]),
+				}
+			}
+		}
+	};
+}
+
+wide!(Vec3x4 4);
+wide!(Vec3x8 8);
+
+//----------------------------------------------------------------
+
+/// Four rays in structure-of-arrays layout.
+///
+/// ```
+/// # use cvmath::prelude::{Vec3, Cuboid, Point3};
+/// use cvmath::wide::{Vec3x4, RayPacket4};
+///
+/// let origins = [Vec3(-5.0, 0.5, 0.5), Vec3(-5.0, 3.0, 0.5), Vec3(0.5, 0.5, 0.5), Vec3(5.0, 0.5, 0.5)];
+/// let dirs = [Vec3(1.0, 0.0, 0.0), Vec3(1.0, 0.0, 0.0), Vec3(0.0, 0.0, 1.0), Vec3(1.0, 0.0, 0.0)];
+/// let packet = RayPacket4::new(Vec3x4::gather(&origins), Vec3x4::gather(&dirs));
+///
+/// let bounds = Cuboid(Point3(0.0, 0.0, 0.0), Point3(1.0, 1.0, 1.0));
+/// assert_eq!([true, false, true, false], packet.intersect_bounds(&bounds, [f64::INFINITY; 4]));
+/// assert_eq!([false, false, true, false], packet.intersect_bounds(&bounds, [1.0; 4]));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RayPacket4<T> {
+	pub origin: Vec3x4<T>,
+	pub direction: Vec3x4<T>,
+	inv_direction: Vec3x4<T>,
+}
+
+impl<T: Float> RayPacket4<T> {
+	/// Constructs a new ray packet.
+	///
+	/// The directions need not be normalized, distances are measured in multiples of the direction.
+	pub fn new(origin: Vec3x4<T>, direction: Vec3x4<T>) -> RayPacket4<T> {
+		let inv = |c: T| T::one() / c;
+		let inv_direction = Vec3x4 { x: direction.x.map(inv), y: direction.y.map(inv), z: direction.z.map(inv) };
+		RayPacket4 { origin, direction, inv_direction }
+	}
+	/// Slab test of the rays against the bounds.
+	///
+	/// Returns which rays hit the bounds within the distance `[0, t_max]`.
+	pub fn intersect_bounds(&self, bounds: &Cuboid<T>, t_max: [T; 4]) -> [bool; 4] {
+		let slab = |mins: T, maxs: T, o: &[T; 4], inv: &[T; 4]| -> ([T; 4], [T; 4]) {
+			let t1: [T; 4] = array::from_fn(|i| (mins - o[i]) * inv[i]);
+			let t2: [T; 4] = array::from_fn(|i| (maxs - o[i]) * inv[i]);
+			(array::from_fn(|i| t1[i].min(t2[i])), array::from_fn(|i| t1[i].max(t2[i])))
+		};
+		let (o, inv) = (&self.origin, &self.inv_direction);
+		let (xmin, xmax) = slab(bounds.mins.x, bounds.maxs.x, &o.x, &inv.x);
+		let (ymin, ymax) = slab(bounds.mins.y, bounds.maxs.y, &o.y, &inv.y);
+		let (zmin, zmax) = slab(bounds.mins.z, bounds.maxs.z, &o.z, &inv.z);
+		array::from_fn(|i| {
+			let near = xmin[i].max(ymin[i]).max(zmin[i]).max(T::zero());
+			let far = xmax[i].min(ymax[i]).min(zmax[i]).min(t_max[i]);
+			near <= far
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn matches_scalar() {
+		let a: Vec<Vec3<f32>> = (0..8).map(|i| Vec3(i as f32, 1.0 - i as f32, 0.5 * i as f32)).collect();
+		let b: Vec<Vec3<f32>> = (0..8).map(|i| Vec3(2.0, i as f32, -1.0)).collect();
+		let (wa, wb) = (Vec3x8::gather(&a), Vec3x8::gather(&b));
+
+		let dot = wa.dot(wb);
+		let mut cross = [Vec3::default(); 8];
+		wa.cross(wb).scatter(&mut cross);
+		for i in 0..8 {
+			assert_eq!(a[i].dot(b[i]), dot[i]);
+			assert_eq!(a[i].cross(b[i]), cross[i]);
+		}
+	}
+
+	#[test]
+	fn ray_packet_axis_aligned() {
+		// Rays parallel to a slab have infinite inverse directions
+		let origins = [Vec3(0.5f32, 0.5, -1.0), Vec3(0.5, 2.0, -1.0), Vec3(-1.0, -1.0, -1.0), Vec3(0.5, 0.5, 2.0)];
+		let dirs = [Vec3(0.0, 0.0, 1.0), Vec3(0.0, 0.0, 1.0), Vec3(1.0, 1.0, 1.0), Vec3(0.0, 0.0, 1.0)];
+		let packet = RayPacket4::new(Vec3x4::gather(&origins), Vec3x4::gather(&dirs));
+		let bounds = Cuboid(Vec3(0.0, 0.0, 0.0), Vec3(1.0, 1.0, 1.0));
+		assert_eq!([true, false, true, false], packet.intersect_bounds(&bounds, [f32::INFINITY; 4]));
+	}
+}
@@ -0,0 +1,149 @@
+/*!
+Texture coordinate wrapping and conversions.
+
+Implements the addressing modes of GPU samplers on normalized texture coordinates.
+
+Normalized texture coordinates span the texture from `0` to `1`, pixel coordinates span it from `0` to the size in pixels.
+The texel with index `i` covers the pixel coordinates `i` up to `i + 1` and its center lies at `i + 0.5`.
+With `flip_y` the Y axis of the texture coordinates points up while the rows of pixels are stored top to bottom.
+
+### Examples
+
+```
+use cvmath::prelude::Vec2;
+use cvmath::wrap::{self, WrapMode};
+
+assert_eq!(Vec2(0.25, 0.75), wrap::repeat(Vec2(1.25, -0.25)));
+assert_eq!(Vec2(1.0, 0.0), wrap::clamp(Vec2(1.25, -0.25)));
+assert_eq!(Vec2(0.75, 0.25), wrap::mirror(Vec2(1.25, -0.25)));
+
+// Repeat horizontally, clamp vertically
+let modes = Vec2(WrapMode::Repeat, WrapMode::Clamp);
+assert_eq!(Vec2(0.5, 1.0), wrap::apply(Vec2(-1.5, 3.0), modes));
+
+// Pixel coordinates of a 4x2 texture
+let size = Vec2(4, 2);
+assert_eq!(Vec2(1, 0), wrap::uv_to_texel(Vec2(0.3, 0.25), size, false));
+assert_eq!(Vec2(1, 1), wrap::uv_to_texel(Vec2(0.3, 0.25), size, true));
+assert_eq!(Vec2(0.375, 0.25), wrap::texel_center_uv(Vec2(1, 0), size, false));
+assert_eq!(Vec2(2.0, 1.5), wrap::uv_to_pixel(Vec2(0.5, 0.25), size, true));
+```
+*/
+
+use num::{Float, CastFrom};
+use vec::Vec2;
+
+/// Texture coordinate wrapping mode.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WrapMode {
+	/// Repeats the texture, keeps the fractional part of the coordinate.
+	Repeat,
+	/// Clamps the coordinate to the edge of the texture.
+	Clamp,
+	/// Repeats the texture mirrored on every odd repetition.
+	Mirror,
+	/// Mirrors the texture once around zero then clamps to the edge.
+	MirrorOnce,
+}
+
+impl WrapMode {
+	/// Wraps the coordinate into the range `[0, 1]`.
+	///
+	/// ```
+	/// use cvmath::wrap::WrapMode;
+	///
+	/// assert_eq!(0.75, WrapMode::Repeat.wrap(-2.25));
+	/// assert_eq!(0.0, WrapMode::Repeat.wrap(-1e-20));
+	/// assert_eq!(0.0, WrapMode::Clamp.wrap(-2.25));
+	/// assert_eq!(0.25, WrapMode::Mirror.wrap(-2.25));
+	/// assert_eq!(1.0, WrapMode::MirrorOnce.wrap(-2.25));
+	/// ```
+	pub fn wrap<T: Float>(self, x: T) -> T {
+		let (zero, one) = (T::zero(), T::one());
+		match self {
+			WrapMode::Repeat => {
+				// Tiny negative coordinates round up to one
+				let t = x - x.floor();
+				if t >= one { zero } else { t }
+			},
+			WrapMode::Clamp => x.max(zero).min(one),
+			WrapMode::Mirror => {
+				// Distance to the nearest even integer
+				let two = one + one;
+				let t = x - (x / two).floor() * two;
+				if t > one { two - t } else { t }
+			},
+			WrapMode::MirrorOnce => x.abs().min(one),
+		}
+	}
+}
+
+/// Wraps the texture coordinates with the repeat mode.
+///
+/// The result is in the range `[0, 1)`.
+pub fn repeat<T: Float>(uv: Vec2<T>) -> Vec2<T> {
+	uv.map(|x| WrapMode::Repeat.wrap(x))
+}
+
+/// Wraps the texture coordinates with the clamp to edge mode.
+pub fn clamp<T: Float>(uv: Vec2<T>) -> Vec2<T> {
+	uv.map(|x| WrapMode::Clamp.wrap(x))
+}
+
+/// Wraps the texture coordinates with the mirrored repeat mode.
+pub fn mirror<T: Float>(uv: Vec2<T>) -> Vec2<T> {
+	uv.map(|x| WrapMode::Mirror.wrap(x))
+}
+
+/// Wraps the texture coordinates with a mode per component.
+pub fn apply<T: Float>(uv: Vec2<T>, modes: Vec2<WrapMode>) -> Vec2<T> {
+	Vec2 {
+		x: modes.x.wrap(uv.x),
+		y: modes.y.wrap(uv.y),
+	}
+}
+
+//----------------------------------------------------------------
+// Pixel coordinates
+
+fn texture_size<T: Float>(size: Vec2<u32>) -> Vec2<T> {
+	Vec2 {
+		x: T::cast_from(size.x as f64),
+		y: T::cast_from(size.y as f64),
+	}
+}
+
+/// Converts normalized texture coordinates to pixel coordinates.
+pub fn uv_to_pixel<T: Float>(uv: Vec2<T>, size: Vec2<u32>, flip_y: bool) -> Vec2<T> {
+	let size = texture_size::<T>(size);
+	let y = if flip_y { T::one() - uv.y } else { uv.y };
+	Vec2 { x: uv.x * size.x, y: y * size.y }
+}
+
+/// Converts pixel coordinates to normalized texture coordinates.
+pub fn pixel_to_uv<T: Float>(pixel: Vec2<T>, size: Vec2<u32>, flip_y: bool) -> Vec2<T> {
+	let size = texture_size::<T>(size);
+	let y = pixel.y / size.y;
+	Vec2 { x: pixel.x / size.x, y: if flip_y { T::one() - y } else { y } }
+}
+
+/// Returns the index of the texel containing the normalized texture coordinates.
+///
+/// The index is not wrapped, coordinates outside the texture return indices outside the texture.
+pub fn uv_to_texel<T: Float>(uv: Vec2<T>, size: Vec2<u32>, flip_y: bool) -> Vec2<i32> where i32: CastFrom<T> {
+	let pixel = uv_to_pixel(uv, size, flip_y);
+	Vec2 {
+		x: i32::cast_from(pixel.x.floor()),
+		y: i32::cast_from(pixel.y.floor()),
+	}
+}
+
+/// Returns the normalized texture coordinates of the center of the texel.
+pub fn texel_center_uv<T: Float>(texel: Vec2<i32>, size: Vec2<u32>, flip_y: bool) -> Vec2<T> {
+	let half = T::one() / (T::one() + T::one());
+	let pixel = Vec2 {
+		x: T::cast_from(texel.x as f64) + half,
+		y: T::cast_from(texel.y as f64) + half,
+	};
+	pixel_to_uv(pixel, size, flip_y)
+}
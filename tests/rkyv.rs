@@ -0,0 +1,30 @@
+#![cfg(feature = "rkyv")]
+
+extern crate cvmath;
+extern crate rkyv;
+
+use cvmath::prelude::*;
+use cvmath::vec::ArchivedVec3;
+use rkyv::rancor::Error;
+
+#[test]
+fn archive_points() {
+	let points = vec![Vec3(1.0f32, 2.0, 3.0), Vec3(-4.0, 5.5, 0.25)];
+	let bytes = rkyv::to_bytes::<Error>(&points).unwrap();
+
+	// Access the points in place without deserializing
+	let archived = rkyv::access::<rkyv::vec::ArchivedVec<ArchivedVec3<f32>>, Error>(&bytes).unwrap();
+	assert_eq!(2, archived.len());
+	assert_eq!(5.5, archived[1].y);
+
+	let deserialized = rkyv::from_bytes::<Vec<Vec3<f32>>, Error>(&bytes).unwrap();
+	assert_eq!(points, deserialized);
+}
+
+#[test]
+fn archive_transform() {
+	let value = (Mat3::<f64>::identity(), Rect(Point2(1, 2), Point2(3, 4)), Deg(90.0f32));
+	let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+	let deserialized = rkyv::from_bytes::<(Mat3<f64>, Rect<i32>, Deg<f32>), Error>(&bytes).unwrap();
+	assert_eq!(value, deserialized);
+}